@@ -0,0 +1,77 @@
+//! 行列の積のベンチマーク / matrix product benchmark
+//!
+//! ブロック化した `Mul` 実装を素朴な三重ループと比較し、結果の一致と所要時間を表示する。
+//! `cargo bench --features parallel` では並列版 `par_prod` も計測する。
+//!
+//! ```sh
+//! cargo bench --bench matmul -- 1000
+//! ```
+
+use matrixa::core::Matrix;
+use std::time::Instant;
+
+fn naive(a: &Matrix<f64>, b: &Matrix<f64>) -> Vec<Vec<f64>> {
+    let mut res = vec![vec![0.0; b.cols()]; a.rows()];
+    for i in 0..a.rows() {
+        for k in 0..a.cols() {
+            for j in 0..b.cols() {
+                res[i][j] += a.data[i][k] * b.data[k][j];
+            }
+        }
+    }
+    res
+}
+
+fn generate(n: usize, seed: u64) -> Matrix<f64> {
+    let mut state = seed;
+    let mut m = Matrix::<f64>::new();
+    for _ in 0..n {
+        let mut row = Vec::with_capacity(n);
+        for _ in 0..n {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            row.push((state >> 33) as f64 / (1u64 << 31) as f64);
+        }
+        m.push(row).unwrap();
+    }
+    m
+}
+
+fn main() {
+    let n = std::env::args()
+        .skip(1)
+        .find_map(|arg| arg.parse::<usize>().ok())
+        .unwrap_or(500);
+    let a = generate(n, 1);
+    let b = generate(n, 2);
+
+    let start = Instant::now();
+    let expected = naive(&a, &b);
+    let naive_time = start.elapsed();
+
+    let start = Instant::now();
+    let res = &a * &b;
+    let blocked_time = start.elapsed();
+
+    assert_eq!(res.data, expected);
+    println!("{n}x{n} f64 matrix product");
+    println!("naive:   {:?}", naive_time);
+    println!("blocked: {:?}", blocked_time);
+    println!(
+        "speedup: {:.2}x",
+        naive_time.as_secs_f64() / blocked_time.as_secs_f64()
+    );
+
+    #[cfg(feature = "parallel")]
+    {
+        let start = Instant::now();
+        let res = a.par_prod(b).unwrap();
+        let parallel_time = start.elapsed();
+
+        assert_eq!(res.data, expected);
+        println!("parallel: {:?}", parallel_time);
+        println!(
+            "speedup: {:.2}x",
+            naive_time.as_secs_f64() / parallel_time.as_secs_f64()
+        );
+    }
+}
@@ -0,0 +1,366 @@
+//! ビット行列 / bit-packed boolean matrices
+//!
+//! 論理行列を各行 u64 ワードの列に詰めて保持する `BitMatrix` を定義する。
+//! `Matrix<bool>` が1要素あたり1バイトを要するのに対し、1要素あたり1ビットで保持し、
+//! 論理演算 `&`, `|`, `^`, `!` をワード単位で計算する。
+//!
+//! ```rust
+//! use matrixa::core::Matrix;
+//! use matrixa::mat;
+//! use matrixa::bitmatrix::BitMatrix;
+//!
+//! let a = BitMatrix::from(&mat![bool: [true, false, true], [false, true, true]]);
+//! let b = BitMatrix::from(&mat![bool: [true, true, false], [false, true, false]]);
+//! let c = &a & &b;
+//! assert_eq!(c.to_matrix() == mat![bool: [true, false, false], [false, true, false]], true);
+//! assert_eq!(a.count_ones(), 4);
+//! assert_eq!((!&a).count_ones_per_col(), vec![1, 1, 0]);
+//! ```
+//!
+
+use crate::core::Matrix;
+use std::ops::Not;
+
+const WORD: usize = u64::BITS as usize;
+
+/// ビット行列 / bit-packed boolean matrix
+///
+/// 各行は `words_per_row` 個の u64 に詰めて連続領域に保持する。
+/// 列数を超える末尾のビットは常に0に保つ。
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitMatrix {
+    rows: usize,
+    cols: usize,
+    words_per_row: usize,
+    data: Vec<u64>,
+}
+
+impl BitMatrix {
+    /// 全要素falseのビット行列を生成する
+    ///
+    pub fn new(rows: usize, cols: usize) -> Self {
+        let words_per_row = cols.div_ceil(WORD);
+        BitMatrix {
+            rows,
+            cols,
+            words_per_row,
+            data: vec![0; rows * words_per_row],
+        }
+    }
+
+    /// 行数
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// 列数
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// (i, j) 要素の取得
+    ///
+    pub fn get(&self, i: usize, j: usize) -> bool {
+        self.bounds_check(i, j);
+        self.data[i * self.words_per_row + j / WORD] >> (j % WORD) & 1 == 1
+    }
+
+    /// (i, j) 要素の設定
+    ///
+    pub fn set(&mut self, i: usize, j: usize, v: bool) {
+        self.bounds_check(i, j);
+        let word = &mut self.data[i * self.words_per_row + j / WORD];
+        if v {
+            *word |= 1 << (j % WORD);
+        } else {
+            *word &= !(1 << (j % WORD));
+        }
+    }
+
+    /// i 行目のワード列
+    ///
+    pub fn row_words(&self, i: usize) -> &[u64] {
+        &self.data[i * self.words_per_row..(i + 1) * self.words_per_row]
+    }
+
+    /// i 行目にワード列 src を論理和で加える
+    ///
+    pub(crate) fn or_row(&mut self, i: usize, src: &[u64]) {
+        let start = i * self.words_per_row;
+        for (w, s) in self.data[start..start + self.words_per_row].iter_mut().zip(src.iter()) {
+            *w |= s;
+        }
+    }
+
+    /// trueである要素の総数
+    ///
+    pub fn count_ones(&self) -> usize {
+        self.data.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// 行ごとのtrueである要素の数
+    ///
+    pub fn count_ones_per_row(&self) -> Vec<usize> {
+        (0..self.rows)
+            .map(|i| self.row_words(i).iter().map(|w| w.count_ones() as usize).sum())
+            .collect()
+    }
+
+    /// 列ごとのtrueである要素の数
+    ///
+    pub fn count_ones_per_col(&self) -> Vec<usize> {
+        let mut res = vec![0; self.cols];
+        for i in 0..self.rows {
+            for (k, word) in self.row_words(i).iter().enumerate() {
+                let mut w = *word;
+                while w != 0 {
+                    res[k * WORD + w.trailing_zeros() as usize] += 1;
+                    w &= w - 1;
+                }
+            }
+        }
+        res
+    }
+
+    /// `Matrix<bool>` への変換
+    ///
+    pub fn to_matrix(&self) -> Matrix<bool> {
+        let mut res = Matrix::new();
+        for i in 0..self.rows {
+            res.data.push((0..self.cols).map(|j| self.get(i, j)).collect());
+        }
+        res
+    }
+
+    fn bounds_check(&self, i: usize, j: usize) {
+        if i >= self.rows || j >= self.cols {
+            panic!("index ({}, {}) out of range for {}x{} bit matrix", i, j, self.rows, self.cols);
+        }
+    }
+
+    fn has_same_size_with(&self, other: &Self) -> bool {
+        self.rows == other.rows && self.cols == other.cols
+    }
+
+    /// 列数を超える末尾のビットを0に戻す
+    fn clear_tail(&mut self) {
+        let rem = self.cols % WORD;
+        if rem == 0 {
+            return;
+        }
+        let mask = (1u64 << rem) - 1;
+        for row in self.data.chunks_mut(self.words_per_row) {
+            if let Some(last) = row.last_mut() {
+                *last &= mask;
+            }
+        }
+    }
+}
+
+/// `Matrix<bool>` からの変換
+///
+/// 空の行列は 0x0 のビット行列に変換する。
+/// 行の長さが揃わない行列を指定した場合はパニックする。
+///
+impl From<&Matrix<bool>> for BitMatrix {
+    fn from(m: &Matrix<bool>) -> Self {
+        if m.data.is_empty() {
+            return BitMatrix::new(0, 0);
+        }
+        m.integrity_check().unwrap();
+        let mut res = BitMatrix::new(m.rows(), m.cols());
+        for (i, row) in m.data.iter().enumerate() {
+            for (j, _) in row.iter().enumerate().filter(|(_, v)| **v) {
+                res.data[i * res.words_per_row + j / WORD] |= 1 << (j % WORD);
+            }
+        }
+        res
+    }
+}
+
+impl From<&BitMatrix> for Matrix<bool> {
+    fn from(m: &BitMatrix) -> Self {
+        m.to_matrix()
+    }
+}
+
+macro_rules! impl_bitmatrix_op {
+    ( $(#[$attr:meta])* $op:ident, $method:ident, $op_assign:ident, $method_assign:ident ) => {
+        $(#[$attr])*
+        impl std::ops::$op for BitMatrix {
+            type Output = BitMatrix;
+            fn $method(mut self, other: BitMatrix) -> BitMatrix {
+                std::ops::$op_assign::$method_assign(&mut self, &other);
+                self
+            }
+        }
+
+        impl std::ops::$op<&BitMatrix> for BitMatrix {
+            type Output = BitMatrix;
+            fn $method(mut self, other: &BitMatrix) -> BitMatrix {
+                std::ops::$op_assign::$method_assign(&mut self, other);
+                self
+            }
+        }
+
+        impl std::ops::$op<BitMatrix> for &BitMatrix {
+            type Output = BitMatrix;
+            fn $method(self, other: BitMatrix) -> BitMatrix {
+                let mut res = self.clone();
+                std::ops::$op_assign::$method_assign(&mut res, &other);
+                res
+            }
+        }
+
+        impl std::ops::$op<&BitMatrix> for &BitMatrix {
+            type Output = BitMatrix;
+            fn $method(self, other: &BitMatrix) -> BitMatrix {
+                let mut res = self.clone();
+                std::ops::$op_assign::$method_assign(&mut res, other);
+                res
+            }
+        }
+
+        impl std::ops::$op_assign<BitMatrix> for BitMatrix {
+            fn $method_assign(&mut self, other: BitMatrix) {
+                std::ops::$op_assign::$method_assign(self, &other);
+            }
+        }
+
+        impl std::ops::$op_assign<&BitMatrix> for BitMatrix {
+            fn $method_assign(&mut self, other: &BitMatrix) {
+                if !self.has_same_size_with(other) {
+                    panic!("abort");
+                }
+                for (w, o) in self.data.iter_mut().zip(other.data.iter()) {
+                    std::ops::$op_assign::$method_assign(w, *o);
+                }
+            }
+        }
+    };
+}
+
+impl_bitmatrix_op!(
+    /// 論理積 / logical product
+    ///
+    BitAnd, bitand, BitAndAssign, bitand_assign
+);
+
+impl_bitmatrix_op!(
+    /// 論理和 / logical sum
+    ///
+    BitOr, bitor, BitOrAssign, bitor_assign
+);
+
+impl_bitmatrix_op!(
+    /// 排他的論理和 / exclusive logical sum
+    ///
+    BitXor, bitxor, BitXorAssign, bitxor_assign
+);
+
+/// 否定 / negation
+///
+impl Not for BitMatrix {
+    type Output = Self;
+    fn not(mut self) -> Self::Output {
+        for w in self.data.iter_mut() {
+            *w = !*w;
+        }
+        self.clear_tail();
+        self
+    }
+}
+
+impl Not for &BitMatrix {
+    type Output = BitMatrix;
+    fn not(self) -> Self::Output {
+        !self.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests_bitmatrix {
+    use crate::bitmatrix::BitMatrix;
+    use crate::core::Matrix;
+    use crate::mat;
+
+    fn pattern(rows: usize, cols: usize, f: impl Fn(usize, usize) -> bool) -> Matrix<bool> {
+        let mut m = Matrix::new();
+        for i in 0..rows {
+            m.push((0..cols).map(|j| f(i, j)).collect()).unwrap();
+        }
+        m
+    }
+
+    #[test]
+    fn test_conversion() {
+        let m = pattern(5, 130, |i, j| (i * 7 + j) % 3 == 0);
+        let b = BitMatrix::from(&m);
+        assert_eq!(b.rows(), 5);
+        assert_eq!(b.cols(), 130);
+        assert_eq!(b.row_words(0).len(), 3);
+        assert_eq!(b.to_matrix() == m, true);
+        assert_eq!(Matrix::from(&b) == m, true);
+
+        let empty = BitMatrix::from(&Matrix::<bool>::new());
+        assert_eq!(empty, BitMatrix::new(0, 0));
+        assert_eq!(Matrix::from(&empty).data.len(), 0);
+    }
+
+    #[test]
+    fn test_get_set() {
+        let mut b = BitMatrix::new(2, 70);
+        b.set(1, 65, true);
+        b.set(0, 3, true);
+        assert!(b.get(1, 65));
+        assert!(!b.get(1, 64));
+        b.set(1, 65, false);
+        assert!(!b.get(1, 65));
+        assert_eq!(b.count_ones(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_out_of_range() {
+        BitMatrix::new(2, 3).get(0, 3);
+    }
+
+    #[test]
+    fn test_logic_ops() {
+        let ma = pattern(3, 100, |i, j| (i + j) % 2 == 0);
+        let mb = pattern(3, 100, |_, j| j % 3 == 0);
+        let (a, b) = (BitMatrix::from(&ma), BitMatrix::from(&mb));
+        assert_eq!((&a & &b).to_matrix() == (&ma & &mb), true);
+        assert_eq!((&a | &b).to_matrix() == (&ma | &mb), true);
+        assert_eq!((&a ^ &b).to_matrix() == (&ma ^ &mb), true);
+        assert_eq!((!&a).to_matrix() == !&ma, true);
+        assert_eq!((!a.clone()).count_ones(), 300 - a.count_ones());
+
+        let mut c = a.clone();
+        c ^= &a;
+        assert_eq!(c.count_ones(), 0);
+        c |= b.clone();
+        assert_eq!(c == b, true);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_unmatched() {
+        let _ = &BitMatrix::new(2, 3) & &BitMatrix::new(3, 2);
+    }
+
+    #[test]
+    fn test_count_ones() {
+        let m = mat![bool: [true, true, false], [false, true, false], [true, true, true]];
+        let b = BitMatrix::from(&m);
+        assert_eq!(b.count_ones(), 6);
+        assert_eq!(b.count_ones_per_row(), vec![2, 1, 3]);
+        assert_eq!(b.count_ones_per_col(), vec![2, 3, 1]);
+
+        let wide = BitMatrix::from(&pattern(4, 200, |i, j| j % 50 == i));
+        assert_eq!(wide.count_ones_per_row(), vec![4, 4, 4, 4]);
+        assert_eq!(wide.count_ones_per_col().iter().sum::<usize>(), 16);
+        assert_eq!(wide.count_ones_per_col()[151], 1);
+    }
+}
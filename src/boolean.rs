@@ -1,72 +1,183 @@
-use crate::core::Matrix;
-use crate::mat;
-use std::ops::{BitAnd, BitOr, BitXor, Not};
+use crate::bitmatrix::BitMatrix;
+use crate::core::{impl_elementwise_op, Matrix};
+use std::ops::Not;
 
-/// 論理積 / logical product
+impl_elementwise_op!(
+    /// 論理積 / logical product
+    ///
+    /// ２つの論理行列の論理積を計算し、新規Matrixインスタンスとして返却する。
+    ///
+    BitAnd, bitand, BitAndAssign, bitand_assign
+);
+
+impl_elementwise_op!(
+    /// 論理和 / logical sum
+    ///
+    /// 2つの論理行列の論理和を計算し、新規Matrixインスタンスとして返却する
+    ///
+    BitOr, bitor, BitOrAssign, bitor_assign
+);
+
+impl_elementwise_op!(
+    /// 排他的論理和 / exclusive logical sum
+    ///
+    /// 2つの論理行列の排他的論理和を計算し、新規Matrixインスタンスとして返却する
+    ///
+    BitXor, bitxor, BitXorAssign, bitxor_assign
+);
+
+/// 否定 / negation
 ///
-/// ２つの論理行列の論理積を計算し、新規Matrixインスタンスとして返却する。
+/// 論理行列の各要素について真偽値を反転し、新規Matrixインスタンスとして返却する。
+/// 所有値に対しては既存のバッファ上で反転を行う。
 ///
-impl<T: Copy + std::ops::BitAnd<Output = T>> BitAnd for Matrix<T> {
+impl<T: Copy + std::ops::Not<Output = T>> Not for Matrix<T> {
     type Output = Self;
-    fn bitand(self, other: Self) -> Self::Output {
-        let mut res = self.clone();
-        for i in 0..res.data.len() {
-            for j in 0..res.data[i].len() {
-                res.data[i][j] = res.data[i][j] & other.data[i][j]
+    fn not(mut self) -> Self::Output {
+        for row in self.data.iter_mut() {
+            for datum in row.iter_mut() {
+                *datum = !*datum;
             }
         }
-        res
+        self
     }
 }
 
-/// 論理和 / logical sum
-///
-/// 2つの論理行列の論理和を計算し、新規Matrixインスタンスとして返却する
-///
-impl<T: Copy + std::ops::BitOr<Output = T>> BitOr for Matrix<T> {
-    type Output = Self;
-    fn bitor(self, other: Self) -> Self::Output {
-        let mut res = self.clone();
-        for i in 0..res.data.len() {
-            for j in 0..res.data[i].len() {
-                res.data[i][j] = res.data[i][j] | other.data[i][j]
-            }
-        }
-        res
+impl<T: Copy + std::ops::Not<Output = T>> Not for &Matrix<T> {
+    type Output = Matrix<T>;
+    fn not(self) -> Self::Output {
+        !self.clone()
     }
 }
 
-/// 排他的論理和 / exclusive logical sum
+/// 論理行列の積と二項関係 / boolean semiring product and binary relations
 ///
-/// 2つの論理行列の排他的論理和を計算し、新規Matrixインスタンスとして返却する
+/// 正方論理行列を集合上の二項関係 R とみなし、(i, j) 要素がtrueであることを i R j と解釈する。
+/// 積と閉包は各行を `BitMatrix` のワード列に詰めて計算する。
 ///
-impl<T: Copy + std::ops::BitXor<Output = T>> BitXor for Matrix<T> {
-    type Output = Self;
-    fn bitxor(self, other: Self) -> Self::Output {
-        let mut res = self.clone();
-        for i in 0..res.data.len() {
-            for j in 0..res.data[i].len() {
-                res.data[i][j] = res.data[i][j] ^ other.data[i][j]
+impl Matrix<bool> {
+    /// 論理積和による行列積
+    ///
+    /// (i, j) 要素を self の i 行と other の j 列の論理積の論理和とした行列を返却する。
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    /// use matrixa::mat;
+    ///
+    /// let a = mat![bool: [true, false], [true, true]];
+    /// let b = mat![bool: [false, true, false], [true, false, false]];
+    /// let c = a.bool_prod(&b).unwrap();
+    /// assert_eq!(c == mat![bool: [false, true, false], [true, true, false]], true);
+    /// ```
+    ///
+    pub fn bool_prod(&self, other: &Matrix<bool>) -> Result<Self, &str> {
+        self.integrity_check()?;
+        other
+            .integrity_check()
+            .map_err(|_| "argument matrix is empty or corrupted")?;
+        if self.cols() != other.rows() {
+            return Err("col length not matched to the row length of argument");
+        }
+        let rhs = BitMatrix::from(other);
+        let mut res = BitMatrix::new(self.rows(), other.cols());
+        for (i, row) in self.data.iter().enumerate() {
+            for (k, _) in row.iter().enumerate().filter(|(_, v)| **v) {
+                res.or_row(i, rhs.row_words(k));
             }
         }
-        res
+        Ok(res.to_matrix())
     }
-}
 
-/// 否定 / negation
-///
-/// 論理行列の各要素について真偽値を反転し、新規Matrixインスタンスとして返却する。
-///
-impl<T: Copy + std::ops::Not<Output = T>> Not for Matrix<T> {
-    type Output = Self;
-    fn not(self) -> Self::Output {
-        let mut res = self.clone();
-        for i in 0..res.data.len() {
-            for j in 0..res.data[i].len() {
-                res.data[i][j] = !res.data[i][j]
+    /// 推移閉包
+    ///
+    /// Warshall法により、関係を含む最小の推移的関係を返却する。
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    /// use matrixa::mat;
+    ///
+    /// let r = mat![bool: [false, true, false], [false, false, true], [false, false, false]];
+    /// let t = r.transitive_closure().unwrap();
+    /// assert_eq!(t == mat![bool: [false, true, true], [false, false, true], [false, false, false]], true);
+    /// ```
+    ///
+    pub fn transitive_closure(&self) -> Result<Self, &str> {
+        self.integrity_check()?;
+        self.is_square()?;
+        let mut res = BitMatrix::from(self);
+        for k in 0..res.rows() {
+            let row_k = res.row_words(k).to_vec();
+            for i in 0..res.rows() {
+                if res.get(i, k) {
+                    res.or_row(i, &row_k);
+                }
             }
         }
-        res
+        Ok(res.to_matrix())
+    }
+
+    /// 反射推移閉包
+    ///
+    /// 推移閉包に対角要素を加えた関係を返却する。
+    ///
+    pub fn reflexive_transitive_closure(&self) -> Result<Self, &str> {
+        let mut res = self.transitive_closure()?;
+        for i in 0..res.rows() {
+            res.data[i][i] = true;
+        }
+        Ok(res)
+    }
+
+    fn is_relation(&self) -> bool {
+        self.integrity_check().is_ok() && self.is_square().is_ok()
+    }
+
+    /// 反射律 (全ての i について i R i)
+    ///
+    pub fn is_reflexive(&self) -> bool {
+        self.is_relation() && (0..self.rows()).all(|i| self.data[i][i])
+    }
+
+    /// 対称律 (i R j ならば j R i)
+    ///
+    pub fn is_symmetric(&self) -> bool {
+        self.is_relation()
+            && (0..self.rows()).all(|i| (0..i).all(|j| self.data[i][j] == self.data[j][i]))
+    }
+
+    /// 反対称律 (i R j かつ j R i ならば i = j)
+    ///
+    pub fn is_antisymmetric(&self) -> bool {
+        self.is_relation()
+            && (0..self.rows()).all(|i| (0..i).all(|j| !(self.data[i][j] && self.data[j][i])))
+    }
+
+    /// 推移律 (i R j かつ j R k ならば i R k)
+    ///
+    pub fn is_transitive(&self) -> bool {
+        self.is_relation() && self.transitive_closure().is_ok_and(|t| t == *self)
+    }
+
+    /// 同値関係 (反射律、対称律、推移律)
+    ///
+    pub fn is_equivalence(&self) -> bool {
+        self.is_reflexive() && self.is_symmetric() && self.is_transitive()
+    }
+
+    /// 半順序関係 (反射律、反対称律、推移律)
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    /// use matrixa::mat;
+    ///
+    /// // {1, 2, 3} 上の整除関係
+    /// let divides = mat![bool: [true, true, true], [false, true, false], [false, false, true]];
+    /// assert!(divides.is_partial_order());
+    /// assert!(!divides.is_equivalence());
+    /// ```
+    ///
+    pub fn is_partial_order(&self) -> bool {
+        self.is_reflexive() && self.is_antisymmetric() && self.is_transitive()
     }
 }
 
@@ -135,6 +246,45 @@ mod tests_matrix_boolean_operator {
         assert_eq!((b ^ v) == res, true);
     }
 
+    #[test]
+    fn test_operator_on_references(){
+        let b = mat![
+            bool:
+            [true,true],
+            [false,true]
+        ];
+        let v = mat![
+            bool:
+            [false,true],
+            [false,true]
+        ];
+        assert_eq!(&b & &v == mat![bool: [false,true],[false,true]], true);
+        assert_eq!(&b | &v == mat![bool: [true,true],[false,true]], true);
+        assert_eq!(&b ^ v.clone() == mat![bool: [true,false],[false,false]], true);
+        assert_eq!(!&b == mat![bool: [false,false],[true,false]], true);
+        assert_eq!(b.rows(), 2);
+    }
+
+    #[test]
+    fn test_compound_assignment(){
+        let mut b = mat![
+            bool:
+            [true,true],
+            [false,true]
+        ];
+        let v = mat![
+            bool:
+            [false,true],
+            [false,true]
+        ];
+        b ^= &v;
+        assert_eq!(b == mat![bool: [true,false],[false,false]], true);
+        b |= &v;
+        assert_eq!(b == mat![bool: [true,true],[false,true]], true);
+        b &= v;
+        assert_eq!(b == mat![bool: [false,true],[false,true]], true);
+    }
+
     #[test]
     fn test_not(){
         let b = mat![
@@ -149,4 +299,87 @@ mod tests_matrix_boolean_operator {
         ];
         assert_eq!(!b == res, true);
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests_matrix_boolean_relation {
+    use crate::core::Matrix;
+    use crate::mat;
+
+    fn naive_prod(a: &Matrix<bool>, b: &Matrix<bool>) -> Matrix<bool> {
+        let mut res = Matrix::new();
+        for i in 0..a.rows() {
+            res.push((0..b.cols()).map(|j| (0..a.cols()).any(|k| a.dump()[i][k] && b.dump()[k][j])).collect())
+                .unwrap();
+        }
+        res
+    }
+
+    #[test]
+    fn test_bool_prod() {
+        let mut a = Matrix::new();
+        let mut b = Matrix::new();
+        for i in 0..5 {
+            a.push((0..70).map(|j| (i * 3 + j) % 7 == 0).collect()).unwrap();
+        }
+        for i in 0..70 {
+            b.push((0..90).map(|j| (i + 2 * j) % 11 == 0).collect()).unwrap();
+        }
+        assert_eq!(a.bool_prod(&b).unwrap() == naive_prod(&a, &b), true);
+        assert!(b.bool_prod(&a).is_err());
+
+        let mut ragged = a.clone();
+        ragged.data[1].pop();
+        assert!(ragged.bool_prod(&b).is_err());
+        assert!(Matrix::<bool>::new().bool_prod(&b).is_err());
+        assert!(a.bool_prod(&Matrix::new()).is_err());
+    }
+
+    #[test]
+    fn test_closure() {
+        // 0 -> 1 -> 2 -> 0, 3 -> 0
+        let r = mat![
+            bool:
+            [false, true, false, false],
+            [false, false, true, false],
+            [true, false, false, false],
+            [true, false, false, false]
+        ];
+        let t = r.transitive_closure().unwrap();
+        for i in 0..4 {
+            for j in 0..4 {
+                assert_eq!(t.dump()[i][j], j != 3);
+            }
+        }
+        assert!(t.is_transitive());
+        assert!(!r.is_transitive());
+
+        let rt = mat![bool: [false, true], [false, false]].reflexive_transitive_closure().unwrap();
+        assert_eq!(rt == mat![bool: [true, true], [false, true]], true);
+        assert!(mat![bool: [true, false]].transitive_closure().is_err());
+    }
+
+    #[test]
+    fn test_relation_predicates() {
+        let eq = mat![bool: [true, true, false], [true, true, false], [false, false, true]];
+        assert!(eq.is_reflexive());
+        assert!(eq.is_symmetric());
+        assert!(!eq.is_antisymmetric());
+        assert!(eq.is_transitive());
+        assert!(eq.is_equivalence());
+        assert!(!eq.is_partial_order());
+
+        let le = mat![bool: [true, true, true], [false, true, true], [false, false, true]];
+        assert!(le.is_partial_order());
+        assert!(!le.is_symmetric());
+
+        let lt = mat![bool: [false, true, true], [false, false, true], [false, false, false]];
+        assert!(!lt.is_reflexive());
+        assert!(lt.is_antisymmetric());
+        assert!(lt.is_transitive());
+
+        let rect = mat![bool: [true, true]];
+        assert!(!rect.is_reflexive());
+        assert!(!rect.is_symmetric());
+        assert!(!rect.is_transitive());
+    }
+}
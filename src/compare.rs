@@ -0,0 +1,222 @@
+//! 比較演算 / element-wise comparisons
+//!
+//! 各元の比較結果を `Matrix<bool>` のマスクとして返却する。
+//! 比較の相手には同じ大きさの行列 `&Matrix<T>` またはスカラー値 `T` を指定でき、
+//! 結果は boolean.rs の `&`, `|`, `!` や mask.rs の `select` 等と組み合わせて利用できる。
+//! 行列の大きさが一致しない場合はpanicする。
+//!
+//! ```rust
+//! use matrixa::core::Matrix;
+//! use matrixa::mat;
+//!
+//! let m = mat![f64: [1.0, f64::NAN, 3.0], [-4.0, 5.0, f64::INFINITY]];
+//! let valid = m.is_finite() & m.ge_elem(0.0);
+//! assert_eq!(valid == mat![bool: [true, false, true], [false, true, false]], true);
+//! assert_eq!(m.select(&valid), vec![1.0, 3.0, 5.0]);
+//! ```
+//!
+
+use crate::core::Matrix;
+
+/// 比較の相手 / right-hand operand of comparisons
+///
+/// 同じ大きさの行列、または全ての元と比較するスカラー値。
+///
+pub trait CompareOperand<T> {
+    /// (i, j) 要素と比較する値
+    fn value_at(&self, i: usize, j: usize) -> &T;
+
+    /// 大きさの検証
+    fn size_matches(&self, m: &Matrix<T>) -> bool;
+}
+
+impl<T> CompareOperand<T> for T {
+    fn value_at(&self, _: usize, _: usize) -> &T {
+        self
+    }
+
+    fn size_matches(&self, _: &Matrix<T>) -> bool {
+        true
+    }
+}
+
+impl<T> CompareOperand<T> for &Matrix<T> {
+    fn value_at(&self, i: usize, j: usize) -> &T {
+        &self.data[i][j]
+    }
+
+    fn size_matches(&self, m: &Matrix<T>) -> bool {
+        self.data.len() == m.data.len()
+            && self.data.iter().zip(m.data.iter()).all(|(a, b)| a.len() == b.len())
+    }
+}
+
+impl<T> Matrix<T> {
+    fn compare<O, F>(&self, other: O, f: F) -> Matrix<bool>
+    where
+        O: CompareOperand<T>,
+        F: Fn(&T, &T) -> bool,
+    {
+        if !other.size_matches(self) {
+            panic!("operand size not matched to the matrix");
+        }
+        let mut res = Matrix::new();
+        for (i, row) in self.data.iter().enumerate() {
+            res.data.push(row.iter().enumerate().map(|(j, v)| f(v, other.value_at(i, j))).collect());
+        }
+        res
+    }
+}
+
+/// 等値比較 / equality comparisons
+///
+impl<T: PartialEq> Matrix<T> {
+    /// 各元が等しいか
+    ///
+    pub fn eq_elem<O: CompareOperand<T>>(&self, other: O) -> Matrix<bool> {
+        self.compare(other, |a, b| a == b)
+    }
+
+    /// 各元が等しくないか
+    ///
+    pub fn ne_elem<O: CompareOperand<T>>(&self, other: O) -> Matrix<bool> {
+        self.compare(other, |a, b| a != b)
+    }
+}
+
+/// 大小比較 / ordering comparisons
+///
+/// 比較不能な元(NaN等)との比較結果はfalseとなる。
+/// `Matrix<T>` は `Iterator` を実装しており `lt` 等の名前は `Iterator` のメソッドが優先されるため、
+/// `_elem` を付した名前とする。
+///
+impl<T: PartialOrd> Matrix<T> {
+    /// 各元が小さいか
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    /// use matrixa::mat;
+    ///
+    /// let a = mat![i32: [1, 5], [3, 2]];
+    /// let b = mat![i32: [2, 5], [1, 2]];
+    /// assert_eq!(a.lt_elem(&b) == mat![bool: [true, false], [false, false]], true);
+    /// assert_eq!(a.lt_elem(3) == mat![bool: [true, false], [false, true]], true);
+    /// ```
+    ///
+    pub fn lt_elem<O: CompareOperand<T>>(&self, other: O) -> Matrix<bool> {
+        self.compare(other, |a, b| a < b)
+    }
+
+    /// 各元が以下か
+    ///
+    pub fn le_elem<O: CompareOperand<T>>(&self, other: O) -> Matrix<bool> {
+        self.compare(other, |a, b| a <= b)
+    }
+
+    /// 各元が大きいか
+    ///
+    pub fn gt_elem<O: CompareOperand<T>>(&self, other: O) -> Matrix<bool> {
+        self.compare(other, |a, b| a > b)
+    }
+
+    /// 各元が以上か
+    ///
+    pub fn ge_elem<O: CompareOperand<T>>(&self, other: O) -> Matrix<bool> {
+        self.compare(other, |a, b| a >= b)
+    }
+}
+
+impl<T: PartialOrd + Copy> Matrix<T> {
+    /// 各元を min 以上 max 以下の範囲に収めた新規インスタンスを返却する
+    ///
+    /// NaN 等の比較不能な元はそのまま保持する。
+    ///
+    pub fn clamp(&self, min: T, max: T) -> Matrix<T> {
+        if min > max {
+            panic!("clamp requires min <= max");
+        }
+        let mut res = Matrix::new();
+        for row in self.data.iter() {
+            res.data.push(
+                row.iter()
+                    .map(|v| if *v < min { min } else if *v > max { max } else { *v })
+                    .collect(),
+            );
+        }
+        res
+    }
+}
+
+macro_rules! impl_float_mask {
+    ($($t:ty),*) => {
+        $(
+            /// 浮動小数点数の判定 / floating point classification
+            ///
+            impl Matrix<$t> {
+                /// 各元がNaNであるか
+                pub fn is_nan(&self) -> Matrix<bool> {
+                    self.compare(0.0, |a, _| a.is_nan())
+                }
+
+                /// 各元が有限値であるか
+                pub fn is_finite(&self) -> Matrix<bool> {
+                    self.compare(0.0, |a, _| a.is_finite())
+                }
+            }
+        )*
+    };
+}
+
+impl_float_mask!(f32, f64);
+
+#[cfg(test)]
+mod tests_matrix_compare {
+    use crate::core::Matrix;
+    use crate::mat;
+
+    #[test]
+    fn test_equality() {
+        let a = mat![i32: [1, 2], [3, 4]];
+        let b = mat![i32: [1, 0], [3, 0]];
+        assert_eq!(a.eq_elem(&b) == mat![bool: [true, false], [true, false]], true);
+        assert_eq!(a.ne_elem(&b) == mat![bool: [false, true], [false, true]], true);
+        assert_eq!(a.eq_elem(4) == mat![bool: [false, false], [false, true]], true);
+
+        let s = mat![String: ["a".to_string(), "b".to_string()]];
+        assert_eq!(s.eq_elem("b".to_string()) == mat![bool: [false, true]], true);
+    }
+
+    #[test]
+    fn test_ordering() {
+        let a = mat![f64: [1.0, 2.0, f64::NAN]];
+        let b = mat![f64: [2.0, 2.0, 0.0]];
+        assert_eq!(a.lt_elem(&b) == mat![bool: [true, false, false]], true);
+        assert_eq!(a.le_elem(&b) == mat![bool: [true, true, false]], true);
+        assert_eq!(a.gt_elem(&b) == mat![bool: [false, false, false]], true);
+        assert_eq!(a.ge_elem(&b) == mat![bool: [false, true, false]], true);
+        assert_eq!((!a.lt_elem(1.5) & !a.is_nan()) == mat![bool: [false, true, false]], true);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_unmatched() {
+        mat![i32: [1, 2]].lt_elem(&mat![i32: [1], [2]]);
+    }
+
+    #[test]
+    fn test_clamp() {
+        let m = mat![i32: [-5, 0, 5], [10, 3, -1]];
+        assert_eq!(m.clamp(0, 4) == mat![i32: [0, 0, 4], [4, 3, 0]], true);
+        let f = mat![f32: [f32::NAN, 2.5, f32::NEG_INFINITY]].clamp(0.0, 1.0);
+        assert!(f.dump()[0][0].is_nan());
+        assert_eq!(f.dump()[0][1..], [1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_float_masks() {
+        let m = mat![f32: [0.0, f32::NAN], [f32::INFINITY, -1.0]];
+        assert_eq!(m.is_nan() == mat![bool: [false, true], [false, false]], true);
+        assert_eq!(m.is_finite() == mat![bool: [true, false], [false, true]], true);
+        assert_eq!(m.is_finite().count_true(), 2);
+    }
+}
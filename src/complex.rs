@@ -0,0 +1,362 @@
+//! 複素数 / complex numbers
+//!
+//! 行列の元として利用できる複素数型 `Complex<T>` と、複素行列向けの
+//! 共役、随伴(共役転置)、エルミート性・ユニタリ性判定を定義する。
+//! `Complex<T>` は num.rs の数値演算が要求する四則演算、`From<u8>`、`Display` を実装するため、
+//! `Matrix<Complex<f64>>` に対しても `+`, `*`, `det`, `inverse`, `tr` 等をそのまま利用できる。
+//!
+//! ```rust
+//! use matrixa::core::Matrix;
+//! use matrixa::mat;
+//! use matrixa::complex::Complex;
+//!
+//! let m = mat![
+//!     Complex<f64>:
+//!         [Complex::new(1.0, 1.0), Complex::new(0.0, 2.0)],
+//!         [Complex::new(3.0, 0.0), Complex::new(1.0, -1.0)]
+//! ];
+//! assert_eq!(m.det(), Complex::new(2.0, -6.0));
+//! assert_eq!(m.tr().unwrap(), Complex::new(2.0, 0.0));
+//! assert_eq!(m.adjoint().row(0), vec![Complex::new(1.0, -1.0), Complex::new(3.0, 0.0)]);
+//! ```
+//!
+
+use crate::core::Matrix;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// 複素数 / complex number
+///
+/// 実部 re と虚部 im を保持する。
+///
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Complex<T> {
+    pub re: T,
+    pub im: T,
+}
+
+impl<T> Complex<T> {
+    /// 複素数生成
+    ///
+    pub fn new(re: T, im: T) -> Self {
+        Complex { re, im }
+    }
+}
+
+impl<T> Complex<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + From<u8>,
+{
+    /// 虚数単位 i
+    pub fn i() -> Self {
+        Complex::new(T::from(0x0u8), T::from(0x1u8))
+    }
+
+    /// 共役複素数
+    pub fn conj(&self) -> Self {
+        Complex::new(self.re, T::from(0x0u8) - self.im)
+    }
+
+    /// 絶対値の二乗
+    pub fn norm_sqr(&self) -> T {
+        self.re * self.re + self.im * self.im
+    }
+}
+
+impl Complex<f64> {
+    /// 絶対値
+    pub fn abs(&self) -> f64 {
+        self.re.hypot(self.im)
+    }
+}
+
+impl Complex<f32> {
+    /// 絶対値
+    pub fn abs(&self) -> f32 {
+        self.re.hypot(self.im)
+    }
+}
+
+impl<T: From<u8>> From<u8> for Complex<T> {
+    fn from(v: u8) -> Self {
+        Complex::new(T::from(v), T::from(0x0u8))
+    }
+}
+
+impl<T: Add<Output = T>> Add for Complex<T> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Complex::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Complex<T> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Complex::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl<T> Mul for Complex<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        Complex::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+}
+
+impl<T> Div for Complex<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T>,
+{
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        let denom = other.re * other.re + other.im * other.im;
+        Complex::new(
+            (self.re * other.re + self.im * other.im) / denom,
+            (self.im * other.re - self.re * other.im) / denom,
+        )
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Complex<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Complex::new(-self.re, -self.im)
+    }
+}
+
+/// 文字列表現 / Display
+///
+/// `a+bi` もしくは `a-bi` の形式で表示する。精度指定 `{:.3}` は実部・虚部の双方に適用される。
+///
+/// ```rust
+/// use matrixa::complex::Complex;
+///
+/// assert_eq!(format!("{}", Complex::new(1, -2)), "1-2i");
+/// assert_eq!(format!("{:.2}", Complex::new(0.5, 0.25)), "0.50+0.25i");
+/// ```
+///
+impl<T> fmt::Display for Complex<T>
+where
+    T: Copy + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // 符号は比較ではなく書式化した結果から判定する (負のゼロを '-' とするため)
+        let im = match f.precision() {
+            Some(p) => format!("{:.*}", p, self.im),
+            None => format!("{}", self.im),
+        };
+        let (sign, im) = match im.strip_prefix('-') {
+            Some(abs) => ('-', abs),
+            None => ('+', im.as_str()),
+        };
+        match f.precision() {
+            Some(p) => write!(f, "{:.*}{}{}i", p, self.re, sign, im),
+            None => write!(f, "{}{}{}i", self.re, sign, im),
+        }
+    }
+}
+
+/// 複素行列用メソッド群 / methods for complex matrices
+///
+impl<T> Matrix<Complex<T>>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + PartialEq + From<u8> + fmt::Debug,
+{
+    /// 共役行列
+    ///
+    /// 各元を共役複素数とした新規インスタンスを返却する。
+    ///
+    pub fn conj(&self) -> Self {
+        let mut res = Matrix::new();
+        for row in self.data.iter() {
+            res.data.push(row.iter().map(|z| z.conj()).collect());
+        }
+        res
+    }
+
+    /// 随伴行列(共役転置)
+    ///
+    /// 共役転置行列を新規インスタンスとして返却する。
+    ///
+    pub fn adjoint(&self) -> Self {
+        let mut res = self.conj();
+        res.transpose();
+        res
+    }
+
+    /// エルミート行列判定
+    ///
+    /// 正方行列であり、随伴行列と一致する場合にtrueを返却する。
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    /// use matrixa::mat;
+    /// use matrixa::complex::Complex;
+    ///
+    /// let h = mat![
+    ///     Complex<i32>:
+    ///         [Complex::new(2, 0), Complex::new(1, 1)],
+    ///         [Complex::new(1, -1), Complex::new(3, 0)]
+    /// ];
+    /// assert!(h.is_hermitian());
+    /// ```
+    ///
+    pub fn is_hermitian(&self) -> bool {
+        let n = self.data.len();
+        if n == 0 || self.data.iter().any(|row| row.len() != n) {
+            return false;
+        }
+        for i in 0..n {
+            for j in 0..=i {
+                if self.data[i][j] != self.data[j][i].conj() {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
+
+impl<T> Matrix<Complex<T>>
+where
+    T: Copy
+    + Add<Output = T>
+    + Sub<Output = T>
+    + Mul<Output = T>
+    + PartialEq
+    + From<u8>
+    + Into<f64>
+    + fmt::Debug,
+{
+    /// ユニタリ行列判定
+    ///
+    /// 随伴行列との積 A^H A の各元と単位行列の各元との差の絶対値が
+    /// いずれも tol 以下である場合にtrueを返却する。
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    /// use matrixa::mat;
+    /// use matrixa::complex::Complex;
+    ///
+    /// let s = std::f64::consts::FRAC_1_SQRT_2;
+    /// let u = mat![
+    ///     Complex<f64>:
+    ///         [Complex::new(s, 0.0), Complex::new(0.0, s)],
+    ///         [Complex::new(0.0, s), Complex::new(s, 0.0)]
+    /// ];
+    /// assert!(u.is_unitary(1e-12));
+    /// ```
+    ///
+    pub fn is_unitary(&self, tol: f64) -> bool {
+        let n = self.data.len();
+        if n == 0 || self.data.iter().any(|row| row.len() != n) {
+            return false;
+        }
+        let adj = self.adjoint();
+        for i in 0..n {
+            for j in 0..n {
+                let mut acc = Complex::from(0x0u8);
+                for k in 0..n {
+                    acc = acc + adj.data[i][k] * self.data[k][j];
+                }
+                let re: f64 = acc.re.into();
+                let im: f64 = acc.im.into();
+                let expected = if i == j { 1.0 } else { 0.0 };
+                if (re - expected).hypot(im) > tol {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests_complex_matrix {
+    use crate::complex::Complex;
+    use crate::core::Matrix;
+    use crate::mat;
+
+    fn c(re: f64, im: f64) -> Complex<f64> {
+        Complex::new(re, im)
+    }
+
+    #[test]
+    fn test_complex_arithmetic() {
+        let z = c(1.0, 2.0);
+        let w = c(3.0, -1.0);
+        assert_eq!(z + w, c(4.0, 1.0));
+        assert_eq!(z - w, c(-2.0, 3.0));
+        assert_eq!(z * w, c(5.0, 5.0));
+        assert_eq!((z * w) / w, z);
+        assert_eq!(-z, c(-1.0, -2.0));
+        assert_eq!(z.conj(), c(1.0, -2.0));
+        assert_eq!(z.norm_sqr(), 5.0);
+        assert_eq!(c(3.0, 4.0).abs(), 5.0);
+        assert_eq!(Complex::<f64>::i() * Complex::i(), c(-1.0, 0.0));
+        assert_eq!(format!("{}", c(1.5, -0.5)), "1.5-0.5i");
+        assert_eq!(format!("{}", c(1.0, -0.0)), "1-0i");
+        assert_eq!(format!("{:.1}", c(1.0, -0.0)), "1.0-0.0i");
+        assert_eq!(format!("{:.1}", c(1.0, 0.0)), "1.0+0.0i");
+    }
+
+    #[test]
+    fn test_matrix_operator() {
+        let m = mat![Complex<f64>: [c(1.0,1.0), c(0.0,2.0)], [c(3.0,0.0), c(1.0,-1.0)]];
+        let n = mat![Complex<f64>: [c(0.0,1.0), c(1.0,0.0)], [c(1.0,0.0), c(0.0,-1.0)]];
+        let sum = mat![Complex<f64>: [c(1.0,2.0), c(1.0,2.0)], [c(4.0,0.0), c(1.0,-2.0)]];
+        let prod = mat![Complex<f64>: [c(-1.0,3.0), c(3.0,1.0)], [c(1.0,2.0), c(2.0,-1.0)]];
+        assert_eq!(&m + &n == sum, true);
+        assert_eq!(&m * &n == prod, true);
+        assert_eq!(m.tr().unwrap(), c(2.0, 0.0));
+        assert_eq!(m.det(), c(2.0, -6.0));
+    }
+
+    #[test]
+    fn test_inverse() {
+        let m = mat![Complex<f64>: [c(1.0,1.0), c(0.0,2.0)], [c(3.0,0.0), c(1.0,-1.0)]];
+        let inv = m.inverse().unwrap();
+        let id = &m * &inv;
+        for i in 0..2 {
+            for j in 0..2 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((id.data[i][j] - c(expected, 0.0)).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_adjoint() {
+        let m = mat![Complex<i32>: [Complex::new(1,2), Complex::new(3,4), Complex::new(5,6)]];
+        let adj = m.adjoint();
+        assert_eq!(adj.rows(), 3);
+        assert_eq!(adj.cols(), 1);
+        assert_eq!(adj.col(0), vec![Complex::new(1,-2), Complex::new(3,-4), Complex::new(5,-6)]);
+        assert_eq!(m.conj().row(0), adj.col(0));
+    }
+
+    #[test]
+    fn test_hermitian_unitary() {
+        let h = mat![Complex<f64>: [c(2.0,0.0), c(1.0,1.0)], [c(1.0,-1.0), c(3.0,0.0)]];
+        assert!(h.is_hermitian());
+        assert!(!h.is_unitary(1e-9));
+        let nh = mat![Complex<f64>: [c(2.0,1.0), c(1.0,1.0)], [c(1.0,-1.0), c(3.0,0.0)]];
+        assert!(!nh.is_hermitian());
+        assert!(!mat![Complex<f64>: [c(1.0,0.0), c(0.0,0.0)]].is_hermitian());
+
+        let s = std::f64::consts::FRAC_1_SQRT_2;
+        let u = mat![Complex<f64>: [c(s,0.0), c(0.0,s)], [c(0.0,s), c(s,0.0)]];
+        assert!(u.is_unitary(1e-12));
+        assert!(!u.is_hermitian());
+        let pauli_y = mat![Complex<f64>: [c(0.0,0.0), c(0.0,-1.0)], [c(0.0,1.0), c(0.0,0.0)]];
+        assert!(pauli_y.is_hermitian());
+        assert!(pauli_y.is_unitary(0.0));
+    }
+}
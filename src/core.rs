@@ -168,6 +168,89 @@ macro_rules! mat {
     };
 }
 
+/// 要素ごとの二項演算子実装用マクロ / element-wise operator implementation
+///
+/// 複合代入演算子(`+=` 等)を既存バッファ上で要素ごとに計算する実装とし、
+/// 所有値・参照の全組み合わせの二項演算子をそれに委譲して実装する。
+/// 行および列の数が一致しない行列が指定された場合はパニックする。
+///
+macro_rules! impl_elementwise_op {
+    ( $(#[$attr:meta])* $op:ident, $method:ident, $op_assign:ident, $method_assign:ident ) => {
+        $(#[$attr])*
+        impl<T> std::ops::$op for Matrix<T>
+        where
+            T: Copy + std::ops::$op<Output = T> + std::fmt::Debug,
+        {
+            type Output = Matrix<T>;
+            fn $method(mut self, other: Matrix<T>) -> Matrix<T> {
+                std::ops::$op_assign::$method_assign(&mut self, &other);
+                self
+            }
+        }
+
+        impl<T> std::ops::$op<&Matrix<T>> for Matrix<T>
+        where
+            T: Copy + std::ops::$op<Output = T> + std::fmt::Debug,
+        {
+            type Output = Matrix<T>;
+            fn $method(mut self, other: &Matrix<T>) -> Matrix<T> {
+                std::ops::$op_assign::$method_assign(&mut self, other);
+                self
+            }
+        }
+
+        impl<T> std::ops::$op<Matrix<T>> for &Matrix<T>
+        where
+            T: Copy + std::ops::$op<Output = T> + std::fmt::Debug,
+        {
+            type Output = Matrix<T>;
+            fn $method(self, other: Matrix<T>) -> Matrix<T> {
+                let mut res = self.clone();
+                std::ops::$op_assign::$method_assign(&mut res, &other);
+                res
+            }
+        }
+
+        impl<T> std::ops::$op<&Matrix<T>> for &Matrix<T>
+        where
+            T: Copy + std::ops::$op<Output = T> + std::fmt::Debug,
+        {
+            type Output = Matrix<T>;
+            fn $method(self, other: &Matrix<T>) -> Matrix<T> {
+                let mut res = self.clone();
+                std::ops::$op_assign::$method_assign(&mut res, other);
+                res
+            }
+        }
+
+        impl<T> std::ops::$op_assign<Matrix<T>> for Matrix<T>
+        where
+            T: Copy + std::ops::$op<Output = T> + std::fmt::Debug,
+        {
+            fn $method_assign(&mut self, other: Matrix<T>) {
+                std::ops::$op_assign::$method_assign(self, &other);
+            }
+        }
+
+        impl<T> std::ops::$op_assign<&Matrix<T>> for Matrix<T>
+        where
+            T: Copy + std::ops::$op<Output = T> + std::fmt::Debug,
+        {
+            fn $method_assign(&mut self, other: &Matrix<T>) {
+                if !self.has_same_size_with(other) {
+                    panic!("abort");
+                }
+                for (row, other_row) in self.data.iter_mut().zip(other.data.iter()) {
+                    for (datum, other_datum) in row.iter_mut().zip(other_row.iter()) {
+                        *datum = std::ops::$op::$method(*datum, *other_datum);
+                    }
+                }
+            }
+        }
+    };
+}
+pub(crate) use impl_elementwise_op;
+
 impl<T> Matrix<T> {
     /// 行列生成
     ///
@@ -182,6 +265,19 @@ impl<T> Matrix<T> {
             max: 0,
         }
     }
+
+    /// 行の一覧からの行列生成
+    ///
+    /// 行の長さは検証しない。呼び出し側で長方形であることを保証する。
+    ///
+    pub(crate) fn from_rows_unchecked(rows: Vec<Vec<T>>) -> Self {
+        Matrix {
+            max: rows.len(),
+            data: rows,
+            debug: false,
+            current: 0,
+        }
+    }
 }
 
 impl<T: Debug> Matrix<T>
@@ -255,8 +351,12 @@ impl<T: std::fmt::Debug> Matrix<T> {
 
     /// データ表示関数
     ///
+    /// 各元の `Debug` 表現を `Display` と同じ形式 (列ごとの右揃え、大きな行列の省略) で標準出力に表示する。
+    /// `Display` を実装しない元の型でも利用できるよう `Debug` 表現を用いるため、`Matrix<String>` の元は引用符付きとなる。
+    /// 精度や枠の形式を指定する場合は `print_with` を、`Display` 表現は `println!("{}", m)` を用いる。
+    ///
     pub fn print(&self) {
-        println!("{:?}", self.data)
+        println!("{}", crate::display::debug_grid(self))
     }
 
     /// 行列データ取得
@@ -628,7 +728,7 @@ impl<T: Copy + ToString> Matrix<T> {
         let mut res = mat![String];
         for i in 0..self.data.len() {
             res.data.push(Vec::new());
-            for j in 0..self.data.len() {
+            for j in 0..self.data[i].len() {
                 res.data[i].push(self.data[i][j].to_string());
             }
         }
@@ -0,0 +1,491 @@
+//! CSV/TSVの入出力 / CSV and TSV reading and writing
+//!
+//! 区切り文字で区切られたテキストを `Matrix<String>` として読み込み、`Display` を実装する元の行列を書き出す。
+//! 区切り文字、引用符、エスケープ文字、見出し行の扱い、列数の揃わない行の扱いは `CsvOptions` で指定する。
+//! 引用符で囲んだ欄には区切り文字や改行を含めることができ、欄内の引用符は二重にして表す。
+//! 型を指定した読み込み `read_csv_parsed` は parse.rs の `parse` を用い、変換に失敗した全ての元を報告する。
+//!
+//! ```rust
+//! use matrixa::core::Matrix;
+//! use matrixa::csv::CsvOptions;
+//!
+//! let text = "name,comment\nalice,\"hello, world\"\nbob,\"say \"\"hi\"\"\"\n";
+//! let opts = CsvOptions::new().header(true);
+//! let m = Matrix::<String>::read_csv(text.as_bytes(), &opts).unwrap();
+//! assert_eq!(m.dump()[0], vec!["alice", "hello, world"]);
+//! assert_eq!(m.dump()[1], vec!["bob", "say \"hi\""]);
+//!
+//! let mut out = Vec::new();
+//! m.write_csv(&mut out, &CsvOptions::new()).unwrap();
+//! assert_eq!(String::from_utf8(out).unwrap(), "alice,\"hello, world\"\nbob,\"say \"\"hi\"\"\"\n");
+//! ```
+//!
+
+use crate::core::Matrix;
+use crate::parse::ParseErrors;
+use std::fmt;
+use std::io::{Read, Write};
+use std::str::FromStr;
+
+/// 列数の揃わない行の扱い / policy for rows with a different number of fields
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RaggedRows {
+    /// `CsvError::Ragged` を返却する
+    Error,
+    /// 最も長い行に合わせ、不足する欄を空文字列で補う
+    Pad,
+    /// 最も短い行に合わせ、超過する欄を捨てる
+    Truncate,
+}
+
+/// CSV/TSVの設定 / CSV and TSV options
+///
+/// 既定値は区切り文字 `,`、引用符 `"`、エスケープ文字なし、見出し行なし、`RaggedRows::Error` とする。
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvOptions {
+    pub delimiter: char,
+    pub quote: Option<char>,
+    pub escape: Option<char>,
+    pub header: bool,
+    pub ragged: RaggedRows,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions {
+            delimiter: ',',
+            quote: Some('"'),
+            escape: None,
+            header: false,
+            ragged: RaggedRows::Error,
+        }
+    }
+}
+
+impl CsvOptions {
+    /// 既定値による設定生成
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// タブ区切り、引用符なしの設定
+    pub fn tsv() -> Self {
+        Self::new().delimiter('\t').no_quote()
+    }
+
+    /// 区切り文字
+    pub fn delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// 引用符
+    pub fn quote(mut self, quote: char) -> Self {
+        self.quote = Some(quote);
+        self
+    }
+
+    /// 引用符を用いない
+    pub fn no_quote(mut self) -> Self {
+        self.quote = None;
+        self
+    }
+
+    /// エスケープ文字
+    ///
+    /// エスケープ文字の直後の一文字は区切り文字・引用符・改行であっても欄の一部として扱う。
+    ///
+    pub fn escape(mut self, escape: char) -> Self {
+        self.escape = Some(escape);
+        self
+    }
+
+    /// 先頭行を見出し行として読み飛ばすか
+    pub fn header(mut self, header: bool) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// 列数の揃わない行の扱い
+    pub fn ragged(mut self, ragged: RaggedRows) -> Self {
+        self.ragged = ragged;
+        self
+    }
+}
+
+/// CSV/TSVの入出力エラー / CSV and TSV errors
+///
+/// 行番号 line は入力テキストの1始まりの行番号で、欄内の改行も数える。
+///
+#[derive(Debug)]
+pub enum CsvError {
+    /// 入出力の失敗 (UTF-8として不正な入力を含む)
+    Io(std::io::Error),
+    /// 引用符の対応が不正
+    Syntax { line: usize, message: String },
+    /// 列数の揃わない行
+    Ragged { line: usize, expected: usize, found: usize },
+    /// 型変換の失敗
+    Parse(ParseErrors),
+    /// 引用符なしの設定で区切り文字・改行を含む元を書き出そうとした
+    Unquotable { row: usize, col: usize },
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CsvError::Io(e) => write!(f, "I/O error: {}", e),
+            CsvError::Syntax { line, message } => write!(f, "line {}: {}", line, message),
+            CsvError::Ragged { line, expected, found } => {
+                write!(f, "line {}: expected {} field(s), found {}", line, expected, found)
+            }
+            CsvError::Parse(e) => fmt::Display::fmt(e, f),
+            CsvError::Unquotable { row, col } => {
+                write!(f, "({}, {}) contains a delimiter or line break but quoting is disabled", row, col)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CsvError {}
+
+impl From<std::io::Error> for CsvError {
+    fn from(e: std::io::Error) -> Self {
+        CsvError::Io(e)
+    }
+}
+
+impl From<ParseErrors> for CsvError {
+    fn from(e: ParseErrors) -> Self {
+        CsvError::Parse(e)
+    }
+}
+
+/// テキストを (開始行番号, 欄の一覧) の列に分割する
+///
+/// 空行は読み飛ばす。
+///
+fn records(text: &str, opts: &CsvOptions) -> Result<Vec<(usize, Vec<String>)>, CsvError> {
+    let mut res = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut line = 1;
+    let mut start = 1;
+    // 欄が引用符で始まったか、引用符の内側にいるか
+    let mut quoted = false;
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if Some(c) == opts.escape {
+            match chars.next() {
+                Some(next) => {
+                    if next == '\n' {
+                        line += 1;
+                    }
+                    field.push(next);
+                    continue;
+                }
+                None => {
+                    return Err(CsvError::Syntax { line, message: "escape character at end of input".to_string() })
+                }
+            }
+        }
+        if in_quotes {
+            if Some(c) == opts.quote {
+                if chars.peek() == Some(&c) {
+                    field.push(c);
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                if c == '\n' {
+                    line += 1;
+                }
+                field.push(c);
+            }
+            continue;
+        }
+        if c == opts.delimiter {
+            fields.push(std::mem::take(&mut field));
+            quoted = false;
+        } else if c == '\n' || c == '\r' {
+            if c == '\r' && chars.peek() == Some(&'\n') {
+                chars.next();
+            }
+            if !fields.is_empty() || !field.is_empty() || quoted {
+                fields.push(std::mem::take(&mut field));
+                res.push((start, std::mem::take(&mut fields)));
+            }
+            quoted = false;
+            line += 1;
+            start = line;
+        } else if quoted {
+            return Err(CsvError::Syntax { line, message: format!("unexpected {:?} after closing quote", c) });
+        } else if Some(c) == opts.quote && field.is_empty() {
+            quoted = true;
+            in_quotes = true;
+        } else {
+            field.push(c);
+        }
+    }
+    if in_quotes {
+        return Err(CsvError::Syntax { line: start, message: "unterminated quoted field".to_string() });
+    }
+    if !fields.is_empty() || !field.is_empty() || quoted {
+        fields.push(field);
+        res.push((start, fields));
+    }
+    Ok(res)
+}
+
+/// CSV/TSVの読み込み / reading
+///
+impl Matrix<String> {
+    /// 区切り文字で区切られたテキストの読み込み
+    ///
+    /// `header` が有効の場合は先頭の行を読み飛ばす。見出し行も列数の検証の対象とする。
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    /// use matrixa::csv::{CsvError, CsvOptions, RaggedRows};
+    ///
+    /// let text = "a\tb\tc\n1\t2\n";
+    /// let err = Matrix::<String>::read_csv(text.as_bytes(), &CsvOptions::tsv()).err().unwrap();
+    /// assert!(matches!(err, CsvError::Ragged { line: 2, expected: 3, found: 2 }));
+    ///
+    /// let m = Matrix::<String>::read_csv(text.as_bytes(), &CsvOptions::tsv().ragged(RaggedRows::Pad)).unwrap();
+    /// assert_eq!(m.dump()[1], vec!["1", "2", ""]);
+    /// ```
+    ///
+    pub fn read_csv<R: Read>(mut reader: R, options: &CsvOptions) -> Result<Matrix<String>, CsvError> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        let mut records = records(&text, options)?;
+
+        let lengths = records.iter().map(|(_, r)| r.len());
+        let width = match options.ragged {
+            RaggedRows::Error => records.first().map_or(0, |(_, r)| r.len()),
+            RaggedRows::Pad => lengths.max().unwrap_or(0),
+            RaggedRows::Truncate => lengths.min().unwrap_or(0),
+        };
+        for (line, record) in records.iter_mut() {
+            if record.len() != width && options.ragged == RaggedRows::Error {
+                return Err(CsvError::Ragged { line: *line, expected: width, found: record.len() });
+            }
+            record.resize(width, String::new());
+        }
+
+        let skip = if options.header { 1 } else { 0 };
+        Ok(Matrix::from_rows_unchecked(records.into_iter().skip(skip).map(|(_, r)| r).collect()))
+    }
+}
+
+/// 型を指定した読み込み / typed reading
+///
+impl<U: FromStr> Matrix<U>
+where
+    U::Err: fmt::Display,
+{
+    /// 区切り文字で区切られたテキストを読み込み、各元を U に変換する
+    ///
+    /// 変換に失敗した元がある場合は、失敗した全ての元を `CsvError::Parse` として返却する。
+    /// 各元の位置は見出し行を除いた行列の添字で表す。
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    /// use matrixa::csv::{CsvError, CsvOptions};
+    ///
+    /// let opts = CsvOptions::new().header(true);
+    /// let m = Matrix::<f64>::read_csv_parsed("x,y\n1.5,2\n-3,4e2\n".as_bytes(), &opts).unwrap();
+    /// assert_eq!(m.dump()[1], vec![-3.0, 400.0]);
+    ///
+    /// match Matrix::<f64>::read_csv_parsed("x,y\n1,n/a\n".as_bytes(), &opts) {
+    ///     Err(CsvError::Parse(e)) => assert_eq!((e.errors[0].row, e.errors[0].col), (0, 1)),
+    ///     _ => panic!(),
+    /// }
+    /// ```
+    ///
+    pub fn read_csv_parsed<R: Read>(reader: R, options: &CsvOptions) -> Result<Matrix<U>, CsvError> {
+        Ok(Matrix::<String>::read_csv(reader, options)?.parse::<U>()?)
+    }
+}
+
+/// CSV/TSVの書き出し / writing
+///
+impl<T: fmt::Display> Matrix<T> {
+    /// 区切り文字で区切られたテキストの書き出し
+    ///
+    /// 区切り文字・引用符・改行を含む元は引用符で囲み、元の中の引用符は二重にする。
+    /// 引用符を用いない設定では、エスケープ文字があればそれを前置し、なければ `CsvError::Unquotable` を返却する。
+    /// 空の元一つのみからなる行は読み込み時に読み飛ばされる空行と区別するため `""` と書き出し、
+    /// 引用符を用いない設定では `CsvError::Unquotable` を返却する。
+    /// 各行は `\n` で終端する。`header` の設定は用いない。
+    ///
+    pub fn write_csv<W: Write>(&self, mut writer: W, options: &CsvOptions) -> Result<(), CsvError> {
+        let special = |c: char| c == options.delimiter || c == '\n' || c == '\r' || Some(c) == options.quote;
+        for (i, row) in self.data.iter().enumerate() {
+            let mut line = String::new();
+            for (j, v) in row.iter().enumerate() {
+                if j > 0 {
+                    line.push(options.delimiter);
+                }
+                let s = v.to_string();
+                // 空の元一つのみの行は空行と区別できないため、引用符で囲む
+                let lone_empty = row.len() == 1 && s.is_empty();
+                if !lone_empty && !s.contains(special) && !options.escape.is_some_and(|e| s.contains(e)) {
+                    line.push_str(&s);
+                    continue;
+                }
+                match (options.quote, options.escape) {
+                    (Some(q), _) => {
+                        line.push(q);
+                        for c in s.chars() {
+                            if c == q || Some(c) == options.escape {
+                                line.push(c);
+                            }
+                            line.push(c);
+                        }
+                        line.push(q);
+                    }
+                    (None, Some(e)) if !lone_empty => {
+                        for c in s.chars() {
+                            if special(c) || c == e {
+                                line.push(e);
+                            }
+                            line.push(c);
+                        }
+                    }
+                    (None, _) => return Err(CsvError::Unquotable { row: i, col: j }),
+                }
+            }
+            line.push('\n');
+            writer.write_all(line.as_bytes())?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_matrix_csv {
+    use crate::core::Matrix;
+    use crate::csv::{CsvError, CsvOptions, RaggedRows};
+    use crate::mat;
+
+    fn read(text: &str, opts: &CsvOptions) -> Result<Matrix<String>, CsvError> {
+        Matrix::<String>::read_csv(text.as_bytes(), opts)
+    }
+
+    #[test]
+    fn test_quoting() {
+        let text = "\"a,b\",\"multi\r\nline\",\"\"\r\n\"\",plain,\"x\"\"y\"\r\n";
+        let m = read(text, &CsvOptions::new()).unwrap();
+        assert_eq!(m.dump()[0], vec!["a,b", "multi\r\nline", ""]);
+        assert_eq!(m.dump()[1], vec!["", "plain", "x\"y"]);
+
+        let m = read("'it''s';b\n\n;\n", &CsvOptions::new().delimiter(';').quote('\'')).unwrap();
+        assert_eq!(m.dump().len(), 2);
+        assert_eq!(m.dump()[0], vec!["it's", "b"]);
+        assert_eq!(m.dump()[1], vec!["", ""]);
+    }
+
+    #[test]
+    fn test_escape() {
+        let opts = CsvOptions::tsv().escape('\\');
+        let m = read("a\\\tb\tc\\\\\nd\\\ne\tf", &opts).unwrap();
+        assert_eq!(m.dump()[0], vec!["a\tb", "c\\"]);
+        assert_eq!(m.dump()[1], vec!["d\ne", "f"]);
+
+        let m = read("\"say \\\"hi\\\"\",x\n", &CsvOptions::new().escape('\\')).unwrap();
+        assert_eq!(m.dump()[0], vec!["say \"hi\"", "x"]);
+    }
+
+    #[test]
+    fn test_syntax_errors() {
+        match read("a,b\n\"open,c\nd,e\n", &CsvOptions::new()) {
+            Err(CsvError::Syntax { line, .. }) => assert_eq!(line, 2),
+            _ => panic!("unterminated quote must fail"),
+        }
+        match read("a,\"b\"c\n", &CsvOptions::new()) {
+            Err(e @ CsvError::Syntax { .. }) => assert_eq!(e.to_string(), "line 1: unexpected 'c' after closing quote"),
+            _ => panic!("text after closing quote must fail"),
+        }
+        let invalid: &[u8] = &[b'a', 0xff, b'\n'];
+        assert!(matches!(Matrix::<String>::read_csv(invalid, &CsvOptions::new()), Err(CsvError::Io(_))));
+    }
+
+    #[test]
+    fn test_header_and_ragged() {
+        let text = "id,name\n1,a,extra\n2\n";
+        match read(text, &CsvOptions::new().header(true)) {
+            Err(CsvError::Ragged { line, expected, found }) => assert_eq!((line, expected, found), (2, 2, 3)),
+            _ => panic!("ragged rows must fail"),
+        }
+        let m = read(text, &CsvOptions::new().header(true).ragged(RaggedRows::Pad)).unwrap();
+        assert_eq!(m.dump().clone(), vec![vec!["1", "a", "extra"], vec!["2", "", ""]]);
+        let m = read(text, &CsvOptions::new().ragged(RaggedRows::Truncate)).unwrap();
+        assert_eq!(m.dump().clone(), vec![vec!["id"], vec!["1"], vec!["2"]]);
+        assert_eq!(m.count(), 3);
+        assert_eq!(read("", &CsvOptions::new()).unwrap().dump().len(), 0);
+    }
+
+    #[test]
+    fn test_typed() {
+        let text = "1,2.5\nx,3\n4,y\n";
+        match Matrix::<f32>::read_csv_parsed(text.as_bytes(), &CsvOptions::new()) {
+            Err(CsvError::Parse(e)) => {
+                let cells: Vec<(usize, usize)> = e.errors.iter().map(|e| (e.row, e.col)).collect();
+                assert_eq!(cells, vec![(1, 0), (2, 1)]);
+            }
+            _ => panic!("unparsable cells must fail"),
+        }
+        let m = Matrix::<i64>::read_csv_parsed("1\t-2\n3\t4\n".as_bytes(), &CsvOptions::tsv()).unwrap();
+        assert_eq!(m == mat![i64: [1, -2], [3, 4]], true);
+    }
+
+    #[test]
+    fn test_write_round_trip() {
+        let m = mat![&str: ["plain", "with,comma", "quote\"d"], ["multi\nline", "", " space "]].to_string();
+        let mut out = Vec::new();
+        m.write_csv(&mut out, &CsvOptions::new()).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text, "plain,\"with,comma\",\"quote\"\"d\"\n\"multi\nline\",, space \n");
+        assert_eq!(read(&text, &CsvOptions::new()).unwrap() == m, true);
+
+        let mut out = Vec::new();
+        mat![f64: [1.5, -2.0]].write_csv(&mut out, &CsvOptions::tsv()).unwrap();
+        assert_eq!(out, b"1.5\t-2\n");
+
+        let m = mat![&str: [""], ["a"], [""]].to_string();
+        let mut out = Vec::new();
+        m.write_csv(&mut out, &CsvOptions::new()).unwrap();
+        assert_eq!(out, b"\"\"\na\n\"\"\n");
+        assert_eq!(read(std::str::from_utf8(&out).unwrap(), &CsvOptions::new()).unwrap() == m, true);
+    }
+
+    #[test]
+    fn test_write_unquoted() {
+        let m = mat![&str: ["a\tb", "c"]].to_string();
+        assert!(matches!(
+            m.write_csv(Vec::new(), &CsvOptions::tsv()),
+            Err(CsvError::Unquotable { row: 0, col: 0 })
+        ));
+        let opts = CsvOptions::tsv().escape('\\');
+        let mut out = Vec::new();
+        m.write_csv(&mut out, &opts).unwrap();
+        assert_eq!(out, b"a\\\tb\tc\n");
+        assert_eq!(read(std::str::from_utf8(&out).unwrap(), &opts).unwrap() == m, true);
+
+        let lone = mat![&str: ["a"], [""]].to_string();
+        assert!(matches!(lone.write_csv(Vec::new(), &opts), Err(CsvError::Unquotable { row: 1, col: 0 })));
+        assert!(matches!(
+            lone.write_csv(Vec::new(), &CsvOptions::tsv().no_quote()),
+            Err(CsvError::Unquotable { row: 1, col: 0 })
+        ));
+    }
+}
@@ -0,0 +1,400 @@
+//! 文字列表現 / pretty-printing
+//!
+//! `Display` を実装する型を元とする `Matrix<T>` に `Display` を実装する。
+//! 各列は表示幅で右揃えとし、CJK文字を含む `Matrix<String>` でも列が揃う。
+//! 書式指定の精度 (`{:.3}` 等) は各元の `Display` にそのまま渡す。
+//! 行数・列数の大きな行列は中央の行・列を `...` で省略する。
+//! 枠の形式や省略の閾値は `PrintOptions` で指定し、`display_with` または `print_with` で利用する。
+//!
+//! ```rust
+//! use matrixa::core::Matrix;
+//! use matrixa::mat;
+//! use matrixa::display::{PrintOptions, PrintStyle};
+//!
+//! let m = mat![f64: [1.0, -2.5], [10.25, 0.125]];
+//! assert_eq!(format!("{:.2}", m), "[  1.00  -2.50 ]\n[ 10.25   0.12 ]");
+//!
+//! let opts = PrintOptions::new().style(PrintStyle::Box);
+//! let s = mat![&str: ["名前", "id"], ["a", "1234"]].to_string();
+//! assert_eq!(
+//!     format!("{}", s.display_with(&opts)),
+//!     "┌──────┬──────┐\n│ 名前 │   id │\n│    a │ 1234 │\n└──────┴──────┘"
+//! );
+//! ```
+//!
+
+use crate::core::Matrix;
+use std::fmt;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+const ELLIPSIS: &str = "...";
+
+/// 枠の形式 / frame style
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrintStyle {
+    /// 各行を `[` `]` で囲む
+    Bracket,
+    /// 罫線 (box-drawing characters) で囲む
+    Box,
+    /// 枠なし
+    Plain,
+}
+
+/// 列内の配置 / alignment within columns
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+    Center,
+}
+
+/// 表示設定 / printing options
+///
+/// 既定値は精度指定なし、`Bracket` 形式、右揃え、見出し行なし、最大20行・12列、元の表示幅の制限なしとする。
+/// export.rs の表形式への出力も精度・配置・見出し行の設定を共有する。
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrintOptions {
+    pub precision: Option<usize>,
+    pub style: PrintStyle,
+    pub align: Align,
+    pub header: bool,
+    pub max_rows: usize,
+    pub max_cols: usize,
+    pub max_cell_width: Option<usize>,
+}
+
+impl Default for PrintOptions {
+    fn default() -> Self {
+        PrintOptions {
+            precision: None,
+            style: PrintStyle::Bracket,
+            align: Align::Right,
+            header: false,
+            max_rows: 20,
+            max_cols: 12,
+            max_cell_width: None,
+        }
+    }
+}
+
+impl PrintOptions {
+    /// 既定値による設定生成
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 精度
+    ///
+    /// 書式指定 `{:.N}` で精度を与えた場合はそちらを優先する。
+    ///
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// 枠の形式
+    pub fn style(mut self, style: PrintStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// 列内の配置
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// 先頭行を見出し行として扱うか
+    ///
+    /// `Box` 形式では見出し行の下に罫線を引く。
+    ///
+    pub fn header(mut self, header: bool) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// 省略せずに表示する最大行数
+    pub fn max_rows(mut self, n: usize) -> Self {
+        self.max_rows = n;
+        self
+    }
+
+    /// 省略せずに表示する最大列数
+    pub fn max_cols(mut self, n: usize) -> Self {
+        self.max_cols = n;
+        self
+    }
+
+    /// 元の最大表示幅
+    ///
+    /// 超過する元は末尾を `…` に置き換えて切り詰める。
+    ///
+    pub fn max_cell_width(mut self, width: usize) -> Self {
+        self.max_cell_width = Some(width.max(1));
+        self
+    }
+}
+
+/// 表示する添字の一覧 (Noneは省略箇所)
+///
+/// len が max を超える場合、先頭 ceil(max/2) 個と末尾 floor(max/2) 個を残す。
+///
+fn visible(len: usize, max: usize) -> Vec<Option<usize>> {
+    if len <= max {
+        return (0..len).map(Some).collect();
+    }
+    let head = max.div_ceil(2);
+    let tail = max / 2;
+    let mut res: Vec<Option<usize>> = (0..head).map(Some).collect();
+    res.push(None);
+    res.extend((len - tail..len).map(Some));
+    res
+}
+
+/// 精度を指定した元の文字列表現
+pub(crate) fn format_cell<T: fmt::Display>(v: &T, precision: Option<usize>) -> String {
+    match precision {
+        Some(p) => format!("{:.*}", p, v),
+        None => v.to_string(),
+    }
+}
+
+/// 表示幅 width に満たない文字列を空白で埋める
+pub(crate) fn pad(s: &str, width: usize, align: Align) -> String {
+    let n = width.saturating_sub(s.width());
+    match align {
+        Align::Left => format!("{}{}", s, " ".repeat(n)),
+        Align::Right => format!("{}{}", " ".repeat(n), s),
+        Align::Center => format!("{}{}{}", " ".repeat(n / 2), s, " ".repeat(n - n / 2)),
+    }
+}
+
+/// 表示幅が width 以下となるよう切り詰め、末尾を `…` とする
+fn truncate(s: String, width: usize) -> String {
+    if s.width() <= width {
+        return s;
+    }
+    let mut res = String::new();
+    let mut total = 0;
+    for g in s.graphemes(true) {
+        total += g.width();
+        if total > width - 1 {
+            break;
+        }
+        res.push_str(g);
+    }
+    res.push('…');
+    res
+}
+
+/// 設定付きの文字列表現 / matrix with printing options
+///
+/// `Matrix::display_with` が返却する。
+///
+pub struct MatrixDisplay<'a, T> {
+    matrix: &'a Matrix<T>,
+    options: &'a PrintOptions,
+}
+
+impl<T: fmt::Display> fmt::Display for MatrixDisplay<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let opts = self.options;
+        let precision = f.precision().or(opts.precision);
+        let data = &self.matrix.data;
+        if data.is_empty() {
+            return write!(f, "[]");
+        }
+        let cols = data.iter().map(|row| row.len()).max().unwrap_or(0);
+        let row_idx = visible(data.len(), opts.max_rows);
+        let col_idx = visible(cols, opts.max_cols);
+
+        let cells: Vec<Vec<String>> = row_idx
+            .iter()
+            .map(|i| {
+                col_idx
+                    .iter()
+                    .map(|j| match (i, j) {
+                        (Some(i), Some(j)) => {
+                            let s = match data[*i].get(*j) {
+                                Some(v) => format_cell(v, precision),
+                                None => String::new(),
+                            };
+                            match opts.max_cell_width {
+                                Some(w) => truncate(s, w),
+                                None => s,
+                            }
+                        }
+                        _ => ELLIPSIS.to_string(),
+                    })
+                    .collect()
+            })
+            .collect();
+        let widths: Vec<usize> = (0..col_idx.len())
+            .map(|j| cells.iter().map(|row| row[j].width()).max().unwrap_or(0))
+            .collect();
+
+        let (open, sep, close) = match opts.style {
+            PrintStyle::Bracket => ("[ ", "  ", " ]"),
+            PrintStyle::Box => ("│ ", " │ ", " │"),
+            PrintStyle::Plain => ("", "  ", ""),
+        };
+        let rule = |left: &str, mid: &str, right: &str| -> String {
+            let segments: Vec<String> = widths.iter().map(|w| "─".repeat(w + 2)).collect();
+            format!("{}{}{}", left, segments.join(mid), right)
+        };
+
+        if opts.style == PrintStyle::Box {
+            writeln!(f, "{}", rule("┌", "┬", "┐"))?;
+        }
+        for (k, row) in cells.iter().enumerate() {
+            if k > 0 {
+                writeln!(f)?;
+                if k == 1 && opts.header && opts.style == PrintStyle::Box {
+                    writeln!(f, "{}", rule("├", "┼", "┤"))?;
+                }
+            }
+            let padded: Vec<String> = row.iter().zip(widths.iter()).map(|(s, w)| pad(s, *w, opts.align)).collect();
+            write!(f, "{}{}{}", open, padded.join(sep), close)?;
+        }
+        if opts.style == PrintStyle::Box {
+            write!(f, "\n{}", rule("└", "┴", "┘"))?;
+        }
+        Ok(())
+    }
+}
+
+/// 元の `Debug` 表現を `Display` として扱う
+struct DebugCell<'a, T>(&'a T);
+
+impl<T: fmt::Debug> fmt::Display for DebugCell<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.0, f)
+    }
+}
+
+/// 各元の `Debug` 表現を `PrintOptions` の既定値で整形する (`Matrix::print` 用)
+pub(crate) fn debug_grid<T: fmt::Debug>(m: &Matrix<T>) -> String {
+    let cells = Matrix::from_rows_unchecked(m.data.iter().map(|row| row.iter().map(DebugCell).collect()).collect());
+    cells.to_string()
+}
+
+/// 文字列表現 / Display
+///
+/// `PrintOptions` の既定値で表示する。
+///
+impl<T: fmt::Display> fmt::Display for Matrix<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.display_with(&PrintOptions::default()), f)
+    }
+}
+
+impl<T: fmt::Display> Matrix<T> {
+    /// 設定を指定した文字列表現
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    /// use matrixa::display::{PrintOptions, PrintStyle};
+    ///
+    /// let mut m = Matrix::<i32>::new();
+    /// for i in 0..5 {
+    ///     m.push((0..5).map(|j| i * 5 + j).collect()).unwrap();
+    /// }
+    /// let opts = PrintOptions::new().style(PrintStyle::Plain).max_rows(2).max_cols(3);
+    /// assert_eq!(
+    ///     format!("{}", m.display_with(&opts)),
+    ///     "  0    1  ...    4\n...  ...  ...  ...\n 20   21  ...   24"
+    /// );
+    /// ```
+    ///
+    pub fn display_with<'a>(&'a self, options: &'a PrintOptions) -> MatrixDisplay<'a, T> {
+        MatrixDisplay { matrix: self, options }
+    }
+
+    /// 設定を指定した表示
+    ///
+    pub fn print_with(&self, options: &PrintOptions) {
+        println!("{}", self.display_with(options))
+    }
+}
+
+#[cfg(test)]
+mod tests_matrix_display {
+    use crate::core::Matrix;
+    use crate::display::{debug_grid, Align, PrintOptions, PrintStyle};
+    use crate::mat;
+
+    #[test]
+    fn test_debug_grid() {
+        let m = mat![i32: [1, -20], [300, 4]];
+        assert_eq!(debug_grid(&m), "[   1  -20 ]\n[ 300    4 ]");
+        let s = mat![&str: ["a", "bc"]].to_string();
+        assert_eq!(debug_grid(&s), "[ \"a\"  \"bc\" ]");
+        let v = mat![&str: ["a,b", "c"]].to_string().split_cell(",");
+        assert_eq!(debug_grid(&v), "[ [\"a\", \"b\"]  [\"c\"] ]");
+        assert_eq!(debug_grid(&Matrix::<f64>::new()), "[]");
+    }
+
+    #[test]
+    fn test_alignment() {
+        let m = mat![i32: [1, -20, 3], [400, 5, 6]];
+        assert_eq!(format!("{}", m), "[   1  -20  3 ]\n[ 400    5  6 ]");
+        let s = mat![&str: ["東京", "a"], ["x", "大阪府"]].to_string();
+        assert_eq!(format!("{}", s), "[ 東京       a ]\n[    x  大阪府 ]");
+        assert_eq!(format!("{}", Matrix::<f64>::new()), "[]");
+    }
+
+    #[test]
+    fn test_precision() {
+        let m = mat![f32: [0.5, 1.0 / 3.0]];
+        assert_eq!(format!("{:.3}", m), "[ 0.500  0.333 ]");
+        let opts = PrintOptions::new().precision(1).style(PrintStyle::Plain);
+        assert_eq!(format!("{}", m.display_with(&opts)), "0.5  0.3");
+        assert_eq!(format!("{:.2}", m.display_with(&opts)), "0.50  0.33");
+    }
+
+    #[test]
+    fn test_elision() {
+        let mut m = Matrix::<usize>::new();
+        for i in 0..30 {
+            m.push((0..30).map(|j| i * 100 + j).collect()).unwrap();
+        }
+        let text = format!("{}", m);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 21);
+        assert!(lines[0].starts_with("[    0     1"));
+        assert!(lines[0].contains("...") && lines[0].ends_with("29 ]"));
+        assert!(lines[10].starts_with("[  ...   ..."));
+        assert!(lines[20].ends_with("2929 ]"));
+
+        let opts = PrintOptions::new().max_rows(0).max_cols(1);
+        assert_eq!(format!("{}", m.display_with(&opts)), "[ ...  ... ]");
+    }
+
+    #[test]
+    fn test_box_and_truncation() {
+        let s = mat![&str: ["short", "a much longer cell"], ["日本語の長い文字列", ""]].to_string();
+        let opts = PrintOptions::new().style(PrintStyle::Box).max_cell_width(6);
+        let text = format!("{}", s.display_with(&opts));
+        assert_eq!(
+            text,
+            "┌───────┬────────┐\n│ short │ a muc… │\n│ 日本… │        │\n└───────┴────────┘"
+        );
+    }
+
+    #[test]
+    fn test_align_and_header() {
+        let s = mat![&str: ["name", "qty"], ["apple", "3"], ["fig", "12"]].to_string();
+        let opts = PrintOptions::new().style(PrintStyle::Box).align(Align::Left).header(true);
+        assert_eq!(
+            format!("{}", s.display_with(&opts)),
+            "┌───────┬─────┐\n│ name  │ qty │\n├───────┼─────┤\n│ apple │ 3   │\n│ fig   │ 12  │\n└───────┴─────┘"
+        );
+        let opts = PrintOptions::new().style(PrintStyle::Plain).align(Align::Center).header(true);
+        assert_eq!(format!("{}", s.display_with(&opts)), "name   qty\napple   3 \n fig   12 ");
+    }
+}
@@ -0,0 +1,299 @@
+//! 文字列間距離 / pairwise string distances
+//!
+//! 二つの文字列の列 a, b の全ての組について距離・類似度を計算し、
+//! a の長さを行数、b の長さを列数とする行列として返却する。
+//! 重複排除や表記揺れの検出では、返却された行列に compare.rs の `le_elem` 等を適用して候補の組を抽出できる。
+//! 距離はいずれも `char` 単位で計算する。
+//!
+//! ```rust
+//! use matrixa::core::Matrix;
+//! use matrixa::mat;
+//!
+//! let a = vec!["kitten".to_string(), "flaw".to_string()];
+//! let b = vec!["sitting".to_string(), "lawn".to_string()];
+//! let d = Matrix::<usize>::levenshtein(&a, &b);
+//! assert_eq!(d == mat![usize: [3, 5], [7, 2]], true);
+//! assert_eq!(d.le_elem(2).count_true(), 1);
+//! ```
+//!
+
+use crate::core::Matrix;
+
+/// a, b の全ての組に f を適用した行列
+fn pairwise<S, U, F>(a: &[S], b: &[S], f: F) -> Matrix<U>
+where
+    S: AsRef<str>,
+    F: Fn(&[char], &[char]) -> U,
+{
+    let b: Vec<Vec<char>> = b.iter().map(|s| s.as_ref().chars().collect()).collect();
+    let mut res = Matrix::new();
+    for s in a.iter() {
+        let s: Vec<char> = s.as_ref().chars().collect();
+        res.data.push(b.iter().map(|t| f(&s, t)).collect());
+    }
+    res
+}
+
+/// 挿入・削除・置換を1とする編集距離
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        cur[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+/// 隣接する二文字の入れ替えを1とする編集距離 (optimal string alignment)
+fn damerau_levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, v) in d[0].iter_mut().enumerate() {
+        *v = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            d[i][j] = (d[i - 1][j - 1] + cost).min(d[i - 1][j] + 1).min(d[i][j - 1] + 1);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+/// 最長共通部分列の長さ
+fn lcs_length(a: &[char], b: &[char]) -> usize {
+    let mut prev = vec![0; b.len() + 1];
+    let mut cur = vec![0; b.len() + 1];
+    for ca in a.iter() {
+        for (j, cb) in b.iter().enumerate() {
+            cur[j + 1] = if ca == cb { prev[j] + 1 } else { prev[j + 1].max(cur[j]) };
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+/// Jaro類似度
+fn jaro(a: &[char], b: &[char]) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+    let mut a_matched = vec![false; a.len()];
+    let mut b_matched = vec![false; b.len()];
+    let mut matches = 0;
+    for (i, ca) in a.iter().enumerate() {
+        let lo = i.saturating_sub(window);
+        let hi = (i + window + 1).min(b.len());
+        for j in lo..hi {
+            if !b_matched[j] && b[j] == *ca {
+                a_matched[i] = true;
+                b_matched[j] = true;
+                matches += 1;
+                break;
+            }
+        }
+    }
+    if matches == 0 {
+        return 0.0;
+    }
+    let a_seq = a.iter().zip(a_matched.iter()).filter(|(_, m)| **m).map(|(c, _)| c);
+    let b_seq = b.iter().zip(b_matched.iter()).filter(|(_, m)| **m).map(|(c, _)| c);
+    let transpositions = a_seq.zip(b_seq).filter(|(x, y)| x != y).count() / 2;
+    let m = matches as f64;
+    (m / a.len() as f64 + m / b.len() as f64 + (m - transpositions as f64) / m) / 3.0
+}
+
+/// Jaro-Winkler類似度 (接頭辞の重み 0.1、最大4文字)
+fn jaro_winkler(a: &[char], b: &[char]) -> f64 {
+    let sim = jaro(a, b);
+    let prefix = a.iter().zip(b.iter()).take(4).take_while(|(x, y)| x == y).count();
+    sim + prefix as f64 * 0.1 * (1.0 - sim)
+}
+
+/// a の部分文字列と pat の編集距離の最小値が max_edits 以下であるか
+fn fuzzy_contains(text: &[char], pat: &[char], max_edits: usize) -> bool {
+    if pat.len() <= max_edits {
+        return true;
+    }
+    // text の任意の位置から照合を開始できるよう、pat の空接頭辞に対するコストを常に0とする
+    let mut prev: Vec<usize> = (0..=pat.len()).collect();
+    let mut cur = vec![0; pat.len() + 1];
+    for ct in text.iter() {
+        cur[0] = 0;
+        for (i, cp) in pat.iter().enumerate() {
+            let cost = if ct == cp { 0 } else { 1 };
+            cur[i + 1] = (prev[i] + cost).min(prev[i + 1] + 1).min(cur[i] + 1);
+        }
+        if cur[pat.len()] <= max_edits {
+            return true;
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    false
+}
+
+/// 編集距離行列 / edit distance matrices
+///
+impl Matrix<usize> {
+    /// Levenshtein距離
+    ///
+    /// (i, j) 要素は a\[i\] と b\[j\] の間の、一文字の挿入・削除・置換を1とする編集距離。
+    ///
+    pub fn levenshtein<S: AsRef<str>>(a: &[S], b: &[S]) -> Matrix<usize> {
+        pairwise(a, b, levenshtein)
+    }
+
+    /// Damerau-Levenshtein距離
+    ///
+    /// Levenshtein距離の編集操作に隣接する二文字の入れ替えを加えた距離。
+    /// 同じ部分文字列を二度編集しない制限付きの距離 (optimal string alignment) として計算する。
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    ///
+    /// let a = vec!["abcd"];
+    /// let b = vec!["acbd", "ca"];
+    /// assert_eq!(Matrix::<usize>::damerau_levenshtein(&a, &b).dump()[0], vec![1, 3]);
+    /// assert_eq!(Matrix::<usize>::levenshtein(&a, &b).dump()[0], vec![2, 3]);
+    /// ```
+    ///
+    pub fn damerau_levenshtein<S: AsRef<str>>(a: &[S], b: &[S]) -> Matrix<usize> {
+        pairwise(a, b, damerau_levenshtein)
+    }
+
+    /// 最長共通部分列 (LCS) の長さ
+    ///
+    pub fn lcs_length<S: AsRef<str>>(a: &[S], b: &[S]) -> Matrix<usize> {
+        pairwise(a, b, lcs_length)
+    }
+}
+
+/// 類似度行列 / similarity matrices
+///
+impl Matrix<f64> {
+    /// Jaro-Winkler類似度
+    ///
+    /// (i, j) 要素は a\[i\] と b\[j\] の類似度で、0.0 (一致なし) から 1.0 (完全一致) の値をとる。
+    /// 共通の接頭辞 (最大4文字) を持つ組ほど高い値となる。
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    ///
+    /// let a = vec!["MARTHA", "DIXON"];
+    /// let b = vec!["MARHTA", "DICKSONX"];
+    /// let s = Matrix::<f64>::jaro_winkler(&a, &b);
+    /// assert!((s.dump()[0][0] - 0.961).abs() < 1e-3);
+    /// assert!((s.dump()[1][1] - 0.813).abs() < 1e-3);
+    /// ```
+    ///
+    pub fn jaro_winkler<S: AsRef<str>>(a: &[S], b: &[S]) -> Matrix<f64> {
+        pairwise(a, b, jaro_winkler)
+    }
+}
+
+/// あいまい検索 / fuzzy matching
+///
+impl Matrix<String> {
+    /// 編集距離 max_edits 以内で pat を含むか
+    ///
+    /// 各元のいずれかの部分文字列と pat のLevenshtein距離が max_edits 以下である場合にtrueとする。
+    /// max_edits が0の場合は `contains` と同じ結果となる。
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    /// use matrixa::mat;
+    ///
+    /// let m = mat![&str: ["Tokyo Station", "Kyoto"], ["Tokio Sta.", "Osaka"]].to_string();
+    /// assert_eq!(m.fuzzy_contains("tokyo", 1) == mat![bool: [true, false], [false, false]], true);
+    /// assert_eq!(m.fuzzy_contains("Tokyo", 1) == mat![bool: [true, false], [true, false]], true);
+    /// ```
+    ///
+    pub fn fuzzy_contains(&self, pat: &str, max_edits: usize) -> Matrix<bool> {
+        let pat: Vec<char> = pat.chars().collect();
+        let mut res = Matrix::new();
+        for row in self.data.iter() {
+            res.data.push(
+                row.iter()
+                    .map(|s| fuzzy_contains(&s.chars().collect::<Vec<char>>(), &pat, max_edits))
+                    .collect(),
+            );
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests_matrix_distance {
+    use crate::core::Matrix;
+    use crate::mat;
+
+    fn words(s: &[&str]) -> Vec<String> {
+        s.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        let a = words(&["", "abc", "東京都"]);
+        let b = words(&["abc", "ab", "京都府"]);
+        let d = Matrix::<usize>::levenshtein(&a, &b);
+        assert_eq!(d == mat![usize: [3, 2, 3], [0, 1, 3], [3, 3, 2]], true);
+        let t = Matrix::<usize>::levenshtein(&b, &a);
+        assert_eq!(t == *d.clone().transpose(), true);
+        let e = Matrix::<usize>::levenshtein(&a, &Vec::<String>::new());
+        assert_eq!(e.data, vec![Vec::<usize>::new(); 3]);
+    }
+
+    #[test]
+    fn test_damerau_levenshtein() {
+        let a = words(&["ab", "abc", "ca"]);
+        let b = words(&["ba", "abc", "abc"]);
+        let d = Matrix::<usize>::damerau_levenshtein(&a, &b);
+        assert_eq!(d.dump()[0], vec![1, 1, 1]);
+        assert_eq!(d.dump()[1], vec![2, 0, 0]);
+        // optimal string alignment: "ca" -> "abc" は入れ替え後の再編集を行わない
+        assert_eq!(d.dump()[2][2], 3);
+    }
+
+    #[test]
+    fn test_lcs_length() {
+        let a = words(&["ABCBDAB", ""]);
+        let b = words(&["BDCABA", "xyz"]);
+        assert_eq!(Matrix::<usize>::lcs_length(&a, &b) == mat![usize: [4, 0], [0, 0]], true);
+    }
+
+    #[test]
+    fn test_jaro_winkler() {
+        let a = ["DWAYNE", "abc", ""];
+        let b = ["DUANE", "abc", "xyz", ""];
+        let s = Matrix::<f64>::jaro_winkler(&a, &b);
+        assert!((s.dump()[0][0] - 0.84).abs() < 1e-2);
+        assert_eq!(s.dump()[1][1], 1.0);
+        assert_eq!(s.dump()[1][2], 0.0);
+        assert_eq!(s.dump()[2][2], 0.0);
+        assert_eq!(s.dump()[2][3], 1.0);
+    }
+
+    #[test]
+    fn test_fuzzy_contains() {
+        let m = mat![&str: ["the quick brown fox", "qiuck"], ["", "東京都千代田区"]].to_string();
+        assert_eq!(m.fuzzy_contains("quick", 0) == m.contains("quick"), true);
+        assert_eq!(m.fuzzy_contains("quick", 2) == mat![bool: [true, true], [false, false]], true);
+        assert_eq!(m.fuzzy_contains("千代田", 1) == mat![bool: [false, false], [false, true]], true);
+        assert_eq!(m.fuzzy_contains("京都干", 1).dump()[1], vec![false, true]);
+        assert!(m.fuzzy_contains("ab", 2).all());
+    }
+}
@@ -0,0 +1,442 @@
+//! 行階段形 / row echelon forms
+//!
+//! 基本行変形(`row_replace` による行交換、行の定数倍、行の加減算)により
+//! 行階段形、簡約行階段形、ランク、退化次数、列空間・零空間の基底を計算する。
+//!
+//! 要素型は `Echelon` トレイトを実装する型とする。
+//! - 浮動小数点型 (`f32`, `f64`, `Complex<f32>`, `Complex<f64>`) は部分ピボット選択を行い、
+//!   絶対値が許容誤差以下の元を0とみなす。許容誤差の既定値は `max(行数, 列数) * EPSILON * 最大絶対値` とする。
+//! - 整数型は除算を伴わない消去を行い、各行を元の最大公約数で割って整数のまま保持する。
+//!   このため整数型の簡約行階段形ではピボットが1とは限らない(正の整数となる)。
+//! - `ModInt<P>` は剰余環上で厳密に計算する。
+//!
+//! ```rust
+//! use matrixa::core::Matrix;
+//! use matrixa::mat;
+//!
+//! let m = mat![f64: [1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]];
+//! assert_eq!(m.rank(), 2);
+//! assert_eq!(m.nullity(), 1);
+//! let (_, pivots) = m.rref();
+//! assert_eq!(pivots, vec![0, 1]);
+//!
+//! let n = mat![i64: [2, 4, 6], [1, 3, 5]];
+//! let (r, pivots) = n.rref();
+//! assert_eq!(r.row(0), vec![1, 0, -1]);
+//! assert_eq!(r.row(1), vec![0, 1, 2]);
+//! assert_eq!(pivots, vec![0, 1]);
+//! assert_eq!(n.null_space_basis(), vec![vec![1, -2, 1]]);
+//! ```
+//!
+
+use crate::complex::Complex;
+use crate::core::Matrix;
+use crate::modular::ModInt;
+use std::ops::{Add, Div, Mul, Sub};
+
+/// 掃き出し法で扱える元 / element types for row reduction
+///
+pub trait Echelon:
+    Copy
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + PartialEq
+    + From<u8>
+    + std::fmt::Debug
+{
+    /// 丸め誤差の相対精度。厳密な型では0とする。
+    const EPSILON: f64;
+
+    /// ピボット選択に用いる大きさ。ピボットとなれない元では0を返却する。
+    fn magnitude(&self) -> f64;
+
+    /// ピボット行 pivot を用いて row の col 列の元を消去する
+    fn cancel(pivot: &[Self], row: &mut [Self], col: usize);
+
+    /// col 列をピボットとする行を正規化する
+    fn normalize(row: &mut [Self], col: usize);
+
+    /// 零空間の基底ベクトルにおいて自由変数に与える値
+    ///
+    /// 簡約行階段形のピボットの値 pivots で割り切れる値を返却する。
+    ///
+    fn null_scale(pivots: &[Self]) -> Self;
+}
+
+fn field_cancel<T: Echelon>(pivot: &[T], row: &mut [T], col: usize) {
+    let factor = row[col] / pivot[col];
+    for j in col..row.len() {
+        row[j] = row[j] - factor * pivot[j];
+    }
+    row[col] = T::from(0x0u8);
+}
+
+fn field_normalize<T: Echelon>(row: &mut [T], col: usize) {
+    let p = row[col];
+    for v in row.iter_mut().skip(col) {
+        *v = *v / p;
+    }
+    row[col] = T::from(0x1u8);
+}
+
+macro_rules! impl_echelon_float {
+    ($($t:ty, $eps:expr, $abs:expr);*) => {
+        $(
+            impl Echelon for $t {
+                const EPSILON: f64 = $eps as f64;
+                fn magnitude(&self) -> f64 {
+                    $abs(self) as f64
+                }
+                fn cancel(pivot: &[Self], row: &mut [Self], col: usize) {
+                    field_cancel(pivot, row, col)
+                }
+                fn normalize(row: &mut [Self], col: usize) {
+                    field_normalize(row, col)
+                }
+                fn null_scale(_: &[Self]) -> Self {
+                    Self::from(0x1u8)
+                }
+            }
+        )*
+    };
+}
+
+impl_echelon_float!(
+    f32, f32::EPSILON, |v: &f32| v.abs();
+    f64, f64::EPSILON, |v: &f64| v.abs();
+    Complex<f32>, f32::EPSILON, |v: &Complex<f32>| v.abs();
+    Complex<f64>, f64::EPSILON, |v: &Complex<f64>| v.abs()
+);
+
+impl<const P: u64> Echelon for ModInt<P> {
+    const EPSILON: f64 = 0.0;
+    fn magnitude(&self) -> f64 {
+        if self.inv().is_some() {
+            1.0
+        } else {
+            0.0
+        }
+    }
+    fn cancel(pivot: &[Self], row: &mut [Self], col: usize) {
+        field_cancel(pivot, row, col)
+    }
+    fn normalize(row: &mut [Self], col: usize) {
+        field_normalize(row, col)
+    }
+    fn null_scale(_: &[Self]) -> Self {
+        ModInt::from(0x1u8)
+    }
+}
+
+macro_rules! impl_echelon_int {
+    ($($t:ty),*) => {
+        $(
+            impl Echelon for $t {
+                const EPSILON: f64 = 0.0;
+                fn magnitude(&self) -> f64 {
+                    self.unsigned_abs() as f64
+                }
+                fn cancel(pivot: &[Self], row: &mut [Self], col: usize) {
+                    let g = gcd(pivot[col], row[col]);
+                    let (a, b) = (pivot[col] / g, row[col] / g);
+                    for j in col..row.len() {
+                        row[j] = a
+                            .checked_mul(row[j])
+                            .zip(b.checked_mul(pivot[j]))
+                            .and_then(|(x, y)| x.checked_sub(y))
+                            .expect("[echelon error]: integer overflow during row reduction");
+                    }
+                    reduce_content(row);
+                }
+                fn normalize(row: &mut [Self], col: usize) {
+                    reduce_content(row);
+                    if row[col] < 0 {
+                        for v in row.iter_mut() {
+                            *v = -*v;
+                        }
+                    }
+                }
+                fn null_scale(pivots: &[Self]) -> Self {
+                    pivots.iter().fold(1, |acc, p| {
+                        (acc / gcd(acc, *p))
+                            .checked_mul(*p)
+                            .expect("[echelon error]: integer overflow during row reduction")
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_echelon_int!(i16, i32, i64, i128, isize);
+
+/// 最大公約数 (非負)
+fn gcd<T>(mut a: T, mut b: T) -> T
+where
+    T: Copy + PartialEq + PartialOrd + std::ops::Rem<Output = T> + std::ops::Neg<Output = T> + From<i8>,
+{
+    let zero = T::from(0);
+    while b != zero {
+        (a, b) = (b, a % b);
+    }
+    if a < zero {
+        -a
+    } else {
+        a
+    }
+}
+
+/// 行の各元を、その最大公約数で割る
+fn reduce_content<T>(row: &mut [T])
+where
+    T: Copy + PartialEq + PartialOrd + std::ops::Rem<Output = T> + std::ops::Neg<Output = T> + Div<Output = T> + From<i8>,
+{
+    let g = row.iter().fold(T::from(0), |acc, v| gcd(acc, *v));
+    if g != T::from(0) && g != T::from(1) {
+        for v in row.iter_mut() {
+            *v = *v / g;
+        }
+    }
+}
+
+/// 行階段形関連の演算 / row reduction
+///
+impl<T: Echelon> Matrix<T> {
+    /// 掃き出し法の本体
+    ///
+    /// reduce が true の場合は簡約行階段形を計算する。結果の行列とピボット列の一覧を返却する。
+    ///
+    fn eliminate(&self, reduce: bool, tol: Option<f64>) -> (Self, Vec<usize>) {
+        let mut res = self.clone();
+        let (rows, cols) = (res.rows(), res.cols());
+        let zero = T::from(0x0u8);
+        let tol = tol.unwrap_or_else(|| {
+            let max = res.data.iter().flatten().map(|v| v.magnitude()).fold(0.0, f64::max);
+            rows.max(cols) as f64 * T::EPSILON * max
+        });
+
+        let mut pivots = Vec::new();
+        for c in 0..cols {
+            let r = pivots.len();
+            if r == rows {
+                break;
+            }
+            let mut p = r;
+            for i in r + 1..rows {
+                if res.data[i][c].magnitude() > res.data[p][c].magnitude() {
+                    p = i;
+                }
+            }
+            if res.data[p][c].magnitude() <= tol {
+                continue;
+            }
+            res.row_replace(p, r).unwrap();
+            T::normalize(&mut res.data[r], c);
+            let start = if reduce { 0 } else { r + 1 };
+            for i in start..rows {
+                if i != r && res.data[i][c] != zero {
+                    let pivot = res.data[r].clone();
+                    T::cancel(&pivot, &mut res.data[i], c);
+                }
+            }
+            pivots.push(c);
+        }
+
+        if reduce {
+            for (r, c) in pivots.iter().enumerate() {
+                T::normalize(&mut res.data[r], *c);
+            }
+        }
+        if T::EPSILON > 0.0 {
+            for v in res.data.iter_mut().flatten() {
+                if v.magnitude() <= tol {
+                    *v = zero;
+                }
+            }
+        }
+        (res, pivots)
+    }
+
+    /// 行階段形
+    ///
+    /// 各ピボットより下の元を0とした行階段形を新規インスタンスとして返却する。
+    /// 体の元ではピボットを1に正規化する。
+    ///
+    pub fn row_echelon(&self) -> Self {
+        self.eliminate(false, None).0
+    }
+
+    /// 簡約行階段形
+    ///
+    /// 簡約行階段形と、ピボット列の添字の一覧を返却する。
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    /// use matrixa::mat;
+    ///
+    /// let m = mat![f64: [0.0, 2.0, 4.0], [1.0, 1.0, 1.0]];
+    /// let (r, pivots) = m.rref();
+    /// assert_eq!(r.row(0), vec![1.0, 0.0, -1.0]);
+    /// assert_eq!(r.row(1), vec![0.0, 1.0, 2.0]);
+    /// assert_eq!(pivots, vec![0, 1]);
+    /// ```
+    ///
+    pub fn rref(&self) -> (Self, Vec<usize>) {
+        self.eliminate(true, None)
+    }
+
+    /// 許容誤差を指定した簡約行階段形
+    ///
+    /// 大きさが tol 以下の元を0とみなす。厳密な型では tol は無視される。
+    ///
+    pub fn rref_with_tol(&self, tol: f64) -> (Self, Vec<usize>) {
+        let tol = if T::EPSILON > 0.0 { tol } else { 0.0 };
+        self.eliminate(true, Some(tol))
+    }
+
+    /// ランク
+    ///
+    pub fn rank(&self) -> usize {
+        self.eliminate(false, None).1.len()
+    }
+
+    /// 許容誤差を指定したランク
+    ///
+    pub fn rank_with_tol(&self, tol: f64) -> usize {
+        self.rref_with_tol(tol).1.len()
+    }
+
+    /// 退化次数 (列数 - ランク)
+    ///
+    pub fn nullity(&self) -> usize {
+        self.cols() - self.rank()
+    }
+
+    /// 列空間の基底
+    ///
+    /// ピボット列に対応する元の行列の列ベクトルを返却する。
+    ///
+    pub fn column_space_basis(&self) -> Vec<Vec<T>> {
+        let (_, pivots) = self.eliminate(false, None);
+        pivots.iter().map(|c| self.col(*c)).collect()
+    }
+
+    /// 零空間の基底
+    ///
+    /// Ax = 0 の解空間の基底ベクトルを、自由変数ごとに返却する。
+    /// 整数型では各ベクトルを最大公約数で割った整数ベクトルとする。
+    ///
+    pub fn null_space_basis(&self) -> Vec<Vec<T>> {
+        let (r, pivots) = self.rref();
+        let zero = T::from(0x0u8);
+        let values: Vec<T> = pivots.iter().enumerate().map(|(k, c)| r.data[k][*c]).collect();
+        let scale = T::null_scale(&values);
+        let mut basis = Vec::new();
+        for f in (0..self.cols()).filter(|c| !pivots.contains(c)) {
+            let mut v = vec![zero; self.cols()];
+            v[f] = scale;
+            for (k, c) in pivots.iter().enumerate() {
+                v[*c] = zero - r.data[k][f] * (scale / values[k]);
+            }
+            T::normalize(&mut v, f);
+            basis.push(v);
+        }
+        basis
+    }
+}
+
+#[cfg(test)]
+mod tests_echelon {
+    use crate::complex::Complex;
+    use crate::core::Matrix;
+    use crate::mat;
+    use crate::modular::ModInt;
+
+    fn mul_vec<T: super::Echelon>(m: &Matrix<T>, v: &[T]) -> Vec<T> {
+        m.dump()
+            .iter()
+            .map(|row| row.iter().zip(v.iter()).fold(T::from(0), |acc, (a, b)| acc + *a * *b))
+            .collect()
+    }
+
+    #[test]
+    fn test_row_echelon_float() {
+        let m = mat![f64: [0.0, 2.0, 4.0], [1.0, 1.0, 1.0], [2.0, 4.0, 6.0]];
+        let e = m.row_echelon();
+        for i in 0..3 {
+            for j in 0..i {
+                assert_eq!(e.dump()[i][j], 0.0);
+            }
+        }
+        assert_eq!(e.row(2), vec![0.0, 0.0, 0.0]);
+        assert_eq!(m.rank(), 2);
+        assert_eq!(m.nullity(), 1);
+    }
+
+    #[test]
+    fn test_rref_tolerance() {
+        let m = mat![f64: [1.0, 1.0], [1.0, 1.0 + 1e-10]];
+        assert_eq!(m.rank(), 2);
+        assert_eq!(m.rank_with_tol(1e-6), 1);
+        let (r, pivots) = m.rref_with_tol(1e-6);
+        assert_eq!(pivots, vec![0]);
+        assert_eq!(r.row(1), vec![0.0, 0.0]);
+        assert_eq!(mat![f64: [0.0, 0.0]].rank(), 0);
+    }
+
+    #[test]
+    fn test_integer() {
+        let m = mat![i64: [2, 4, 6, 8], [1, 3, 5, 7], [3, 7, 11, 15]];
+        assert_eq!(m.rank(), 2);
+        let (r, pivots) = m.rref();
+        assert_eq!(pivots, vec![0, 1]);
+        assert_eq!(r.row(0), vec![1, 0, -1, -2]);
+        assert_eq!(r.row(1), vec![0, 1, 2, 3]);
+        assert_eq!(r.row(2), vec![0, 0, 0, 0]);
+
+        let n = mat![i32: [2, 1], [0, 3]];
+        let (r, _) = n.rref();
+        assert_eq!(r.row(0), vec![1, 0]);
+        assert_eq!(r.row(1), vec![0, 1]);
+        // 有理数の範囲ではピボットが1となる行も、整数のまま保持する
+        let k = mat![i32: [2, 1, 1]];
+        assert_eq!(k.rref().0.row(0), vec![2, 1, 1]);
+        assert_eq!(k.null_space_basis(), vec![vec![-1, 2, 0], vec![-1, 0, 2]]);
+    }
+
+    #[test]
+    fn test_bases() {
+        let m = mat![f64: [1.0, 2.0, 0.0, 1.0], [2.0, 4.0, 1.0, 4.0], [3.0, 6.0, 2.0, 7.0]];
+        assert_eq!(m.rank(), 2);
+        assert_eq!(m.column_space_basis(), vec![vec![1.0, 2.0, 3.0], vec![0.0, 1.0, 2.0]]);
+        let null = m.null_space_basis();
+        assert_eq!(null.len(), m.nullity());
+        for v in null.iter() {
+            assert!(mul_vec(&m, v).iter().all(|x| x.abs() < 1e-12));
+        }
+
+        let i = mat![i64: [1, 2, 3], [2, 4, 6]];
+        let null = i.null_space_basis();
+        assert_eq!(null, vec![vec![-2, 1, 0], vec![-3, 0, 1]]);
+        for v in null.iter() {
+            assert_eq!(mul_vec(&i, v), vec![0, 0]);
+        }
+        assert!(mat![i64: [1, 0], [0, 1]].null_space_basis().is_empty());
+    }
+
+    #[test]
+    fn test_modular_complex() {
+        type F5 = ModInt<5>;
+        let m = mat![F5: [F5::new(1), F5::new(2)], [F5::new(3), F5::new(1)]];
+        assert_eq!(m.rank(), 1);
+        assert_eq!(m.null_space_basis(), vec![vec![F5::new(3), F5::new(1)]]);
+
+        let c = |re: f64, im: f64| Complex::new(re, im);
+        let z = mat![Complex<f64>: [c(1.0, 0.0), c(0.0, 1.0)], [c(0.0, 1.0), c(-1.0, 0.0)]];
+        assert_eq!(z.rank(), 1);
+        let null = z.null_space_basis();
+        assert!(mul_vec(&z, &null[0]).iter().all(|x| x.abs() < 1e-12));
+    }
+}
@@ -0,0 +1,354 @@
+//! 表形式への出力 / export to document markup
+//!
+//! `Display` を実装する型を元とする `Matrix<T>` を Markdown, HTML, LaTeX, AsciiDoc の表として出力する。
+//! 精度・列内の配置・見出し行の有無は端末表示と同じ `PrintOptions` を `to_markdown_with` 等に渡して指定し、
+//! `to_markdown` 等は `PrintOptions` の既定値を用いる。
+//! 端末表示と異なり行・列の省略や元の切り詰めは行わず、全ての元を出力する。
+//! 各形式で特別な意味を持つ文字はエスケープする。
+//!
+//! ```rust
+//! use matrixa::core::Matrix;
+//! use matrixa::mat;
+//! use matrixa::display::PrintOptions;
+//!
+//! let s = mat![&str: ["item", "price"], ["a|b", "1.5"]].to_string();
+//! let opts = PrintOptions::new().header(true);
+//! assert_eq!(s.to_markdown_with(&opts), "| item | price |\n| ---: | ---: |\n| a\\|b | 1.5 |\n");
+//! ```
+//!
+
+use crate::core::Matrix;
+use crate::display::{format_cell, Align, PrintOptions};
+use std::fmt;
+
+/// LaTeXの環境 / LaTeX environment
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LatexEnv {
+    /// 丸括弧の行列 (amsmath)
+    Pmatrix,
+    /// 角括弧の行列 (amsmath)
+    Bmatrix,
+    /// 表
+    Tabular,
+}
+
+fn escape_markdown(s: &str) -> String {
+    escape_html(s).replace('|', "\\|").replace('\n', "<br>")
+}
+
+fn escape_html(s: &str) -> String {
+    let mut res = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => res.push_str("&amp;"),
+            '<' => res.push_str("&lt;"),
+            '>' => res.push_str("&gt;"),
+            '"' => res.push_str("&quot;"),
+            '\'' => res.push_str("&#39;"),
+            _ => res.push(c),
+        }
+    }
+    res
+}
+
+fn escape_latex(s: &str) -> String {
+    let mut res = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => res.push_str("\\textbackslash{}"),
+            '~' => res.push_str("\\textasciitilde{}"),
+            '^' => res.push_str("\\textasciicircum{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                res.push('\\');
+                res.push(c);
+            }
+            _ => res.push(c),
+        }
+    }
+    res
+}
+
+fn escape_asciidoc(s: &str) -> String {
+    s.replace('|', "\\|")
+}
+
+/// 表形式への出力 / export to document markup
+///
+impl<T: fmt::Display> Matrix<T> {
+    /// 各元を精度を指定して文字列化し、esc でエスケープする
+    ///
+    /// 長さの異なる行は空文字列で補う。
+    ///
+    fn export_cells<F: Fn(&str) -> String>(&self, options: &PrintOptions, esc: F) -> Vec<Vec<String>> {
+        let cols = self.data.iter().map(|row| row.len()).max().unwrap_or(0);
+        self.data
+            .iter()
+            .map(|row| {
+                (0..cols)
+                    .map(|j| row.get(j).map_or(String::new(), |v| esc(&format_cell(v, options.precision))))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// 既定の設定による Markdown の表
+    ///
+    /// `to_markdown_with(&PrintOptions::default())` と同じ。
+    ///
+    pub fn to_markdown(&self) -> String {
+        self.to_markdown_with(&PrintOptions::default())
+    }
+
+    /// 既定の設定による HTML の表
+    ///
+    pub fn to_html(&self) -> String {
+        self.to_html_with(&PrintOptions::default())
+    }
+
+    /// 既定の設定による LaTeX の行列または表
+    ///
+    pub fn to_latex(&self, env: LatexEnv) -> String {
+        self.to_latex_with(env, &PrintOptions::default())
+    }
+
+    /// 既定の設定による AsciiDoc の表
+    ///
+    pub fn to_asciidoc(&self) -> String {
+        self.to_asciidoc_with(&PrintOptions::default())
+    }
+
+    /// Markdown (GitHub Flavored Markdown) の表
+    ///
+    /// Markdownの表は見出し行を必須とするため、`header` が無効の場合は空の見出し行を出力する。
+    /// 元に含まれる `|` と HTML の特殊文字はエスケープし、改行は `<br>` とする。
+    /// 行を持たない行列は表を構成できないため、空文字列を返却する。
+    ///
+    pub fn to_markdown_with(&self, options: &PrintOptions) -> String {
+        let cells = self.export_cells(options, escape_markdown);
+        if cells.is_empty() {
+            return String::new();
+        }
+        let cols = cells.first().map_or(0, |row| row.len());
+        let line = |row: &[String]| format!("| {} |\n", row.join(" | "));
+        let rule = match options.align {
+            Align::Left => ":---",
+            Align::Right => "---:",
+            Align::Center => ":---:",
+        };
+
+        let mut res = String::new();
+        let mut rows = cells.iter();
+        match rows.next() {
+            Some(first) if options.header => res.push_str(&line(first)),
+            _ => {
+                res.push_str(&line(&vec![String::new(); cols]));
+                rows = cells.iter();
+            }
+        }
+        res.push_str(&line(&vec![rule.to_string(); cols]));
+        for row in rows {
+            res.push_str(&line(row));
+        }
+        res
+    }
+
+    /// HTMLの表
+    ///
+    /// `header` が有効の場合は先頭行を `<thead>` の `<th>` として出力する。
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    /// use matrixa::mat;
+    /// use matrixa::display::{Align, PrintOptions};
+    ///
+    /// let s = mat![&str: ["a<b", "c"]].to_string();
+    /// let html = s.to_html_with(&PrintOptions::new().align(Align::Left));
+    /// assert_eq!(
+    ///     html,
+    ///     "<table>\n<tbody>\n<tr><td style=\"text-align: left\">a&lt;b</td><td style=\"text-align: left\">c</td></tr>\n</tbody>\n</table>\n"
+    /// );
+    /// ```
+    ///
+    pub fn to_html_with(&self, options: &PrintOptions) -> String {
+        let cells = self.export_cells(options, escape_html);
+        let align = match options.align {
+            Align::Left => "left",
+            Align::Right => "right",
+            Align::Center => "center",
+        };
+        let line = |row: &[String], tag: &str| -> String {
+            let tds: Vec<String> = row
+                .iter()
+                .map(|s| format!("<{} style=\"text-align: {}\">{}</{}>", tag, align, s, tag))
+                .collect();
+            format!("<tr>{}</tr>\n", tds.concat())
+        };
+
+        let mut res = String::from("<table>\n");
+        let mut rows = cells.iter();
+        if options.header {
+            if let Some(first) = rows.next() {
+                res.push_str("<thead>\n");
+                res.push_str(&line(first, "th"));
+                res.push_str("</thead>\n");
+            }
+        }
+        res.push_str("<tbody>\n");
+        for row in rows {
+            res.push_str(&line(row, "td"));
+        }
+        res.push_str("</tbody>\n</table>\n");
+        res
+    }
+
+    /// LaTeXの行列または表
+    ///
+    /// `Tabular` では配置を列指定 (`l`, `r`, `c`) に反映し、`header` が有効の場合は先頭行の下に `\hline` を引く。
+    /// `Pmatrix`, `Bmatrix` では配置と見出し行の設定は用いない。
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    /// use matrixa::mat;
+    /// use matrixa::display::PrintOptions;
+    /// use matrixa::export::LatexEnv;
+    ///
+    /// let m = mat![f64: [1.0, 0.5], [-2.0, 3.25]];
+    /// let tex = m.to_latex_with(LatexEnv::Bmatrix, &PrintOptions::new().precision(1));
+    /// assert_eq!(tex, "\\begin{bmatrix}\n1.0 & 0.5 \\\\\n-2.0 & 3.2\n\\end{bmatrix}\n");
+    /// ```
+    ///
+    pub fn to_latex_with(&self, env: LatexEnv, options: &PrintOptions) -> String {
+        let cells = self.export_cells(options, escape_latex);
+        let lines: Vec<String> = cells.iter().map(|row| row.join(" & ")).collect();
+        match env {
+            LatexEnv::Pmatrix | LatexEnv::Bmatrix => {
+                let name = if env == LatexEnv::Pmatrix { "pmatrix" } else { "bmatrix" };
+                format!("\\begin{{{}}}\n{}\n\\end{{{}}}\n", name, lines.join(" \\\\\n"), name)
+            }
+            LatexEnv::Tabular => {
+                let spec = match options.align {
+                    Align::Left => "l",
+                    Align::Right => "r",
+                    Align::Center => "c",
+                };
+                let cols = cells.first().map_or(0, |row| row.len());
+                let mut res = format!("\\begin{{tabular}}{{{}}}\n", spec.repeat(cols));
+                for (i, line) in lines.iter().enumerate() {
+                    res.push_str(line);
+                    res.push_str(" \\\\\n");
+                    if i == 0 && options.header {
+                        res.push_str("\\hline\n");
+                    }
+                }
+                res.push_str("\\end{tabular}\n");
+                res
+            }
+        }
+    }
+
+    /// AsciiDocの表
+    ///
+    pub fn to_asciidoc_with(&self, options: &PrintOptions) -> String {
+        let cells = self.export_cells(options, escape_asciidoc);
+        let cols = cells.first().map_or(0, |row| row.len());
+        let spec = match options.align {
+            Align::Left => "<",
+            Align::Right => ">",
+            Align::Center => "^",
+        };
+        let mut res = format!("[cols=\"{}\"", vec![spec; cols].join(","));
+        if options.header {
+            res.push_str(", options=\"header\"");
+        }
+        res.push_str("]\n|===\n");
+        for (i, row) in cells.iter().enumerate() {
+            let line: Vec<String> = row.iter().map(|s| format!("|{}", s)).collect();
+            res.push_str(&line.join(" "));
+            res.push('\n');
+            if i == 0 && options.header {
+                res.push('\n');
+            }
+        }
+        res.push_str("|===\n");
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests_matrix_export {
+    use crate::core::Matrix;
+    use crate::display::{Align, PrintOptions};
+    use crate::export::LatexEnv;
+    use crate::mat;
+
+    #[test]
+    fn test_markdown() {
+        let m = mat![f32: [1.0, 2.5], [3.0, 4.125]];
+        let opts = PrintOptions::new().precision(2).align(Align::Center);
+        assert_eq!(
+            m.to_markdown_with(&opts),
+            "|  |  |\n| :---: | :---: |\n| 1.00 | 2.50 |\n| 3.00 | 4.12 |\n"
+        );
+        let s = mat![&str: ["line\nbreak"]].to_string();
+        assert_eq!(s.to_markdown_with(&PrintOptions::new().header(true)), "| line<br>break |\n| ---: |\n");
+
+        let html = mat![&str: ["<b>x</b>", "a&b"]].to_string();
+        assert_eq!(html.to_markdown(), "|  |  |\n| ---: | ---: |\n| &lt;b&gt;x&lt;/b&gt; | a&amp;b |\n");
+        assert_eq!(Matrix::<i32>::new().to_markdown(), "");
+    }
+
+    #[test]
+    fn test_default_options() {
+        let m = mat![f64: [1.5, 2.0]];
+        let opts = PrintOptions::default();
+        assert_eq!(m.to_markdown(), m.to_markdown_with(&opts));
+        assert_eq!(m.to_html(), m.to_html_with(&opts));
+        assert_eq!(m.to_latex(LatexEnv::Pmatrix), "\\begin{pmatrix}\n1.5 & 2\n\\end{pmatrix}\n");
+        assert_eq!(m.to_asciidoc(), "[cols=\">,>\"]\n|===\n|1.5 |2\n|===\n");
+    }
+
+    #[test]
+    fn test_html() {
+        let s = mat![&str: ["名前", "note"], ["\"x\" & 'y'", "<b>"]].to_string();
+        let html = s.to_html_with(&PrintOptions::new().header(true));
+        let lines: Vec<&str> = html.lines().collect();
+        assert_eq!(lines[1], "<thead>");
+        assert_eq!(
+            lines[2],
+            "<tr><th style=\"text-align: right\">名前</th><th style=\"text-align: right\">note</th></tr>"
+        );
+        assert!(lines[5].contains(">&quot;x&quot; &amp; &#39;y&#39;</td>"));
+        assert!(lines[5].contains(">&lt;b&gt;</td>"));
+        assert_eq!(lines.len(), 8);
+    }
+
+    #[test]
+    fn test_latex() {
+        let m = mat![i32: [1, -2], [3, 4]];
+        let opts = PrintOptions::new();
+        assert_eq!(
+            m.to_latex_with(LatexEnv::Pmatrix, &opts),
+            "\\begin{pmatrix}\n1 & -2 \\\\\n3 & 4\n\\end{pmatrix}\n"
+        );
+        let s = mat![&str: ["50%", "a_b"], ["$x$", "{~}"]].to_string();
+        let opts = PrintOptions::new().header(true).align(Align::Left);
+        assert_eq!(
+            s.to_latex_with(LatexEnv::Tabular, &opts),
+            "\\begin{tabular}{ll}\n50\\% & a\\_b \\\\\n\\hline\n\\$x\\$ & \\{\\textasciitilde{}\\} \\\\\n\\end{tabular}\n"
+        );
+    }
+
+    #[test]
+    fn test_asciidoc() {
+        let s = mat![&str: ["a", "b|c"], ["1", ""]].to_string();
+        assert_eq!(
+            s.to_asciidoc_with(&PrintOptions::new().header(true)),
+            "[cols=\">,>\", options=\"header\"]\n|===\n|a |b\\|c\n\n|1 |\n|===\n"
+        );
+        assert_eq!(
+            mat![i32: [7]].to_asciidoc_with(&PrintOptions::new().align(Align::Center)),
+            "[cols=\"^\"]\n|===\n|7\n|===\n"
+        );
+    }
+}
@@ -2,3 +2,25 @@ pub mod core;
 pub mod num;
 pub mod boolean;
 pub mod list;
+pub mod sparse;
+pub mod solver;
+pub mod structured;
+pub mod complex;
+pub mod modular;
+pub mod echelon;
+pub mod normal_form;
+pub mod bitmatrix;
+pub mod mask;
+pub mod compare;
+pub mod parse;
+pub mod unicode;
+pub mod distance;
+pub mod display;
+pub mod export;
+pub mod csv;
+pub mod matrix_market;
+pub mod npy;
+#[cfg(feature = "rayon")]
+pub mod par;
+#[cfg(feature = "serde")]
+pub mod serialize;
@@ -190,6 +190,239 @@ impl Matrix<String>  {
         }
         res
     }
+
+    /// 区切り文字による列の分割
+    ///
+    /// 各元を sep で最大 max 個に分割し、各列をその列で最も多い分割数の列に展開した新規インスタンスを返却する。
+    /// 分割数が不足する元は空文字列で補い、補完が発生した行の番号の一覧を併せて返却する。
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    /// use matrixa::mat;
+    ///
+    /// let log = mat![&str: ["INFO main started"], ["WARN disk"], ["ERROR net timeout after 30s"]].to_string();
+    /// let (m, ragged) = log.split_to_cols(" ", 3).unwrap();
+    /// assert_eq!(m.dump()[1], vec!["WARN", "disk", ""]);
+    /// assert_eq!(m.dump()[2], vec!["ERROR", "net", "timeout after 30s"]);
+    /// assert_eq!(ragged, vec![1]);
+    /// ```
+    ///
+    pub fn split_to_cols(&self, sep: &str, max: usize) -> Result<(Matrix<String>, Vec<usize>), &str> {
+        if max == 0 {
+            return Err("the number of columns to split into must be positive");
+        }
+        if sep.is_empty() {
+            return Err("separator must not be empty");
+        }
+        let cols = self.data.first().map_or(0, |row| row.len());
+        if self.data.iter().any(|row| row.len() != cols) {
+            return Err("matrix rows have different lengths");
+        }
+        let cells = self.split_cell_n(sep, max);
+        let widths: Vec<usize> = (0..cols)
+            .map(|j| cells.data.iter().map(|row| row[j].len()).max().unwrap_or(0))
+            .collect();
+
+        let mut res = mat![String];
+        let mut ragged = Vec::new();
+        for (i, row) in cells.data.into_iter().enumerate() {
+            let mut expanded = Vec::new();
+            for (mut parts, width) in row.into_iter().zip(widths.iter()) {
+                if parts.len() < *width {
+                    if ragged.last() != Some(&i) {
+                        ragged.push(i);
+                    }
+                    parts.resize(*width, String::new());
+                }
+                expanded.extend(parts);
+            }
+            res.data.push(expanded);
+        }
+        Ok((res, ragged))
+    }
+
+    fn split_cell_n(&self, sep: &str, max: usize) -> Matrix<Vec<String>> {
+        let mut res = mat![Vec<String>];
+        for row in self.data.iter() {
+            res.data.push(row.iter().map(|s| s.splitn(max, sep).map(|p| p.to_string()).collect()).collect());
+        }
+        res
+    }
+
+    /// 区切り文字による元の分割
+    ///
+    /// 各元を sep で分割した文字列のVecを元とする Matrix<Vec<String>> を返却する。
+    ///
+    pub fn split_cell(&self, sep: &str) -> Matrix<Vec<String>> {
+        self.split_cell_n(sep, usize::MAX)
+    }
+
+    /// 行の結合
+    ///
+    /// 各行の元を sep で連結した文字列を行ごとに返却する。
+    ///
+    pub fn join_row(&self, sep: &str) -> Vec<String> {
+        self.data.iter().map(|row| row.join(sep)).collect()
+    }
+
+    /// 列の結合
+    ///
+    /// 各列の元を sep で連結した文字列を列ごとに返却する。
+    ///
+    pub fn join_col(&self, sep: &str) -> Vec<String> {
+        (0..self.data.first().map_or(0, |row| row.len()))
+            .map(|j| self.data.iter().map(|row| row[j].as_str()).collect::<Vec<&str>>().join(sep))
+            .collect()
+    }
+
+    /// 要素ごとの文字列連結
+    ///
+    /// 同じ大きさの行列の各元を連結した新規インスタンスを返却する。
+    /// 数値行列の `+` を実装する総称的な `Add` 実装と衝突するため、`Matrix<String>` には `Add` を実装せず本メソッドを用いる。
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    /// use matrixa::mat;
+    ///
+    /// let a = mat![&str: ["foo", "a"]].to_string();
+    /// let b = mat![&str: ["bar", "b"]].to_string();
+    /// assert_eq!(a.concat(&b).unwrap().dump()[0], vec!["foobar", "ab"]);
+    /// ```
+    ///
+    pub fn concat(&self, other: &Matrix<String>) -> Result<Matrix<String>, &str> {
+        if self.data.len() != other.data.len()
+            || self.data.iter().zip(other.data.iter()).any(|(row, other_row)| row.len() != other_row.len())
+        {
+            return Err("matrix size not matched for the concatenation");
+        }
+        Ok(Matrix::from_rows_unchecked(
+            self.data
+                .iter()
+                .zip(other.data.iter())
+                .map(|(row, other_row)| row.iter().zip(other_row.iter()).map(|(a, b)| format!("{}{}", a, b)).collect())
+                .collect(),
+        ))
+    }
+}
+
+/// 正規表現エラー / invalid regular expression
+///
+/// 正規表現のコンパイルに失敗した場合、もしくは存在しないキャプチャグループを指定した場合に返却する。
+///
+#[cfg(feature = "regex")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegexError {
+    pub pattern: String,
+    pub message: String,
+}
+
+#[cfg(feature = "regex")]
+impl std::fmt::Display for RegexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "invalid regex `{}`: {}", self.pattern, self.message)
+    }
+}
+
+#[cfg(feature = "regex")]
+impl std::error::Error for RegexError {}
+
+#[cfg(feature = "regex")]
+fn compile(pattern: &str) -> Result<regex::Regex, RegexError> {
+    regex::Regex::new(pattern).map_err(|e| RegexError {
+        pattern: pattern.to_string(),
+        message: e.to_string(),
+    })
+}
+
+/// 正規表現による文字列操作 / regex operations
+///
+/// `regex` フィーチャの有効時に利用可能。正規表現は呼び出しごとに一度だけコンパイルし、全ての元に適用する。
+///
+/// ```rust
+/// use matrixa::core::Matrix;
+/// use matrixa::mat;
+///
+/// let mut m = mat![&str: ["2024-01-05", "n/a"], ["1999-12-31", "2000-02-29"]].to_string();
+/// let date = r"(\d{4})-(\d{2})-(\d{2})";
+/// assert_eq!(m.is_match(date).unwrap() == mat![bool: [true, false], [true, true]], true);
+/// assert_eq!(m.captures(date, 1).unwrap().dump()[1], vec![Some("1999".to_string()), Some("2000".to_string())]);
+/// m.replace_regex(date, "$3/$2/$1").unwrap();
+/// assert_eq!(m.dump()[0], vec!["05/01/2024", "n/a"]);
+/// assert!(m.is_match("(").is_err());
+/// ```
+///
+#[cfg(feature = "regex")]
+impl Matrix<String> {
+    fn map_regex<U, F>(&self, re: &regex::Regex, f: F) -> Matrix<U>
+    where
+        F: Fn(&regex::Regex, &str) -> U,
+    {
+        let mut res = Matrix::new();
+        for row in self.data.iter() {
+            res.data.push(row.iter().map(|s| f(re, s)).collect());
+        }
+        res
+    }
+
+    /// 正規表現一致判定
+    ///
+    /// 各元が正規表現に一致する部分を含むかどうかを判定し、結果をMatrix<bool>で返却する。
+    ///
+    pub fn is_match(&self, pattern: &str) -> Result<Matrix<bool>, RegexError> {
+        let re = compile(pattern)?;
+        Ok(self.map_regex(&re, |re, s| re.is_match(s)))
+    }
+
+    /// 最初の一致部分の抽出
+    ///
+    /// 各元で最初に一致した部分文字列を返却する。一致しない元はNoneとなる。
+    ///
+    pub fn find(&self, pattern: &str) -> Result<Matrix<Option<String>>, RegexError> {
+        let re = compile(pattern)?;
+        Ok(self.map_regex(&re, |re, s| re.find(s).map(|m| m.as_str().to_string())))
+    }
+
+    /// キャプチャグループの抽出
+    ///
+    /// 各元で最初に一致した箇所の group 番目のキャプチャグループを返却する(0は一致部分全体)。
+    /// 一致しない元、もしくはグループが一致に参加しなかった元はNoneとなる。
+    ///
+    pub fn captures(&self, pattern: &str, group: usize) -> Result<Matrix<Option<String>>, RegexError> {
+        let re = compile(pattern)?;
+        if group >= re.captures_len() {
+            return Err(RegexError {
+                pattern: pattern.to_string(),
+                message: format!("capture group {} does not exist", group),
+            });
+        }
+        Ok(self.map_regex(&re, |re, s| {
+            re.captures(s).and_then(|c| c.get(group)).map(|m| m.as_str().to_string())
+        }))
+    }
+
+    /// 正規表現による置換
+    ///
+    /// 各元で一致した全ての箇所を replacement で置換する。
+    /// replacement 中の `$1` や `${name}` はキャプチャグループの内容に展開される。
+    ///
+    pub fn replace_regex(&mut self, pattern: &str, replacement: &str) -> Result<&mut Self, RegexError> {
+        let re = compile(pattern)?;
+        for s in self.data.iter_mut().flatten() {
+            if let std::borrow::Cow::Owned(replaced) = re.replace_all(s, replacement) {
+                *s = replaced;
+            }
+        }
+        Ok(self)
+    }
+
+    /// 一致数の計数
+    ///
+    /// 各元で正規表現に一致した(重複しない)箇所の数を返却する。
+    ///
+    pub fn count_matches(&self, pattern: &str) -> Result<Matrix<usize>, RegexError> {
+        let re = compile(pattern)?;
+        Ok(self.map_regex(&re, |re, s| re.find_iter(s).count()))
+    }
 }
 
 #[cfg(test)]
@@ -414,4 +647,113 @@ mod tests_matrix_conversion {
         ].to_string();
         assert_eq!(s.as_bytes() == t.as_bytes(), true);
     }
+
+    #[test]
+    fn test_split_to_cols(){
+        let s = mat![
+            &str:
+            ["a,b,c", "1"],
+            ["d", "2,3"],
+            ["e,f", "4"]
+        ].to_string();
+        let (m, ragged) = s.split_to_cols(",", 5).unwrap();
+        assert_eq!(m.data[0].len(), 5);
+        assert_eq!(m.dump()[0], vec!["a", "b", "c", "1", ""]);
+        assert_eq!(m.dump()[1], vec!["d", "", "", "2", "3"]);
+        assert_eq!(m.dump()[2], vec!["e", "f", "", "4", ""]);
+        assert_eq!(ragged, vec![0, 1, 2]);
+
+        let (m, ragged) = s.split_to_cols(",", 1).unwrap();
+        assert_eq!(m == s, true);
+        assert!(ragged.is_empty());
+        assert!(s.split_to_cols(",", 0).is_err());
+        assert!(s.split_to_cols("", 2).is_err());
+    }
+
+    #[test]
+    fn test_split_cell(){
+        let s = mat![&str: ["a b", ""], ["c", "d e f"]].to_string();
+        let c = s.split_cell(" ");
+        assert_eq!(c.dump()[0], vec![vec!["a", "b"], vec![""]]);
+        assert_eq!(c.dump()[1], vec![vec!["c"], vec!["d", "e", "f"]]);
+    }
+
+    #[test]
+    fn test_join(){
+        let s = mat![&str: ["a", "b", "c"], ["d", "e", "f"]].to_string();
+        assert_eq!(s.join_row("-"), vec!["a-b-c", "d-e-f"]);
+        assert_eq!(s.join_col(""), vec!["ad", "be", "cf"]);
+        let (m, _) = mat![&str: ["x=1"], ["y=2"]].to_string().split_to_cols("=", 2).unwrap();
+        assert_eq!(m.join_row("="), vec!["x=1", "y=2"]);
+    }
+
+    #[test]
+    fn test_concat(){
+        let a = mat![&str: ["平", "a"], ["", "c"]].to_string();
+        let b = mat![&str: ["川", ""], ["b", "d"]].to_string();
+        let c = a.concat(&b).unwrap();
+        assert_eq!(c == mat![&str: ["平川", "a"], ["b", "cd"]].to_string(), true);
+        assert!(a.concat(&mat![&str: ["x"]].to_string()).is_err());
+        let mut ragged = mat![&str: ["x", "y"], ["z", "w"]].to_string();
+        ragged.data[1].pop();
+        assert!(a.concat(&ragged).is_err());
+
+        let empty = Matrix::<String>::new();
+        assert_eq!(empty.concat(&Matrix::new()).unwrap().dump().len(), 0);
+        assert!(empty.concat(&a).is_err());
+    }
+}
+
+#[cfg(all(test, feature = "regex"))]
+mod tests_matrix_regex {
+    use crate::core::Matrix;
+    use crate::mat;
+
+    fn sample() -> Matrix<String> {
+        mat![
+            &str:
+            ["id=12, id=7", "none"],
+            ["ID=3", "id=100"]
+        ].to_string()
+    }
+
+    #[test]
+    fn test_is_match_find() {
+        let s = sample();
+        assert_eq!(s.is_match(r"id=\d+").unwrap() == mat![bool: [true, false], [false, true]], true);
+        assert_eq!(s.is_match(r"(?i)id=\d+").unwrap() == mat![bool: [true, false], [true, true]], true);
+        let f = s.find(r"\d+").unwrap();
+        assert_eq!(f.dump()[0], vec![Some("12".to_string()), None]);
+        assert_eq!(f.dump()[1], vec![Some("3".to_string()), Some("100".to_string())]);
+    }
+
+    #[test]
+    fn test_captures() {
+        let s = sample();
+        let c = s.captures(r"id=(\d+)(x)?", 1).unwrap();
+        assert_eq!(c.dump()[0], vec![Some("12".to_string()), None]);
+        let c = s.captures(r"id=(\d+)(x)?", 2).unwrap();
+        assert_eq!(c.dump()[0], vec![None, None]);
+        let e = s.captures(r"id=(\d+)", 2).err().unwrap();
+        assert_eq!(e.pattern, r"id=(\d+)");
+    }
+
+    #[test]
+    fn test_replace_count() {
+        let mut s = sample();
+        assert_eq!(s.count_matches(r"\d+").unwrap() == mat![usize: [2, 0], [1, 1]], true);
+        s.replace_regex(r"id=(?P<n>\d+)", "<${n}>").unwrap();
+        assert_eq!(s.dump()[0], vec!["<12>, <7>", "none"]);
+        assert_eq!(s.dump()[1], vec!["ID=3", "<100>"]);
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let mut s = sample();
+        let e = s.replace_regex("[a-", "").err().unwrap();
+        assert_eq!(e.pattern, "[a-");
+        assert!(e.to_string().starts_with("invalid regex `[a-`"));
+        assert!(s.count_matches("(?P<").is_err());
+        assert!(s.find("*").is_err());
+    }
 }
@@ -0,0 +1,227 @@
+//! マスク操作 / mask-based selection
+//!
+//! list.rs の文字列判定などが返却する `Matrix<bool>` をマスクとして用い、
+//! 元の抽出、条件付き代入、行の絞り込みを行う。
+//! マスクは対象の行列と同じ大きさである必要があり、大きさが一致しない場合はpanicする。
+//!
+//! ```rust
+//! use matrixa::core::Matrix;
+//! use matrixa::mat;
+//!
+//! let names = mat![
+//!     String:
+//!         ["apple".to_string(), "banana".to_string()],
+//!         ["avocado".to_string(), "cherry".to_string()]
+//! ];
+//! let mask = names.starts_with("a");
+//! assert_eq!(mask.count_true(), 2);
+//! assert_eq!(names.select(&mask), vec!["apple".to_string(), "avocado".to_string()]);
+//! ```
+//!
+
+use crate::core::Matrix;
+
+/// マスクによる抽出・代入 / selection and assignment by masks
+///
+impl<T: Clone> Matrix<T> {
+    fn mask_check(&self, mask: &Matrix<bool>) {
+        let matched = self.data.len() == mask.data.len()
+            && self.data.iter().zip(mask.data.iter()).all(|(r, m)| r.len() == m.len());
+        if !matched {
+            panic!("mask size not matched to the matrix");
+        }
+    }
+
+    /// 要素の抽出
+    ///
+    /// マスクがtrueである位置の元を、行優先の順序でVecとして返却する。
+    ///
+    pub fn select(&self, mask: &Matrix<bool>) -> Vec<T> {
+        self.mask_check(mask);
+        self.data
+            .iter()
+            .flatten()
+            .zip(mask.data.iter().flatten())
+            .filter(|(_, m)| **m)
+            .map(|(v, _)| v.clone())
+            .collect()
+    }
+
+    /// 条件による元の選択
+    ///
+    /// マスクがtrueである位置には if_true の元を、falseである位置には if_false の元を格納した
+    /// 新規インスタンスを返却する。
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    /// use matrixa::mat;
+    ///
+    /// let mask = mat![bool: [true, false], [false, true]];
+    /// let a = mat![i32: [1, 2], [3, 4]];
+    /// let b = mat![i32: [-1, -2], [-3, -4]];
+    /// let c = Matrix::where_(&mask, &a, &b);
+    /// assert_eq!(c == mat![i32: [1, -2], [-3, 4]], true);
+    /// ```
+    ///
+    pub fn where_(mask: &Matrix<bool>, if_true: &Matrix<T>, if_false: &Matrix<T>) -> Matrix<T> {
+        if_true.mask_check(mask);
+        if_false.mask_check(mask);
+        let mut res = Matrix::new();
+        for ((m, t), f) in mask.data.iter().zip(if_true.data.iter()).zip(if_false.data.iter()) {
+            res.data.push(
+                m.iter()
+                    .zip(t.iter().zip(f.iter()))
+                    .map(|(m, (t, f))| if *m { t.clone() } else { f.clone() })
+                    .collect(),
+            );
+        }
+        res
+    }
+
+    /// 条件付き代入
+    ///
+    /// マスクがtrueである位置の元を value で置き換える。
+    ///
+    pub fn set_where(&mut self, mask: &Matrix<bool>, value: T) -> &mut Self {
+        self.mask_check(mask);
+        for (v, m) in self.data.iter_mut().flatten().zip(mask.data.iter().flatten()) {
+            if *m {
+                *v = value.clone();
+            }
+        }
+        self
+    }
+
+    /// 条件による行の絞り込み
+    ///
+    /// 述語 f がtrueを返す行のみからなる新規インスタンスを返却する。
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    /// use matrixa::mat;
+    ///
+    /// let m = mat![i32: [1, 2], [3, 4], [5, 6]];
+    /// let f = m.filter_rows(|row| row[0] > 2);
+    /// assert_eq!(f == mat![i32: [3, 4], [5, 6]], true);
+    /// ```
+    ///
+    pub fn filter_rows<F: Fn(&[T]) -> bool>(&self, f: F) -> Matrix<T> {
+        let mut res = Matrix::new();
+        for row in self.data.iter().filter(|row| f(row)) {
+            res.data.push(row.clone());
+        }
+        res
+    }
+
+    /// 行マスクによる行の絞り込み
+    ///
+    /// rows がtrueである行のみからなる新規インスタンスを返却する。rows の長さは行数と一致する必要がある。
+    ///
+    pub fn filter_rows_by(&self, rows: &[bool]) -> Matrix<T> {
+        if rows.len() != self.data.len() {
+            panic!("row mask length not matched to the number of rows");
+        }
+        let mut res = Matrix::new();
+        for (row, _) in self.data.iter().zip(rows.iter()).filter(|(_, m)| **m) {
+            res.data.push(row.clone());
+        }
+        res
+    }
+}
+
+/// 論理行列の集約 / reductions on boolean matrices
+///
+impl Matrix<bool> {
+    /// いずれかの元がtrueであるか
+    ///
+    pub fn any(&self) -> bool {
+        self.data.iter().flatten().any(|v| *v)
+    }
+
+    /// 全ての元がtrueであるか
+    ///
+    pub fn all(&self) -> bool {
+        self.data.iter().flatten().all(|v| *v)
+    }
+
+    /// trueである元の数
+    ///
+    pub fn count_true(&self) -> usize {
+        self.data.iter().flatten().filter(|v| **v).count()
+    }
+}
+
+#[cfg(test)]
+mod tests_matrix_mask {
+    use crate::core::Matrix;
+    use crate::mat;
+
+    fn strings() -> Matrix<String> {
+        mat![
+            String:
+                ["".to_string(), "b".to_string(), "c".to_string()],
+                ["d".to_string(), "".to_string(), "".to_string()]
+        ]
+    }
+
+    #[test]
+    fn test_select() {
+        let m = mat![i32: [1, 2, 3], [4, 5, 6]];
+        let mask = mat![bool: [true, false, true], [false, true, false]];
+        assert_eq!(m.select(&mask), vec![1, 3, 5]);
+        assert_eq!(strings().select(&strings().is_empty()), vec![String::new(); 3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_select_unmatched() {
+        mat![i32: [1, 2]].select(&mat![bool: [true]]);
+    }
+
+    #[test]
+    fn test_where() {
+        let s = strings();
+        let mask = s.is_empty();
+        let fill = mat![
+            String:
+                ["-".to_string(), "-".to_string(), "-".to_string()],
+                ["-".to_string(), "-".to_string(), "-".to_string()]
+        ];
+        let res = Matrix::where_(&mask, &fill, &s);
+        assert_eq!(res.data[0], vec!["-", "b", "c"]);
+        assert_eq!(res.data[1], vec!["d", "-", "-"]);
+    }
+
+    #[test]
+    fn test_set_where() {
+        let mut s = strings();
+        let mask = s.is_empty();
+        s.set_where(&mask, "n/a".to_string());
+        assert_eq!(s.data[1], vec!["d", "n/a", "n/a"]);
+        let mut m = mat![f64: [1.0, -2.0], [-3.0, 4.0]];
+        m.set_where(&mat![bool: [false, true], [true, false]], 0.0);
+        assert_eq!(m == mat![f64: [1.0, 0.0], [0.0, 4.0]], true);
+    }
+
+    #[test]
+    fn test_filter_rows() {
+        let s = strings();
+        let f = s.filter_rows(|row| row.iter().all(|v| !v.is_empty()));
+        assert_eq!(f.data.len(), 0);
+        let f = s.filter_rows(|row| row[0] == "d");
+        assert_eq!(f.data, vec![vec!["d", "", ""]]);
+
+        let m = mat![i32: [1, 2], [3, 4], [5, 6]];
+        assert_eq!(m.filter_rows_by(&vec![true, false, true]) == mat![i32: [1, 2], [5, 6]], true);
+    }
+
+    #[test]
+    fn test_reductions() {
+        let mask = strings().is_empty();
+        assert!(mask.any());
+        assert!(!mask.all());
+        assert_eq!(mask.count_true(), 3);
+        assert!(mat![bool: [true, true]].all());
+        assert!(!mat![bool: [false, false]].any());
+    }
+}
@@ -0,0 +1,721 @@
+//! Matrix Market形式の入出力 / Matrix Market exchange format
+//!
+//! SuiteSparse Matrix Collection 等で用いられる Matrix Market 形式 (.mtx) を読み書きする。
+//! 非ゼロ要素を列挙する `coordinate` と全要素を列優先で列挙する `array` の二つの形式、
+//! `real`, `integer`, `complex`, `pattern` の値の種類、
+//! `general`, `symmetric`, `skew-symmetric`, `hermitian` の対称性に対応する。
+//! 対称性を持つファイルは下三角部分のみを保持するため、読み込み時に上三角部分を補って展開する。
+//! 書式の誤りは入力の1始まりの行番号とともに `MtxError::Syntax` として返却する。
+//!
+//! ```rust
+//! use matrixa::core::Matrix;
+//! use matrixa::mat;
+//!
+//! let text = "%%MatrixMarket matrix coordinate real symmetric\n\
+//!             % 3x3 symmetric\n\
+//!             3 3 4\n\
+//!             1 1 2.0\n\
+//!             2 1 -1.0\n\
+//!             3 2 -1.0\n\
+//!             3 3 2.0\n";
+//! let m = Matrix::<f64>::read_matrix_market(text.as_bytes()).unwrap();
+//! assert_eq!(m == mat![f64: [2.0, -1.0, 0.0], [-1.0, 0.0, -1.0], [0.0, -1.0, 2.0]], true);
+//! ```
+//!
+
+use crate::complex::Complex;
+use crate::core::Matrix;
+use crate::sparse::CooMatrix;
+use std::fmt;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::ops::{Add, Mul, Neg};
+
+/// 格納形式 / storage layout
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MtxFormat {
+    /// 非ゼロ要素を (行, 列, 値) で列挙する
+    Coordinate,
+    /// 全要素を列優先で列挙する
+    Array,
+}
+
+/// 値の種類 / value field
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MtxField {
+    Real,
+    Integer,
+    Complex,
+    /// 値を持たず、非ゼロ要素の位置のみを表す
+    Pattern,
+}
+
+/// 対称性 / symmetry
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MtxSymmetry {
+    General,
+    Symmetric,
+    SkewSymmetric,
+    Hermitian,
+}
+
+/// ヘッダ行 / banner line
+///
+/// `%%MatrixMarket matrix <format> <field> <symmetry>` に対応する。
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MtxHeader {
+    pub format: MtxFormat,
+    pub field: MtxField,
+    pub symmetry: MtxSymmetry,
+}
+
+impl MtxHeader {
+    /// ヘッダ生成
+    ///
+    pub fn new(format: MtxFormat, field: MtxField, symmetry: MtxSymmetry) -> Self {
+        MtxHeader { format, field, symmetry }
+    }
+
+    fn parse(line: &str) -> Result<Self, String> {
+        let tokens: Vec<String> = line.split_whitespace().map(|t| t.to_ascii_lowercase()).collect();
+        if tokens.len() != 5 || tokens[0] != "%%matrixmarket" || tokens[1] != "matrix" {
+            return Err("expected '%%MatrixMarket matrix <format> <field> <symmetry>'".to_string());
+        }
+        let format = match tokens[2].as_str() {
+            "coordinate" => MtxFormat::Coordinate,
+            "array" => MtxFormat::Array,
+            other => return Err(format!("unknown format '{}'", other)),
+        };
+        let field = match tokens[3].as_str() {
+            "real" => MtxField::Real,
+            "integer" => MtxField::Integer,
+            "complex" => MtxField::Complex,
+            "pattern" => MtxField::Pattern,
+            other => return Err(format!("unknown field '{}'", other)),
+        };
+        let symmetry = match tokens[4].as_str() {
+            "general" => MtxSymmetry::General,
+            "symmetric" => MtxSymmetry::Symmetric,
+            "skew-symmetric" => MtxSymmetry::SkewSymmetric,
+            "hermitian" => MtxSymmetry::Hermitian,
+            other => return Err(format!("unknown symmetry '{}'", other)),
+        };
+        let header = MtxHeader::new(format, field, symmetry);
+        header.validate()?;
+        Ok(header)
+    }
+
+    /// 仕様上許されない組み合わせの検出
+    fn validate(&self) -> Result<(), String> {
+        if self.format == MtxFormat::Array && self.field == MtxField::Pattern {
+            return Err("pattern field requires coordinate format".to_string());
+        }
+        if self.symmetry == MtxSymmetry::Hermitian && self.field != MtxField::Complex {
+            return Err("hermitian symmetry requires complex field".to_string());
+        }
+        Ok(())
+    }
+
+    /// 一つの元を表す値のトークン数
+    fn value_tokens(&self) -> usize {
+        match self.field {
+            MtxField::Pattern => 0,
+            MtxField::Complex => 2,
+            _ => 1,
+        }
+    }
+}
+
+impl fmt::Display for MtxHeader {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let format = match self.format {
+            MtxFormat::Coordinate => "coordinate",
+            MtxFormat::Array => "array",
+        };
+        let field = match self.field {
+            MtxField::Real => "real",
+            MtxField::Integer => "integer",
+            MtxField::Complex => "complex",
+            MtxField::Pattern => "pattern",
+        };
+        let symmetry = match self.symmetry {
+            MtxSymmetry::General => "general",
+            MtxSymmetry::Symmetric => "symmetric",
+            MtxSymmetry::SkewSymmetric => "skew-symmetric",
+            MtxSymmetry::Hermitian => "hermitian",
+        };
+        write!(f, "%%MatrixMarket matrix {} {} {}", format, field, symmetry)
+    }
+}
+
+/// Matrix Market形式の入出力エラー / Matrix Market errors
+///
+#[derive(Debug)]
+pub enum MtxError {
+    /// 入出力の失敗 (UTF-8として不正な入力を含む)
+    Io(std::io::Error),
+    /// 入力の書式の誤り
+    Syntax { line: usize, message: String },
+    /// 指定した形式で書き出せない行列
+    Invalid(String),
+}
+
+impl fmt::Display for MtxError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MtxError::Io(e) => write!(f, "I/O error: {}", e),
+            MtxError::Syntax { line, message } => write!(f, "line {}: {}", line, message),
+            MtxError::Invalid(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for MtxError {}
+
+impl From<std::io::Error> for MtxError {
+    fn from(e: std::io::Error) -> Self {
+        MtxError::Io(e)
+    }
+}
+
+/// Matrix Market形式で読み書きできる元 / element types of Matrix Market files
+///
+/// 整数型は `integer` と `pattern`、浮動小数点数型は加えて `real`、複素数型は全ての値の種類を読み込める。
+/// `pattern` の元は1として読み込む。
+///
+pub trait MtxValue: Copy + PartialEq + Add<Output = Self> + Neg<Output = Self> + From<u8> {
+    /// 書き出し時の値の種類
+    const FIELD: MtxField;
+
+    /// 値のトークンからの変換
+    ///
+    /// tokens は値の種類に応じた個数 (pattern: 0, complex: 2, その他: 1) のトークンを保持する。
+    ///
+    fn parse_mtx(field: MtxField, tokens: &[&str]) -> Result<Self, String>;
+
+    /// 値のトークンへの変換
+    fn format_mtx(&self) -> String;
+
+    /// 共役 (hermitian の展開に用いる)
+    fn conj_mtx(self) -> Self {
+        self
+    }
+}
+
+macro_rules! impl_mtx_float {
+    ($($t:ty),*) => {
+        $(
+            impl MtxValue for $t {
+                const FIELD: MtxField = MtxField::Real;
+
+                fn parse_mtx(field: MtxField, tokens: &[&str]) -> Result<Self, String> {
+                    match field {
+                        MtxField::Real | MtxField::Integer => tokens[0].parse().map_err(|e| format!("{}: {:?}", e, tokens[0])),
+                        MtxField::Pattern => Ok(1.0),
+                        MtxField::Complex => Err("complex values cannot be read into a real matrix".to_string()),
+                    }
+                }
+
+                fn format_mtx(&self) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_mtx_int {
+    ($($t:ty),*) => {
+        $(
+            impl MtxValue for $t {
+                const FIELD: MtxField = MtxField::Integer;
+
+                fn parse_mtx(field: MtxField, tokens: &[&str]) -> Result<Self, String> {
+                    match field {
+                        MtxField::Integer => tokens[0].parse().map_err(|e| format!("{}: {:?}", e, tokens[0])),
+                        MtxField::Pattern => Ok(1),
+                        _ => Err(format!("{:?} values cannot be read into an integer matrix", field)),
+                    }
+                }
+
+                fn format_mtx(&self) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_mtx_complex {
+    ($($t:ty),*) => {
+        $(
+            impl MtxValue for Complex<$t> {
+                const FIELD: MtxField = MtxField::Complex;
+
+                fn parse_mtx(field: MtxField, tokens: &[&str]) -> Result<Self, String> {
+                    let parse = |s: &str| s.parse::<$t>().map_err(|e| format!("{}: {:?}", e, s));
+                    match field {
+                        MtxField::Complex => Ok(Complex::new(parse(tokens[0])?, parse(tokens[1])?)),
+                        MtxField::Real | MtxField::Integer => Ok(Complex::new(parse(tokens[0])?, 0.0)),
+                        MtxField::Pattern => Ok(Complex::new(1.0, 0.0)),
+                    }
+                }
+
+                fn format_mtx(&self) -> String {
+                    format!("{} {}", self.re, self.im)
+                }
+
+                fn conj_mtx(self) -> Self {
+                    self.conj()
+                }
+            }
+        )*
+    };
+}
+
+impl_mtx_float!(f32, f64);
+impl_mtx_int!(i32, i64);
+impl_mtx_complex!(f32, f64);
+
+/// 読み込んだ行列の大きさと、対称性を展開した (行, 列, 値) の一覧
+struct Entries<T> {
+    size_line: usize,
+    rows: usize,
+    cols: usize,
+    entries: Vec<(usize, usize, T)>,
+}
+
+fn syntax(line: usize, message: impl Into<String>) -> MtxError {
+    MtxError::Syntax { line, message: message.into() }
+}
+
+fn parse_usize(line: usize, s: &str) -> Result<usize, MtxError> {
+    s.parse().map_err(|_| syntax(line, format!("invalid size or index {:?}", s)))
+}
+
+fn read_entries<T: MtxValue, R: Read>(reader: R) -> Result<Entries<T>, MtxError> {
+    let mut lines = BufReader::new(reader).lines().enumerate().map(|(i, l)| (i + 1, l));
+    let header = match lines.next() {
+        Some((n, line)) => MtxHeader::parse(&line?).map_err(|m| syntax(n, m))?,
+        None => return Err(syntax(1, "empty input")),
+    };
+    // コメント行と空行を除いたデータ行
+    let mut data = lines.filter_map(|(n, line)| match line {
+        Ok(l) if l.trim().is_empty() || l.trim_start().starts_with('%') => None,
+        other => Some((n, other)),
+    });
+
+    let (size_line, size) = match data.next() {
+        Some((n, line)) => (n, line?),
+        None => return Err(syntax(1, "missing size line")),
+    };
+    let size: Vec<&str> = size.split_whitespace().collect();
+    let expected_tokens = if header.format == MtxFormat::Coordinate { 3 } else { 2 };
+    if size.len() != expected_tokens {
+        return Err(syntax(size_line, format!("size line must have {} values", expected_tokens)));
+    }
+    let rows = parse_usize(size_line, size[0])?;
+    let cols = parse_usize(size_line, size[1])?;
+    if header.symmetry != MtxSymmetry::General && rows != cols {
+        return Err(syntax(size_line, format!("{:?} matrix must be square", header.symmetry)));
+    }
+
+    // 宣言された大きさは信用せず、元の総数が usize に収まることだけを先に確かめる
+    let cells = rows
+        .checked_mul(cols)
+        .ok_or_else(|| syntax(size_line, format!("{}x{} matrix is too large", rows, cols)))?;
+
+    // 読み込む元の位置 (array 形式は列優先、対称性を持つ場合は下三角部分)。
+    // 宣言された大きさから一覧を作らず、元を読むたびに順に取り出す
+    let symmetry = header.symmetry;
+    let mut positions = match header.format {
+        MtxFormat::Coordinate => None,
+        MtxFormat::Array => Some((0..cols).flat_map(move |j| {
+            let start = match symmetry {
+                MtxSymmetry::General => 0,
+                MtxSymmetry::SkewSymmetric => j + 1,
+                _ => j,
+            };
+            (start..rows).map(move |i| (i, j))
+        })),
+    };
+    let count = match (header.format, symmetry) {
+        (MtxFormat::Coordinate, _) => parse_usize(size_line, size[2])?,
+        (MtxFormat::Array, MtxSymmetry::General) => cells,
+        // 正方行列の下三角部分: (n^2 + n) / 2、対角を除くと (n^2 - n) / 2
+        (MtxFormat::Array, MtxSymmetry::SkewSymmetric) => (cells - rows) / 2,
+        (MtxFormat::Array, _) => cells / 2 + rows.div_ceil(2),
+    };
+
+    let value_tokens = header.value_tokens();
+    let index_tokens = if positions.is_none() { 2 } else { 0 };
+    let mut entries = Vec::new();
+    let mut last_line = size_line;
+    for k in 0..count {
+        let (n, line) = match data.next() {
+            Some((n, line)) => (n, line?),
+            None => return Err(syntax(last_line + 1, format!("expected {} entries, found {}", count, k))),
+        };
+        last_line = n;
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        if tokens.len() != index_tokens + value_tokens {
+            return Err(syntax(n, format!("expected {} values, found {}", index_tokens + value_tokens, tokens.len())));
+        }
+        let (i, j) = match &mut positions {
+            Some(p) => p.next().expect("count matches the array layout"),
+            None => {
+                let (i, j) = (parse_usize(n, tokens[0])?, parse_usize(n, tokens[1])?);
+                if i == 0 || j == 0 || i > rows || j > cols {
+                    return Err(syntax(n, format!("index ({}, {}) out of range for {}x{} matrix", i, j, rows, cols)));
+                }
+                (i - 1, j - 1)
+            }
+        };
+        let v = T::parse_mtx(header.field, &tokens[index_tokens..]).map_err(|m| syntax(n, m))?;
+        entries.push((i, j, v));
+        if i != j {
+            match header.symmetry {
+                MtxSymmetry::General => {}
+                MtxSymmetry::Symmetric => entries.push((j, i, v)),
+                MtxSymmetry::SkewSymmetric => entries.push((j, i, -v)),
+                MtxSymmetry::Hermitian => entries.push((j, i, v.conj_mtx())),
+            }
+        } else if header.symmetry == MtxSymmetry::SkewSymmetric {
+            return Err(syntax(n, "skew-symmetric matrix cannot have diagonal entries"));
+        }
+    }
+    if let Some((n, _)) = data.next() {
+        return Err(syntax(n, format!("unexpected entry after {} entries", count)));
+    }
+    Ok(Entries { size_line, rows, cols, entries })
+}
+
+/// Matrix Market形式の入出力 / Matrix Market reading and writing
+///
+impl<T: MtxValue> Matrix<T> {
+    /// Matrix Market形式の読み込み
+    ///
+    /// 密行列として読み込む。coordinate 形式で同じ位置に複数の元がある場合は加算する。
+    /// 宣言された大きさの行列を確保できない場合は `MtxError::Syntax` を返却する。
+    ///
+    pub fn read_matrix_market<R: Read>(reader: R) -> Result<Matrix<T>, MtxError> {
+        let parsed = read_entries::<T, R>(reader)?;
+        let zero = T::from(0x0u8);
+        let too_large = || syntax(parsed.size_line, format!("{}x{} matrix is too large", parsed.rows, parsed.cols));
+        // 確保する総バイト数を先に確かめ、各行も失敗しうる形で確保する
+        let bytes = parsed.rows.checked_mul(parsed.cols).and_then(|n| n.checked_mul(std::mem::size_of::<T>()));
+        if bytes.filter(|&n| n <= isize::MAX as usize).is_none() {
+            return Err(too_large());
+        }
+        let mut data = Vec::new();
+        data.try_reserve_exact(parsed.rows).map_err(|_| too_large())?;
+        for _ in 0..parsed.rows {
+            let mut row = Vec::new();
+            row.try_reserve_exact(parsed.cols).map_err(|_| too_large())?;
+            row.resize(parsed.cols, zero);
+            data.push(row);
+        }
+        for (i, j, v) in parsed.entries {
+            data[i][j] = data[i][j] + v;
+        }
+        Ok(Matrix::from_rows_unchecked(data))
+    }
+
+    /// Matrix Market形式の書き出し
+    ///
+    /// header の値の種類は元の型の種類 (`MtxValue::FIELD`) または `pattern` (coordinate 形式のみ) とする。
+    /// 対称性を指定した場合は行列がその対称性を満たすことを検証し、下三角部分のみを書き出す。
+    /// coordinate 形式ではゼロでない元のみを書き出す。
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    /// use matrixa::mat;
+    /// use matrixa::matrix_market::{MtxField, MtxFormat, MtxHeader, MtxSymmetry};
+    ///
+    /// let m = mat![i32: [0, -3], [3, 0]];
+    /// let header = MtxHeader::new(MtxFormat::Coordinate, MtxField::Integer, MtxSymmetry::SkewSymmetric);
+    /// let mut out = Vec::new();
+    /// m.write_matrix_market(&mut out, &header).unwrap();
+    /// let text = String::from_utf8(out).unwrap();
+    /// assert_eq!(text, "%%MatrixMarket matrix coordinate integer skew-symmetric\n2 2 1\n2 1 3\n");
+    /// assert_eq!(Matrix::<i32>::read_matrix_market(text.as_bytes()).unwrap() == m, true);
+    /// ```
+    ///
+    pub fn write_matrix_market<W: Write>(&self, mut writer: W, header: &MtxHeader) -> Result<(), MtxError> {
+        header.validate().map_err(MtxError::Invalid)?;
+        if header.field != T::FIELD && header.field != MtxField::Pattern {
+            return Err(MtxError::Invalid(format!("{:?} matrix cannot be written as {:?}", T::FIELD, header.field)));
+        }
+        let rows = self.data.len();
+        let cols = self.data.first().map_or(0, |row| row.len());
+        if self.data.iter().any(|row| row.len() != cols) {
+            return Err(MtxError::Invalid("matrix rows have different lengths".to_string()));
+        }
+        if header.symmetry != MtxSymmetry::General {
+            if rows != cols {
+                return Err(MtxError::Invalid(format!("{:?} matrix must be square", header.symmetry)));
+            }
+            for i in 0..rows {
+                for j in 0..=i {
+                    let (a, b) = (self.data[i][j], self.data[j][i]);
+                    let mirrored = match header.symmetry {
+                        MtxSymmetry::SkewSymmetric => -a,
+                        MtxSymmetry::Hermitian => a.conj_mtx(),
+                        _ => a,
+                    };
+                    if b != mirrored {
+                        return Err(MtxError::Invalid(format!(
+                            "matrix is not {:?} at ({}, {})",
+                            header.symmetry, i, j
+                        )));
+                    }
+                }
+            }
+        }
+
+        let zero = T::from(0x0u8);
+        let stored = |i: usize, j: usize| match header.symmetry {
+            MtxSymmetry::General => true,
+            MtxSymmetry::SkewSymmetric => i > j,
+            _ => i >= j,
+        };
+        let mut body = String::new();
+        let mut nnz = 0;
+        match header.format {
+            MtxFormat::Coordinate => {
+                for (i, row) in self.data.iter().enumerate() {
+                    for (j, v) in row.iter().enumerate().filter(|(j, v)| stored(i, *j) && **v != zero) {
+                        nnz += 1;
+                        if header.field == MtxField::Pattern {
+                            body.push_str(&format!("{} {}\n", i + 1, j + 1));
+                        } else {
+                            body.push_str(&format!("{} {} {}\n", i + 1, j + 1, v.format_mtx()));
+                        }
+                    }
+                }
+            }
+            MtxFormat::Array => {
+                for j in 0..cols {
+                    for i in (0..rows).filter(|i| stored(*i, j)) {
+                        body.push_str(&self.data[i][j].format_mtx());
+                        body.push('\n');
+                    }
+                }
+            }
+        }
+
+        writeln!(writer, "{}", header)?;
+        match header.format {
+            MtxFormat::Coordinate => writeln!(writer, "{} {} {}", rows, cols, nnz)?,
+            MtxFormat::Array => writeln!(writer, "{} {}", rows, cols)?,
+        }
+        writer.write_all(body.as_bytes())?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Matrix Market形式の入出力 / Matrix Market reading and writing
+///
+impl<T> CooMatrix<T>
+where
+    T: MtxValue + Mul<Output = T>,
+{
+    /// Matrix Market形式の疎行列としての読み込み
+    ///
+    /// 対称性を展開した元を保持する。同じ位置の元は取得時および変換時に加算される。
+    ///
+    /// ```rust
+    /// use matrixa::sparse::{CooMatrix, SparseMatrix};
+    ///
+    /// let text = "%%MatrixMarket matrix coordinate pattern general\n3 4 2\n1 4\n3 2\n";
+    /// let s = CooMatrix::<f64>::read_matrix_market(text.as_bytes()).unwrap();
+    /// assert_eq!((s.rows(), s.cols(), s.nnz()), (3, 4, 2));
+    /// assert_eq!(s.get(0, 3), 1.0);
+    /// ```
+    ///
+    pub fn read_matrix_market<R: Read>(reader: R) -> Result<CooMatrix<T>, MtxError> {
+        let parsed = read_entries::<T, R>(reader)?;
+        let mut res = CooMatrix::new(parsed.rows, parsed.cols);
+        for (i, j, v) in parsed.entries {
+            res.push(i, j, v).expect("entries are within the declared size");
+        }
+        Ok(res)
+    }
+
+    /// Matrix Market形式の書き出し
+    ///
+    /// 保持している元をそのまま `coordinate general` 形式で書き出す。
+    ///
+    pub fn write_matrix_market<W: Write>(&self, mut writer: W) -> Result<(), MtxError> {
+        use crate::sparse::SparseMatrix;
+        let header = MtxHeader::new(MtxFormat::Coordinate, T::FIELD, MtxSymmetry::General);
+        let triplets = self.triplets();
+        writeln!(writer, "{}", header)?;
+        writeln!(writer, "{} {} {}", self.rows(), self.cols(), triplets.len())?;
+        for (i, j, v) in triplets {
+            writeln!(writer, "{} {} {}", i + 1, j + 1, v.format_mtx())?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_matrix_market {
+    use crate::complex::Complex;
+    use crate::core::Matrix;
+    use crate::mat;
+    use crate::matrix_market::{MtxError, MtxField, MtxFormat, MtxHeader, MtxSymmetry};
+    use crate::sparse::{CooMatrix, SparseMatrix};
+
+    fn read<T: crate::matrix_market::MtxValue>(text: &str) -> Result<Matrix<T>, MtxError> {
+        Matrix::<T>::read_matrix_market(text.as_bytes())
+    }
+
+    fn write<T: crate::matrix_market::MtxValue>(m: &Matrix<T>, header: &MtxHeader) -> Result<String, MtxError> {
+        let mut out = Vec::new();
+        m.write_matrix_market(&mut out, header)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    fn syntax_line<T>(r: Result<T, MtxError>) -> usize {
+        match r {
+            Err(MtxError::Syntax { line, .. }) => line,
+            _ => panic!("expected a syntax error"),
+        }
+    }
+
+    #[test]
+    fn test_array() {
+        let m = read::<f64>("%%MatrixMarket matrix array real general\n2 3\n1\n4\n2\n5\n3\n6\n").unwrap();
+        assert_eq!(m == mat![f64: [1.0, 2.0, 3.0], [4.0, 5.0, 6.0]], true);
+        let s = read::<i64>("%%matrixmarket MATRIX Array Integer Symmetric\n% c\n\n2 2\n1\n2\n3\n").unwrap();
+        assert_eq!(s == mat![i64: [1, 2], [2, 3]], true);
+        let k = read::<i32>("%%MatrixMarket matrix array integer skew-symmetric\n3 3\n1\n2\n3\n").unwrap();
+        assert_eq!(k == mat![i32: [0, -1, -2], [1, 0, -3], [2, 3, 0]], true);
+    }
+
+    #[test]
+    fn test_coordinate() {
+        let text = "%%MatrixMarket matrix coordinate integer general\n2 2 3\n1 1 5\n2 2 -1\n1 1 2\n";
+        assert_eq!(read::<i32>(text).unwrap() == mat![i32: [7, 0], [0, -1]], true);
+        assert_eq!(read::<f32>(text).unwrap() == mat![f32: [7.0, 0.0], [0.0, -1.0]], true);
+        let p = read::<i32>("%%MatrixMarket matrix coordinate pattern symmetric\n3 3 2\n2 1\n3 3\n").unwrap();
+        assert_eq!(p == mat![i32: [0, 1, 0], [1, 0, 0], [0, 0, 1]], true);
+    }
+
+    #[test]
+    fn test_complex() {
+        let text = "%%MatrixMarket matrix coordinate complex hermitian\n2 2 2\n1 1 2 0\n2 1 1 -1\n";
+        let h = read::<Complex<f64>>(text).unwrap();
+        assert_eq!(h.data[0][1], Complex::new(1.0, 1.0));
+        assert_eq!(h.data[1][0], Complex::new(1.0, -1.0));
+        assert!(h.is_hermitian());
+        let header = MtxHeader::new(MtxFormat::Coordinate, MtxField::Complex, MtxSymmetry::Hermitian);
+        assert_eq!(write(&h, &header).unwrap(), text);
+
+        let r = read::<Complex<f32>>("%%MatrixMarket matrix array real general\n1 1\n2.5\n").unwrap();
+        assert_eq!(r.data[0][0], Complex::new(2.5, 0.0));
+        assert!(read::<f64>(text).is_err());
+    }
+
+    #[test]
+    fn test_syntax_errors() {
+        assert_eq!(syntax_line(read::<f64>("%%MatrixMarket matrix coordinate real\n")), 1);
+        assert_eq!(syntax_line(read::<f64>("%%MatrixMarket matrix array pattern general\n1 1\n")), 1);
+        assert_eq!(syntax_line(read::<f64>("%%MatrixMarket matrix coordinate real hermitian\n1 1 0\n")), 1);
+        assert_eq!(syntax_line(read::<f64>("%%MatrixMarket matrix coordinate real general\n%\n2 2\n")), 3);
+        assert_eq!(syntax_line(read::<f64>("%%MatrixMarket matrix array real symmetric\n2 3\n")), 2);
+        let text = "%%MatrixMarket matrix coordinate real general\n2 2 2\n1 1 1.0\n\n% note\n3 1 1.0\n";
+        assert_eq!(syntax_line(read::<f64>(text)), 6);
+        let text = "%%MatrixMarket matrix coordinate real general\n2 2 2\n1 1 x\n";
+        assert_eq!(syntax_line(read::<f64>(text)), 3);
+        let text = "%%MatrixMarket matrix coordinate real general\n2 2 2\n1 1 1.0\n";
+        assert_eq!(syntax_line(read::<f64>(text)), 4);
+        let text = "%%MatrixMarket matrix array real general\n1 1\n1.0\n2.0\n";
+        assert_eq!(syntax_line(read::<f64>(text)), 4);
+        let text = "%%MatrixMarket matrix coordinate real general\n1 1 1\n1 1 1.5\n";
+        assert_eq!(syntax_line(read::<i32>(text)), 3);
+        let text = "%%MatrixMarket matrix coordinate integer skew-symmetric\n2 2 1\n1 1 1\n";
+        assert_eq!(syntax_line(read::<i32>(text)), 3);
+    }
+
+    #[test]
+    fn test_declared_sizes() {
+        // 宣言された大きさだけでは確保せず、元が足りなければ書式の誤りとする
+        let text = "%%MatrixMarket matrix coordinate real general\n2 2 18446744073709551615\n1 1 1.0\n";
+        assert_eq!(syntax_line(read::<f64>(text)), 4);
+        let text = "%%MatrixMarket matrix array real general\n100000 100000\n1.0\n";
+        assert_eq!(syntax_line(read::<f64>(text)), 4);
+        let text = "%%MatrixMarket matrix array real symmetric\n100000 100000\n1.0\n";
+        assert_eq!(syntax_line(read::<f64>(text)), 4);
+        // 元の総数が usize に収まらない
+        let text = "%%MatrixMarket matrix array real general\n4294967296 4294967296\n1.0\n";
+        assert_eq!(syntax_line(read::<f64>(text)), 2);
+        let text = "%%MatrixMarket matrix coordinate real general\n%\n18446744073709551615 2 0\n";
+        assert_eq!(syntax_line(read::<f64>(text)), 3);
+        // 密行列として確保できない大きさ
+        let text = "%%MatrixMarket matrix coordinate real general\n18446744073709551615 0 0\n";
+        assert_eq!(syntax_line(read::<f64>(text)), 2);
+        let coo = CooMatrix::<f64>::read_matrix_market(text.as_bytes()).unwrap();
+        assert_eq!((coo.rows(), coo.cols(), coo.nnz()), (usize::MAX, 0, 0));
+        let text = "%%MatrixMarket matrix coordinate real general\n1 18446744073709551615 0\n";
+        assert_eq!(syntax_line(read::<f64>(text)), 2);
+        let text = "%%MatrixMarket matrix coordinate real general\n2 4611686018427387904 0\n";
+        assert_eq!(syntax_line(read::<f64>(text)), 2);
+    }
+
+    #[test]
+    fn test_write() {
+        let m = mat![f64: [1.5, 0.0], [0.0, -2.0], [0.0, 0.25]];
+        let coord = MtxHeader::new(MtxFormat::Coordinate, MtxField::Real, MtxSymmetry::General);
+        let text = write(&m, &coord).unwrap();
+        assert_eq!(text, "%%MatrixMarket matrix coordinate real general\n3 2 3\n1 1 1.5\n2 2 -2\n3 2 0.25\n");
+        assert_eq!(read::<f64>(&text).unwrap() == m, true);
+
+        let array = MtxHeader::new(MtxFormat::Array, MtxField::Real, MtxSymmetry::General);
+        let text = write(&m, &array).unwrap();
+        assert_eq!(text, "%%MatrixMarket matrix array real general\n3 2\n1.5\n0\n0\n0\n-2\n0.25\n");
+        assert_eq!(read::<f64>(&text).unwrap() == m, true);
+
+        let pattern = MtxHeader::new(MtxFormat::Coordinate, MtxField::Pattern, MtxSymmetry::General);
+        assert!(write(&m, &pattern).unwrap().ends_with("3 2 3\n1 1\n2 2\n3 2\n"));
+
+        let s = mat![i32: [4, 1], [1, 3]];
+        let sym = MtxHeader::new(MtxFormat::Array, MtxField::Integer, MtxSymmetry::Symmetric);
+        let text = write(&s, &sym).unwrap();
+        assert_eq!(text, "%%MatrixMarket matrix array integer symmetric\n2 2\n4\n1\n3\n");
+        assert_eq!(read::<i32>(&text).unwrap() == s, true);
+    }
+
+    #[test]
+    fn test_write_invalid() {
+        let m = mat![i32: [1, 2], [3, 4]];
+        let sym = MtxHeader::new(MtxFormat::Coordinate, MtxField::Integer, MtxSymmetry::Symmetric);
+        assert!(matches!(write(&m, &sym), Err(MtxError::Invalid(_))));
+        let real = MtxHeader::new(MtxFormat::Coordinate, MtxField::Real, MtxSymmetry::General);
+        assert!(matches!(write(&m, &real), Err(MtxError::Invalid(_))));
+        let pattern = MtxHeader::new(MtxFormat::Array, MtxField::Pattern, MtxSymmetry::General);
+        assert!(matches!(write(&m, &pattern), Err(MtxError::Invalid(_))));
+    }
+
+    #[test]
+    fn test_sparse() {
+        let text = "%%MatrixMarket matrix coordinate real symmetric\n3 3 3\n1 1 2\n3 1 -1\n2 2 4\n";
+        let s = CooMatrix::<f64>::read_matrix_market(text.as_bytes()).unwrap();
+        assert_eq!(s.nnz(), 4);
+        assert_eq!(s.get(0, 2), -1.0);
+        assert_eq!(s.to_dense() == read::<f64>(text).unwrap(), true);
+
+        let mut out = Vec::new();
+        s.write_matrix_market(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("%%MatrixMarket matrix coordinate real general\n3 3 4\n"));
+        let back = CooMatrix::<f64>::read_matrix_market(text.as_bytes()).unwrap();
+        assert_eq!(back.to_dense() == s.to_dense(), true);
+    }
+}
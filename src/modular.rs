@@ -0,0 +1,480 @@
+//! 剰余環・有限体 / modular arithmetic and finite fields
+//!
+//! 法 P の剰余類を表す元 `ModInt<P>` と、`Matrix<ModInt<P>>` 向けの掃き出し法
+//! (連立一次方程式の求解)、および `Matrix<bool>` を GF(2) 上の行列とみなした
+//! ビットパック掃き出し法を定義する。
+//!
+//! `ModInt<P>` は四則演算、`From<u8>`、`Display` を実装するため、num.rs の `det`, `inverse`, `tr` 等も
+//! そのまま利用できる。除算はモジュラ逆元との積として計算されるため、`inverse` は整数除算を経由しない。
+//! 除数が法 P と互いに素でない場合、除算はpanicする。体として扱う演算は P が素数であることを前提とする。
+//!
+//! ```rust
+//! use matrixa::core::Matrix;
+//! use matrixa::mat;
+//! use matrixa::modular::ModInt;
+//!
+//! type F7 = ModInt<7>;
+//! let m = mat![F7: [F7::new(1), F7::new(2)], [F7::new(3), F7::new(4)]];
+//! assert_eq!(m.det(), F7::new(-2));
+//! let inv = m.inverse().unwrap();
+//! assert_eq!(&m * &inv == m.identity().unwrap(), true);
+//! assert_eq!(m.rank(), 2);
+//! assert_eq!(m.solve(&[F7::new(5), F7::new(6)]).unwrap(), vec![F7::new(3), F7::new(1)]);
+//! ```
+//!
+
+use crate::core::Matrix;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// 法 P の剰余類 / integer modulo P
+///
+/// 値は常に `0..P` の範囲に正規化して保持する。P は 2 以上であること。
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ModInt<const P: u64>(u64);
+
+impl<const P: u64> ModInt<P> {
+    /// 剰余類生成
+    ///
+    /// 負数を含む任意の整数を `0..P` の代表元に正規化して生成する。
+    ///
+    pub fn new(v: i64) -> Self {
+        ModInt((v as i128).rem_euclid(P as i128) as u64)
+    }
+
+    /// 法
+    pub fn modulus() -> u64 {
+        P
+    }
+
+    /// 代表元
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
+    /// 冪乗
+    ///
+    /// 繰り返し二乗法で self^e を計算する。
+    ///
+    pub fn pow(&self, mut e: u64) -> Self {
+        let mut base = *self;
+        let mut res = ModInt(1 % P);
+        while e > 0 {
+            if e & 1 == 1 {
+                res = res * base;
+            }
+            base = base * base;
+            e >>= 1;
+        }
+        res
+    }
+
+    /// モジュラ逆元
+    ///
+    /// 拡張ユークリッド互除法で逆元を計算する。法 P と互いに素でない場合はNoneを返却する。
+    ///
+    pub fn inv(&self) -> Option<Self> {
+        let (mut r0, mut r1) = (P as i128, self.0 as i128);
+        let (mut t0, mut t1) = (0i128, 1i128);
+        while r1 != 0 {
+            let q = r0 / r1;
+            (r0, r1) = (r1, r0 - q * r1);
+            (t0, t1) = (t1, t0 - q * t1);
+        }
+        if r0 != 1 {
+            return None;
+        }
+        Some(ModInt(t0.rem_euclid(P as i128) as u64))
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl<const P: u64> From<u8> for ModInt<P> {
+    fn from(v: u8) -> Self {
+        ModInt(v as u64 % P)
+    }
+}
+
+impl<const P: u64> Add for ModInt<P> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        ModInt(((self.0 as u128 + other.0 as u128) % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> Sub for ModInt<P> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        ModInt(((self.0 as u128 + P as u128 - other.0 as u128) % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> Mul for ModInt<P> {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        ModInt(((self.0 as u128 * other.0 as u128) % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> Div for ModInt<P> {
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        Mul::mul(self, other.inv().expect("[modular error]: divisor is not invertible modulo P"))
+    }
+}
+
+impl<const P: u64> Neg for ModInt<P> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        ModInt(0) - self
+    }
+}
+
+impl<const P: u64> fmt::Display for ModInt<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/// 掃き出し法の本体
+///
+/// 先頭 cols 列について部分的に掃き出しを行い、ピボット列の一覧を返却する。
+/// reduce が true の場合はピボットより上の行も消去する(簡約行階段形)。
+///
+fn eliminate<const P: u64>(rows: &mut Matrix<ModInt<P>>, cols: usize, reduce: bool) -> Vec<usize> {
+    let mut pivots = Vec::new();
+    let mut r = 0;
+    for c in 0..cols {
+        if r == rows.rows() {
+            break;
+        }
+        let found = (r..rows.rows()).find_map(|i| rows.data[i][c].inv().map(|inv| (i, inv)));
+        let (p, inv) = match found {
+            Some(v) => v,
+            None => continue,
+        };
+        rows.row_replace(p, r).unwrap();
+        for v in rows.data[r].iter_mut() {
+            *v = *v * inv;
+        }
+        let start = if reduce { 0 } else { r + 1 };
+        for i in start..rows.rows() {
+            if i == r || rows.data[i][c].is_zero() {
+                continue;
+            }
+            let factor = rows.data[i][c];
+            for j in c..rows.cols() {
+                let sub = factor * rows.data[r][j];
+                rows.data[i][j] = rows.data[i][j] - sub;
+            }
+        }
+        pivots.push(c);
+        r += 1;
+    }
+    pivots
+}
+
+/// 剰余環上の行列演算 / linear algebra over Z/PZ
+///
+impl<const P: u64> Matrix<ModInt<P>> {
+    /// 連立一次方程式 Ax = b の求解
+    ///
+    /// 解が存在しない場合はErrを返却する。解が一意でない場合は、自由変数を0とした解の一つを返却する。
+    ///
+    pub fn solve(&self, b: &[ModInt<P>]) -> Result<Vec<ModInt<P>>, &str> {
+        if b.len() != self.rows() {
+            return Err("vector length not matched to the number of rows");
+        }
+        let cols = self.cols();
+        let mut aug = Matrix::new();
+        for (row, v) in self.data.iter().zip(b.iter()) {
+            let mut r = row.clone();
+            r.push(*v);
+            aug.data.push(r);
+        }
+        let pivots = eliminate(&mut aug, cols, true);
+        if aug.data[pivots.len()..].iter().any(|row| !row[cols].is_zero()) {
+            return Err("the system has no solution");
+        }
+        let mut x = vec![ModInt(0); cols];
+        for (r, c) in pivots.iter().enumerate() {
+            x[*c] = aug.data[r][cols];
+        }
+        Ok(x)
+    }
+}
+
+/// ビットパックされた GF(2) 上の行列
+///
+/// 各行を u64 のワード列として保持し、行の加算を XOR で行う。
+///
+struct PackedRows {
+    cols: usize,
+    rows: Vec<Vec<u64>>,
+}
+
+impl PackedRows {
+    fn from_matrix(m: &Matrix<bool>, extra: usize) -> Self {
+        let cols = m.cols() + extra;
+        let words = cols.div_ceil(64);
+        let rows = m
+            .data
+            .iter()
+            .map(|row| {
+                let mut packed = vec![0u64; words];
+                for (j, _) in row.iter().enumerate().filter(|(_, b)| **b) {
+                    packed[j / 64] |= 1 << (j % 64);
+                }
+                packed
+            })
+            .collect();
+        PackedRows { cols, rows }
+    }
+
+    fn get(&self, i: usize, j: usize) -> bool {
+        self.rows[i][j / 64] >> (j % 64) & 1 == 1
+    }
+
+    fn set(&mut self, i: usize, j: usize, v: bool) {
+        if v {
+            self.rows[i][j / 64] |= 1 << (j % 64);
+        } else {
+            self.rows[i][j / 64] &= !(1 << (j % 64));
+        }
+    }
+
+    fn eliminate(&mut self, cols: usize, reduce: bool) -> Vec<usize> {
+        let mut pivots = Vec::new();
+        let mut r = 0;
+        for c in 0..cols {
+            if r == self.rows.len() {
+                break;
+            }
+            let p = match (r..self.rows.len()).find(|i| self.get(*i, c)) {
+                Some(p) => p,
+                None => continue,
+            };
+            self.rows.swap(p, r);
+            let pivot = self.rows[r].clone();
+            let start = if reduce { 0 } else { r + 1 };
+            for i in start..self.rows.len() {
+                if i != r && self.get(i, c) {
+                    for (w, pw) in self.rows[i].iter_mut().zip(pivot.iter()) {
+                        *w ^= pw;
+                    }
+                }
+            }
+            pivots.push(c);
+            r += 1;
+        }
+        pivots
+    }
+
+    fn to_matrix(&self, from: usize, to: usize) -> Matrix<bool> {
+        let mut res = Matrix::new();
+        for i in 0..self.rows.len() {
+            res.data.push((from..to).map(|j| self.get(i, j)).collect());
+        }
+        res
+    }
+}
+
+/// GF(2) 上の行列演算 / linear algebra over GF(2)
+///
+/// true を 1、false を 0 とし、加算を XOR、乗算を AND とする体上の行列として扱う。
+///
+/// ```rust
+/// use matrixa::core::Matrix;
+/// use matrixa::mat;
+///
+/// let m = mat![bool: [true, true, false], [false, true, true], [true, false, true]];
+/// assert_eq!(m.gf2_rank(), 2);
+/// assert_eq!(m.gf2_solve(&[true, true, false]).unwrap(), vec![false, true, false]);
+/// assert!(m.gf2_solve(&[true, false, false]).is_err());
+/// ```
+///
+impl Matrix<bool> {
+    /// GF(2) 上の行階段形
+    ///
+    pub fn gf2_row_echelon(&self) -> Self {
+        let mut packed = PackedRows::from_matrix(self, 0);
+        packed.eliminate(self.cols(), false);
+        packed.to_matrix(0, self.cols())
+    }
+
+    /// GF(2) 上のランク
+    ///
+    pub fn gf2_rank(&self) -> usize {
+        PackedRows::from_matrix(self, 0).eliminate(self.cols(), false).len()
+    }
+
+    /// GF(2) 上の行列式
+    ///
+    /// 0x0 行列の行列式は1 (true) とする。
+    ///
+    pub fn gf2_det(&self) -> Result<bool, &str> {
+        if self.data.is_empty() {
+            return Ok(true);
+        }
+        self.is_square()?;
+        Ok(self.gf2_rank() == self.rows())
+    }
+
+    /// GF(2) 上の逆行列
+    ///
+    pub fn gf2_inverse(&self) -> Result<Self, &str> {
+        if self.data.is_empty() {
+            return Ok(Matrix::new());
+        }
+        self.is_square()?;
+        let n = self.rows();
+        let mut packed = PackedRows::from_matrix(self, n);
+        for i in 0..n {
+            packed.set(i, n + i, true);
+        }
+        if packed.eliminate(n, true).len() != n {
+            return Err("not a regular matrix");
+        }
+        Ok(packed.to_matrix(n, packed.cols))
+    }
+
+    /// GF(2) 上の連立一次方程式 Ax = b の求解
+    ///
+    /// 解が存在しない場合はErrを返却する。解が一意でない場合は、自由変数を0とした解の一つを返却する。
+    ///
+    pub fn gf2_solve(&self, b: &[bool]) -> Result<Vec<bool>, &str> {
+        if b.len() != self.rows() {
+            return Err("vector length not matched to the number of rows");
+        }
+        let cols = self.cols();
+        let mut packed = PackedRows::from_matrix(self, 1);
+        for (i, v) in b.iter().enumerate() {
+            packed.set(i, cols, *v);
+        }
+        let pivots = packed.eliminate(cols, true);
+        if (pivots.len()..self.rows()).any(|i| packed.get(i, cols)) {
+            return Err("the system has no solution");
+        }
+        let mut x = vec![false; cols];
+        for (r, c) in pivots.iter().enumerate() {
+            x[*c] = packed.get(r, cols);
+        }
+        Ok(x)
+    }
+}
+
+#[cfg(test)]
+mod tests_modular_matrix {
+    use crate::core::Matrix;
+    use crate::mat;
+    use crate::modular::ModInt;
+
+    type F7 = ModInt<7>;
+
+    fn f7(rows: &[&[i64]]) -> Matrix<F7> {
+        let mut m = Matrix::new();
+        for row in rows {
+            m.push(row.iter().map(|v| F7::new(*v)).collect()).unwrap();
+        }
+        m
+    }
+
+    #[test]
+    fn test_modint_arithmetic() {
+        assert_eq!(F7::new(-1).value(), 6);
+        assert_eq!(F7::new(5) + F7::new(4), F7::new(2));
+        assert_eq!(F7::new(2) - F7::new(5), F7::new(4));
+        assert_eq!(F7::new(3) * F7::new(5), F7::new(1));
+        assert_eq!(F7::new(1) / F7::new(3), F7::new(5));
+        assert_eq!(-F7::new(3), F7::new(4));
+        assert_eq!(F7::new(3).pow(6), F7::new(1));
+        assert_eq!(F7::new(0).inv(), None);
+        assert_eq!(ModInt::<12>::new(4).inv(), None);
+        assert_eq!(ModInt::<12>::new(5).inv(), Some(ModInt::<12>::new(5)));
+        let big = ModInt::<{ u64::MAX - 58 }>::new(-1);
+        assert_eq!(big * big, ModInt::new(1));
+        assert_eq!(format!("{}", F7::new(10)), "3");
+    }
+
+    #[test]
+    fn test_det_inverse() {
+        let m = f7(&[&[2, 3, 1], &[1, 0, 4], &[5, 6, 0]]);
+        assert_eq!(m.det(), F7::new(18));
+        let s = f7(&[&[1, 2], &[2, 4]]);
+        assert!(s.inverse().is_err());
+        let n = f7(&[&[2, 3, 1], &[1, 1, 4], &[5, 6, 0]]);
+        let inv = n.inverse().unwrap();
+        assert_eq!(&n * &inv == n.identity().unwrap(), true);
+    }
+
+    #[test]
+    fn test_row_echelon_rank() {
+        let m = f7(&[&[1, 2, 3], &[2, 4, 6], &[1, 0, 1]]);
+        assert_eq!(m.rank(), 2);
+        let e = m.row_echelon();
+        assert_eq!(e.row(0), f7(&[&[1, 2, 3]]).row(0));
+        assert_eq!(e.row(1), f7(&[&[0, 1, 1]]).row(0));
+        assert_eq!(e.row(2), f7(&[&[0, 0, 0]]).row(0));
+        assert_eq!(f7(&[&[0, 0], &[0, 0]]).rank(), 0);
+    }
+
+    #[test]
+    fn test_solve() {
+        let m = f7(&[&[2, 3, 1], &[1, 1, 4], &[5, 6, 0]]);
+        let x = vec![F7::new(1), F7::new(2), F7::new(3)];
+        let b: Vec<F7> = (0..3)
+            .map(|i| (0..3).fold(F7::new(0), |acc, j| acc + m.data[i][j] * x[j]))
+            .collect();
+        assert_eq!(m.solve(&b).unwrap(), x);
+
+        let s = f7(&[&[1, 2], &[2, 4]]);
+        assert!(s.solve(&[F7::new(1), F7::new(1)]).is_err());
+        assert_eq!(s.solve(&[F7::new(1), F7::new(2)]).unwrap(), vec![F7::new(1), F7::new(0)]);
+        assert!(s.solve(&[F7::new(1)]).is_err());
+    }
+
+    #[test]
+    fn test_gf2() {
+        let m = mat![bool: [true, false, true], [true, true, false], [false, true, true]];
+        assert_eq!(m.gf2_rank(), 2);
+        assert_eq!(m.gf2_det().unwrap(), false);
+        assert!(m.gf2_inverse().is_err());
+        let e = m.gf2_row_echelon();
+        assert_eq!(e.row(0), vec![true, false, true]);
+        assert_eq!(e.row(1), vec![false, true, true]);
+        assert_eq!(e.row(2), vec![false, false, false]);
+
+        let n = mat![bool: [true, true, false], [false, true, true], [false, false, true]];
+        assert_eq!(n.gf2_det().unwrap(), true);
+        let inv = n.gf2_inverse().unwrap();
+        assert_eq!(inv.row(0), vec![true, true, true]);
+        assert_eq!(inv.row(1), vec![false, true, true]);
+        assert_eq!(inv.row(2), vec![false, false, true]);
+        assert_eq!(n.gf2_solve(&[true, false, true]).unwrap(), vec![false, true, true]);
+        assert!(mat![bool: [true, false]].gf2_det().is_err());
+
+        let empty = Matrix::<bool>::new();
+        assert_eq!(empty.gf2_det(), Ok(true));
+        assert_eq!(empty.gf2_inverse().unwrap().data.len(), 0);
+    }
+
+    #[test]
+    fn test_gf2_wide() {
+        // 64列を超える行列でワード境界をまたぐ掃き出しを確認する
+        let n = 130;
+        let mut m = Matrix::new();
+        for i in 0..n {
+            m.push((0..n).map(|j| j == i || j == i + 1).collect()).unwrap();
+        }
+        assert_eq!(m.gf2_rank(), n);
+        let b: Vec<bool> = (0..n).map(|i| i == n - 1).collect();
+        let x = m.gf2_solve(&b).unwrap();
+        assert!(x.iter().all(|v| *v));
+        let inv = m.gf2_inverse().unwrap();
+        assert_eq!(inv.row(0), vec![true; n]);
+    }
+}
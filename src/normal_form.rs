@@ -0,0 +1,280 @@
+//! 整数行列の標準形 / normal forms of integer matrices
+//!
+//! `Matrix<i64>` に対し、ユニモジュラ行列(整数成分で行列式が ±1 の行列)による変換で
+//! エルミート標準形とスミス標準形を計算する。
+//! 途中の計算は全て検査付き演算で行い、i64 の範囲を超えた場合はErrを返却する。
+//!
+//! ```rust
+//! use matrixa::core::Matrix;
+//! use matrixa::mat;
+//!
+//! let a = mat![i64: [2, 4, 4], [-6, 6, 12], [10, -4, -16]];
+//! let (s, u, v) = a.smith_normal_form().unwrap();
+//! assert_eq!(s.row(0), vec![2, 0, 0]);
+//! assert_eq!(s.row(1), vec![0, 6, 0]);
+//! assert_eq!(s.row(2), vec![0, 0, 12]);
+//! assert_eq!(&(&u * &a) * &v == s, true);
+//! ```
+//!
+
+use crate::core::Matrix;
+
+const OVERFLOW: &str = "integer overflow during normal form computation";
+
+fn unit(n: usize) -> Vec<Vec<i64>> {
+    (0..n).map(|i| (0..n).map(|j| (i == j) as i64).collect()).collect()
+}
+
+fn to_matrix(data: Vec<Vec<i64>>) -> Matrix<i64> {
+    let mut m = Matrix::new();
+    m.data = data;
+    m
+}
+
+/// dst 行から src 行の q 倍を引く
+fn row_sub(m: &mut [Vec<i64>], dst: usize, src: usize, q: i64) -> Result<(), &'static str> {
+    for j in 0..m[dst].len() {
+        m[dst][j] = q
+            .checked_mul(m[src][j])
+            .and_then(|v| m[dst][j].checked_sub(v))
+            .ok_or(OVERFLOW)?;
+    }
+    Ok(())
+}
+
+/// dst 列から src 列の q 倍を引く
+fn col_sub(m: &mut [Vec<i64>], dst: usize, src: usize, q: i64) -> Result<(), &'static str> {
+    for row in m.iter_mut() {
+        row[dst] = q
+            .checked_mul(row[src])
+            .and_then(|v| row[dst].checked_sub(v))
+            .ok_or(OVERFLOW)?;
+    }
+    Ok(())
+}
+
+fn row_neg(m: &mut [Vec<i64>], i: usize) -> Result<(), &'static str> {
+    for v in m[i].iter_mut() {
+        *v = v.checked_neg().ok_or(OVERFLOW)?;
+    }
+    Ok(())
+}
+
+fn col_swap(m: &mut [Vec<i64>], a: usize, b: usize) {
+    for row in m.iter_mut() {
+        row.swap(a, b);
+    }
+}
+
+/// 整数行列の標準形 / normal forms for integer matrices
+///
+impl Matrix<i64> {
+    /// エルミート標準形
+    ///
+    /// 行基本変形により得られるエルミート標準形 H と、ユニモジュラ行列 U (U·A = H) を返却する。
+    /// H は行階段形であり、各ピボットは正、ピボットより上の元は 0 以上ピボット未満に簡約される。
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    /// use matrixa::mat;
+    ///
+    /// let a = mat![i64: [4, 6], [2, 5]];
+    /// let (h, u) = a.hermite_normal_form().unwrap();
+    /// assert_eq!(h.row(0), vec![2, 1]);
+    /// assert_eq!(h.row(1), vec![0, 4]);
+    /// assert_eq!(&u * &a == h, true);
+    /// ```
+    ///
+    pub fn hermite_normal_form(&self) -> Result<(Self, Self), &str> {
+        self.integrity_check()?;
+        let (rows, cols) = (self.rows(), self.cols());
+        let mut h = self.data.clone();
+        let mut u = unit(rows);
+
+        let mut r = 0;
+        for c in 0..cols {
+            if r == rows {
+                break;
+            }
+            while let Some(p) = (r..rows).filter(|i| h[*i][c] != 0).min_by_key(|i| h[*i][c].unsigned_abs()) {
+                h.swap(p, r);
+                u.swap(p, r);
+                let mut done = true;
+                for i in r + 1..rows {
+                    let q = h[i][c].checked_div(h[r][c]).ok_or(OVERFLOW)?;
+                    row_sub(&mut h, i, r, q)?;
+                    row_sub(&mut u, i, r, q)?;
+                    done &= h[i][c] == 0;
+                }
+                if done {
+                    break;
+                }
+            }
+            if h[r][c] == 0 {
+                continue;
+            }
+            if h[r][c] < 0 {
+                row_neg(&mut h, r)?;
+                row_neg(&mut u, r)?;
+            }
+            for i in 0..r {
+                let q = h[i][c].checked_div_euclid(h[r][c]).ok_or(OVERFLOW)?;
+                row_sub(&mut h, i, r, q)?;
+                row_sub(&mut u, i, r, q)?;
+            }
+            r += 1;
+        }
+        Ok((to_matrix(h), to_matrix(u)))
+    }
+
+    /// スミス標準形
+    ///
+    /// スミス標準形 S と、ユニモジュラ行列 U, V (U·A·V = S) を返却する。
+    /// S は対角成分のみが非負で、各対角成分は次の対角成分を割り切る。
+    ///
+    pub fn smith_normal_form(&self) -> Result<(Self, Self, Self), &str> {
+        self.integrity_check()?;
+        let (rows, cols) = (self.rows(), self.cols());
+        let mut s = self.data.clone();
+        let mut u = unit(rows);
+        let mut v = unit(cols);
+
+        for t in 0..rows.min(cols) {
+            loop {
+                let pivot = (t..rows)
+                    .flat_map(|i| (t..cols).map(move |j| (i, j)))
+                    .filter(|(i, j)| s[*i][*j] != 0)
+                    .min_by_key(|(i, j)| s[*i][*j].unsigned_abs());
+                let (p, q) = match pivot {
+                    Some(v) => v,
+                    None => return Ok((to_matrix(s), to_matrix(u), to_matrix(v))),
+                };
+                s.swap(p, t);
+                u.swap(p, t);
+                col_swap(&mut s, q, t);
+                col_swap(&mut v, q, t);
+
+                let mut done = true;
+                for i in t + 1..rows {
+                    let q = s[i][t].checked_div(s[t][t]).ok_or(OVERFLOW)?;
+                    row_sub(&mut s, i, t, q)?;
+                    row_sub(&mut u, i, t, q)?;
+                    done &= s[i][t] == 0;
+                }
+                for j in t + 1..cols {
+                    let q = s[t][j].checked_div(s[t][t]).ok_or(OVERFLOW)?;
+                    col_sub(&mut s, j, t, q)?;
+                    col_sub(&mut v, j, t, q)?;
+                    done &= s[t][j] == 0;
+                }
+                if !done {
+                    continue;
+                }
+
+                // 残りの元が全てピボットで割り切れるまで、割り切れない元を含む行を加える
+                let d = s[t][t];
+                match (t + 1..rows).find(|i| s[*i][t + 1..].iter().any(|x| x.wrapping_rem(d) != 0)) {
+                    Some(i) => {
+                        row_sub(&mut s, t, i, -1)?;
+                        row_sub(&mut u, t, i, -1)?;
+                    }
+                    None => break,
+                }
+            }
+            if s[t][t] < 0 {
+                row_neg(&mut s, t)?;
+                row_neg(&mut u, t)?;
+            }
+        }
+        Ok((to_matrix(s), to_matrix(u), to_matrix(v)))
+    }
+}
+
+#[cfg(test)]
+mod tests_normal_form {
+    use crate::core::Matrix;
+    use crate::mat;
+
+    fn is_unimodular(m: &Matrix<i64>) -> bool {
+        let d = m.det();
+        d == 1 || d == -1
+    }
+
+    #[test]
+    fn test_hermite_normal_form() {
+        let a = mat![i64: [2, 3, 6, 2], [5, 6, 1, 6], [8, 3, 1, 1]];
+        let (h, u) = a.hermite_normal_form().unwrap();
+        assert_eq!(&u * &a == h, true);
+        assert!(is_unimodular(&u));
+        let mut last = None;
+        for (r, row) in h.dump().iter().enumerate() {
+            let c = match row.iter().position(|x| *x != 0) {
+                Some(c) => c,
+                None => continue,
+            };
+            assert!(last.map_or(true, |l| c > l));
+            assert!(row[c] > 0);
+            for i in 0..r {
+                assert!(h.dump()[i][c] >= 0 && h.dump()[i][c] < row[c]);
+            }
+            last = Some(c);
+        }
+    }
+
+    #[test]
+    fn test_hermite_rank_deficient() {
+        let a = mat![i64: [2, 4], [3, 6], [-1, -2]];
+        let (h, u) = a.hermite_normal_form().unwrap();
+        assert_eq!(h.row(0), vec![1, 2]);
+        assert_eq!(h.row(1), vec![0, 0]);
+        assert_eq!(h.row(2), vec![0, 0]);
+        assert_eq!(&u * &a == h, true);
+        assert!(is_unimodular(&u));
+    }
+
+    #[test]
+    fn test_smith_normal_form() {
+        let a = mat![i64: [2, 4, 4], [-6, 6, 12], [10, 4, 16]];
+        let (s, u, v) = a.smith_normal_form().unwrap();
+        assert_eq!(&(&u * &a) * &v == s, true);
+        assert!(is_unimodular(&u) && is_unimodular(&v));
+        let d: Vec<i64> = (0..3).map(|i| s.dump()[i][i]).collect();
+        assert_eq!(d.iter().product::<i64>(), a.det().abs());
+        assert!(d.windows(2).all(|w| w[1] % w[0] == 0));
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!(i == j || s.dump()[i][j] == 0);
+            }
+        }
+
+        // 割り切り条件のために行の加算が必要となる例
+        let b = mat![i64: [2, 0], [0, 3]];
+        let (s, u, v) = b.smith_normal_form().unwrap();
+        assert_eq!(s.row(0), vec![1, 0]);
+        assert_eq!(s.row(1), vec![0, 6]);
+        assert_eq!(&(&u * &b) * &v == s, true);
+    }
+
+    #[test]
+    fn test_smith_rectangular() {
+        let a = mat![i64: [6, 4, 2], [4, 2, 0]];
+        let (s, u, v) = a.smith_normal_form().unwrap();
+        assert_eq!(s.row(0), vec![2, 0, 0]);
+        assert_eq!(s.row(1), vec![0, 2, 0]);
+        assert_eq!(&(&u * &a) * &v == s, true);
+        assert_eq!(u.rows(), 2);
+        assert_eq!(v.rows(), 3);
+    }
+
+    #[test]
+    fn test_overflow() {
+        let a = mat![i64: [i64::MAX, 1], [1, i64::MAX]];
+        assert!(a.hermite_normal_form().is_err());
+        assert!(a.smith_normal_form().is_err());
+
+        // i64::MIN / -1 の商が i64 の範囲を超える
+        assert!(mat![i64: [-1], [i64::MIN]].hermite_normal_form().is_err());
+        assert!(mat![i64: [-1, i64::MIN]].smith_normal_form().is_err());
+        assert!(mat![i64: [-1], [i64::MIN]].smith_normal_form().is_err());
+    }
+}
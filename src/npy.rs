@@ -0,0 +1,589 @@
+//! NumPy形式の入出力 / NumPy .npy and .npz interchange
+//!
+//! NumPy の `numpy.save` が出力する .npy 形式の二次元配列を `Matrix<T>` として読み書きする。
+//! 元の型は `i8`〜`i64`, `u8`〜`u64`, `f32`, `f64`, `bool` に対応し、
+//! ヘッダの dtype (型・バイト順) と `fortran_order` (列優先の格納) に従って読み込む。
+//! dtype が元の型と一致しない場合や二次元でない配列はエラーとする。
+//! `numpy.savez` が出力する無圧縮の .npz 形式からは、名前を指定して配列を読み込める。
+//!
+//! ```rust
+//! use matrixa::core::Matrix;
+//! use matrixa::mat;
+//!
+//! let m = mat![f64: [1.0, 2.5], [-3.0, 0.125]];
+//! let mut bytes = Vec::new();
+//! m.write_npy(&mut bytes).unwrap();
+//! assert_eq!(&bytes[..6], b"\x93NUMPY");
+//! let back = Matrix::<f64>::read_npy(&bytes[..]).unwrap();
+//! assert_eq!(back == m, true);
+//!
+//! assert!(Matrix::<f32>::read_npy(&bytes[..]).is_err());
+//! ```
+//!
+
+use crate::core::Matrix;
+use std::convert::TryInto;
+use std::fmt;
+use std::io::{Read, Seek, SeekFrom, Write};
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+
+/// NumPy形式の入出力エラー / NumPy format errors
+///
+#[derive(Debug)]
+pub enum NpyError {
+    /// 入出力の失敗
+    Io(std::io::Error),
+    /// マジックナンバーやヘッダの書式の誤り
+    Format(String),
+    /// 対応していない dtype
+    UnsupportedDtype(String),
+    /// ヘッダの dtype と読み込み先の元の型の不一致
+    DtypeMismatch { expected: String, found: String },
+    /// 二次元でない配列
+    Shape(Vec<usize>),
+    /// 指定した名前の配列が .npz に存在しない
+    MissingArray(String),
+    /// 書き出せない行列
+    Invalid(String),
+}
+
+impl fmt::Display for NpyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NpyError::Io(e) => write!(f, "I/O error: {}", e),
+            NpyError::Format(message) => write!(f, "invalid npy data: {}", message),
+            NpyError::UnsupportedDtype(descr) => write!(f, "unsupported dtype {:?}", descr),
+            NpyError::DtypeMismatch { expected, found } => {
+                write!(f, "dtype mismatch: expected {:?}, found {:?}", expected, found)
+            }
+            NpyError::Shape(shape) => write!(f, "expected a 2-D array, found shape {:?}", shape),
+            NpyError::MissingArray(name) => write!(f, "array {:?} not found in npz archive", name),
+            NpyError::Invalid(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for NpyError {}
+
+impl From<std::io::Error> for NpyError {
+    fn from(e: std::io::Error) -> Self {
+        NpyError::Io(e)
+    }
+}
+
+/// NumPy形式で読み書きできる元 / element types of NumPy arrays
+///
+pub trait NpyValue: Copy {
+    /// dtype の種類を表す文字 (`i`, `u`, `f`, `b`)
+    const KIND: char;
+    /// 一つの元のバイト数
+    const SIZE: usize;
+
+    /// SIZE バイトからの変換
+    fn from_npy_bytes(bytes: &[u8], big_endian: bool) -> Self;
+
+    /// リトルエンディアンのバイト列として out へ追記する
+    fn write_npy_bytes(&self, out: &mut Vec<u8>);
+}
+
+macro_rules! impl_npy_value {
+    ($($t:ty => $kind:expr),*) => {
+        $(
+            impl NpyValue for $t {
+                const KIND: char = $kind;
+                const SIZE: usize = std::mem::size_of::<$t>();
+
+                fn from_npy_bytes(bytes: &[u8], big_endian: bool) -> Self {
+                    let bytes = bytes.try_into().unwrap();
+                    if big_endian {
+                        <$t>::from_be_bytes(bytes)
+                    } else {
+                        <$t>::from_le_bytes(bytes)
+                    }
+                }
+
+                fn write_npy_bytes(&self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+            }
+        )*
+    };
+}
+
+impl_npy_value!(
+    i8 => 'i', i16 => 'i', i32 => 'i', i64 => 'i',
+    u8 => 'u', u16 => 'u', u32 => 'u', u64 => 'u',
+    f32 => 'f', f64 => 'f'
+);
+
+impl NpyValue for bool {
+    const KIND: char = 'b';
+    const SIZE: usize = 1;
+
+    fn from_npy_bytes(bytes: &[u8], _big_endian: bool) -> Self {
+        bytes[0] != 0
+    }
+
+    fn write_npy_bytes(&self, out: &mut Vec<u8>) {
+        out.push(*self as u8);
+    }
+}
+
+/// 元の型に対応する dtype の記述 (`<f8`, `|u1`, `|b1` 等)
+fn descr_of<T: NpyValue>() -> String {
+    let order = if T::SIZE == 1 { '|' } else { '<' };
+    format!("{}{}{}", order, T::KIND, T::SIZE)
+}
+
+/// ヘッダの辞書 `{'descr': '<f8', 'fortran_order': False, 'shape': (2, 3), }` の解析結果
+struct Header {
+    descr: String,
+    fortran_order: bool,
+    shape: Vec<usize>,
+}
+
+/// 辞書から key の値の文字列を切り出す
+fn header_value<'a>(dict: &'a str, key: &str) -> Result<&'a str, NpyError> {
+    let missing = || NpyError::Format(format!("header has no {:?} key", key));
+    let start = ["'", "\""]
+        .iter()
+        .find_map(|q| dict.find(&format!("{}{}{}", q, key, q)).map(|i| i + key.len() + 2))
+        .ok_or_else(missing)?;
+    let rest = dict[start..].trim_start();
+    let rest = rest.strip_prefix(':').ok_or_else(missing)?.trim_start();
+    let end = match rest.chars().next() {
+        Some('(') => rest.find(')').map(|i| i + 1),
+        Some(q @ '\'') | Some(q @ '"') => rest[1..].find(q).map(|i| i + 2),
+        _ => rest.find([',', '}']),
+    };
+    end.map(|i| rest[..i].trim()).ok_or_else(|| NpyError::Format(format!("malformed value for {:?}", key)))
+}
+
+impl Header {
+    fn parse(dict: &str) -> Result<Self, NpyError> {
+        let dict = dict.trim();
+        if !dict.starts_with('{') || !dict.ends_with('}') {
+            return Err(NpyError::Format("header is not a dictionary".to_string()));
+        }
+        let descr = header_value(dict, "descr")?;
+        if descr.len() < 2 || !(descr.starts_with('\'') || descr.starts_with('"')) {
+            return Err(NpyError::UnsupportedDtype(descr.to_string()));
+        }
+        let descr = descr[1..descr.len() - 1].to_string();
+        let fortran_order = match header_value(dict, "fortran_order")? {
+            "True" => true,
+            "False" => false,
+            other => return Err(NpyError::Format(format!("invalid fortran_order {:?}", other))),
+        };
+        let shape = header_value(dict, "shape")?;
+        let shape = shape
+            .trim_start_matches('(')
+            .trim_end_matches(')')
+            .split(',')
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.trim_end_matches('L').parse())
+            .collect::<Result<Vec<usize>, _>>()
+            .map_err(|_| NpyError::Format(format!("invalid shape {:?}", shape)))?;
+        Ok(Header { descr, fortran_order, shape })
+    }
+
+    /// dtype が T と一致するかを検証し、ビッグエンディアンかどうかを返却する
+    fn check_dtype<T: NpyValue>(&self) -> Result<bool, NpyError> {
+        let mut chars = self.descr.chars();
+        let (order, kind, size) = match (chars.next(), chars.next(), chars.as_str().parse::<usize>()) {
+            (Some(order), Some(kind), Ok(size)) if "<>|=".contains(order) && "iufb".contains(kind) => {
+                (order, kind, size)
+            }
+            _ => return Err(NpyError::UnsupportedDtype(self.descr.clone())),
+        };
+        let supported = match kind {
+            'i' | 'u' => [1, 2, 4, 8].contains(&size),
+            'f' => size == 4 || size == 8,
+            _ => size == 1,
+        };
+        if !supported {
+            return Err(NpyError::UnsupportedDtype(self.descr.clone()));
+        }
+        if kind != T::KIND || size != T::SIZE {
+            return Err(NpyError::DtypeMismatch { expected: descr_of::<T>(), found: self.descr.clone() });
+        }
+        Ok(order == '>' || (order == '=' && cfg!(target_endian = "big")))
+    }
+}
+
+/// NumPy形式の入出力 / NumPy reading and writing
+///
+impl<T: NpyValue> Matrix<T> {
+    /// .npy 形式の読み込み
+    ///
+    /// 形式のバージョン 1.0, 2.0, 3.0 に対応する。
+    /// ヘッダの dtype の型とバイト数は T と一致する必要がある (バイト順は問わない)。
+    /// データが shape に満たない場合は `NpyError::Io` を返却する。
+    ///
+    pub fn read_npy<R: Read>(mut reader: R) -> Result<Matrix<T>, NpyError> {
+        let mut preamble = [0u8; 8];
+        reader.read_exact(&mut preamble)?;
+        if &preamble[..6] != MAGIC {
+            return Err(NpyError::Format("missing magic string".to_string()));
+        }
+        let header_len = match preamble[6] {
+            1 => {
+                let mut len = [0u8; 2];
+                reader.read_exact(&mut len)?;
+                u16::from_le_bytes(len) as usize
+            }
+            2 | 3 => {
+                let mut len = [0u8; 4];
+                reader.read_exact(&mut len)?;
+                u32::from_le_bytes(len) as usize
+            }
+            v => return Err(NpyError::Format(format!("unsupported format version {}.{}", v, preamble[7]))),
+        };
+        let mut dict = vec![0u8; header_len];
+        reader.read_exact(&mut dict)?;
+        let dict = String::from_utf8(dict).map_err(|_| NpyError::Format("header is not UTF-8".to_string()))?;
+        let header = Header::parse(&dict)?;
+        let big_endian = header.check_dtype::<T>()?;
+        let (rows, cols) = match header.shape[..] {
+            [rows, cols] => (rows, cols),
+            _ => return Err(NpyError::Shape(header.shape)),
+        };
+
+        let len = rows
+            .checked_mul(cols)
+            .and_then(|n| n.checked_mul(T::SIZE))
+            .ok_or_else(|| NpyError::Format(format!("shape {:?} is too large", header.shape)))?;
+        // 宣言された shape の分を先に確保せず、実際に読めたバイト数を確かめる
+        let mut bytes = Vec::new();
+        reader.by_ref().take(len as u64).read_to_end(&mut bytes)?;
+        if bytes.len() != len {
+            return Err(NpyError::Io(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                format!("expected {} bytes of data, found {}", len, bytes.len()),
+            )));
+        }
+        let values: Vec<T> = bytes.chunks_exact(T::SIZE).map(|b| T::from_npy_bytes(b, big_endian)).collect();
+        // 列数0の shape はデータを持たないため、行数だけで確保できるかを確かめる
+        let mut data = Vec::new();
+        data.try_reserve_exact(rows)
+            .map_err(|_| NpyError::Format(format!("shape {:?} is too large", header.shape)))?;
+        data.extend((0..rows).map(|i| {
+            (0..cols)
+                .map(|j| if header.fortran_order { values[j * rows + i] } else { values[i * cols + j] })
+                .collect()
+        }));
+        Ok(Matrix::from_rows_unchecked(data))
+    }
+
+    /// .npy 形式 (バージョン 1.0、リトルエンディアン、行優先) の書き出し
+    ///
+    /// 行の長さが揃わない行列はエラーとする。
+    ///
+    pub fn write_npy<W: Write>(&self, mut writer: W) -> Result<(), NpyError> {
+        let rows = self.data.len();
+        let cols = self.data.first().map_or(0, |row| row.len());
+        if self.data.iter().any(|row| row.len() != cols) {
+            return Err(NpyError::Invalid("matrix rows have different lengths".to_string()));
+        }
+        let mut dict = format!(
+            "{{'descr': '{}', 'fortran_order': False, 'shape': ({}, {}), }}",
+            descr_of::<T>(),
+            rows,
+            cols
+        );
+        // マジックナンバーからヘッダの末尾の改行までを64バイトの倍数とする
+        let total = MAGIC.len() + 4 + dict.len() + 1;
+        dict.push_str(&" ".repeat((64 - total % 64) % 64));
+        dict.push('\n');
+        if dict.len() > u16::MAX as usize {
+            return Err(NpyError::Invalid("header is too long".to_string()));
+        }
+
+        let mut out = Vec::with_capacity(MAGIC.len() + 4 + dict.len() + rows * cols * T::SIZE);
+        out.extend_from_slice(MAGIC);
+        out.extend_from_slice(&[1, 0]);
+        out.extend_from_slice(&(dict.len() as u16).to_le_bytes());
+        out.extend_from_slice(dict.as_bytes());
+        for v in self.data.iter().flatten() {
+            v.write_npy_bytes(&mut out);
+        }
+        writer.write_all(&out)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// 無圧縮の .npz 形式からの読み込み
+    ///
+    /// name は `numpy.savez` に渡したキーワード (`arr_0` 等) で、末尾の `.npy` は省略できる。
+    /// 圧縮された (`numpy.savez_compressed` の) アーカイブと ZIP64 には対応しない。
+    ///
+    pub fn read_npz<R: Read + Seek>(mut reader: R, name: &str) -> Result<Matrix<T>, NpyError> {
+        let target = if name.ends_with(".npy") { name.to_string() } else { format!("{}.npy", name) };
+        let entries = npz_entries(&mut reader)?;
+        let entry = entries.iter().find(|e| e.name == target).ok_or_else(|| NpyError::MissingArray(name.to_string()))?;
+        if entry.method != 0 {
+            return Err(NpyError::Format(format!("{:?} is compressed (method {})", entry.name, entry.method)));
+        }
+
+        let mut local = [0u8; 30];
+        reader.seek(SeekFrom::Start(entry.offset))?;
+        reader.read_exact(&mut local)?;
+        if local[..4] != [0x50, 0x4b, 0x03, 0x04] {
+            return Err(NpyError::Format("invalid local file header".to_string()));
+        }
+        let skip = u16_at(&local, 26) as i64 + u16_at(&local, 28) as i64;
+        reader.seek(SeekFrom::Current(skip))?;
+        Matrix::read_npy(reader.take(entry.size))
+    }
+}
+
+/// .npz に含まれる配列の名前の一覧 (`.npy` を除く)
+///
+/// ```rust
+/// use matrixa::npy::npz_names;
+///
+/// let empty_zip = [0x50, 0x4b, 0x05, 0x06, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+/// assert_eq!(npz_names(std::io::Cursor::new(&empty_zip[..])).unwrap(), Vec::<String>::new());
+/// ```
+///
+pub fn npz_names<R: Read + Seek>(mut reader: R) -> Result<Vec<String>, NpyError> {
+    Ok(npz_entries(&mut reader)?
+        .into_iter()
+        .map(|e| e.name.strip_suffix(".npy").map_or(e.name.clone(), |s| s.to_string()))
+        .collect())
+}
+
+/// ZIP の中央ディレクトリの項目
+struct ZipEntry {
+    name: String,
+    method: u16,
+    size: u64,
+    offset: u64,
+}
+
+fn u16_at(b: &[u8], i: usize) -> u16 {
+    u16::from_le_bytes([b[i], b[i + 1]])
+}
+
+fn u32_at(b: &[u8], i: usize) -> u32 {
+    u32::from_le_bytes([b[i], b[i + 1], b[i + 2], b[i + 3]])
+}
+
+fn npz_entries<R: Read + Seek>(reader: &mut R) -> Result<Vec<ZipEntry>, NpyError> {
+    // 中央ディレクトリの終端レコード (22バイト + 最大65535バイトのコメント) を末尾から探す
+    let len = reader.seek(SeekFrom::End(0))?;
+    let tail_len = len.min(22 + u16::MAX as u64);
+    let mut tail = vec![0u8; tail_len as usize];
+    reader.seek(SeekFrom::Start(len - tail_len))?;
+    reader.read_exact(&mut tail)?;
+    let eocd = (0..tail.len().saturating_sub(21))
+        .rev()
+        .find(|&i| tail[i..i + 4] == [0x50, 0x4b, 0x05, 0x06])
+        .ok_or_else(|| NpyError::Format("not a zip archive".to_string()))?;
+    let count = u16_at(&tail, eocd + 10);
+    let dir_size = u32_at(&tail, eocd + 12);
+    let dir_offset = u32_at(&tail, eocd + 16);
+    if count == u16::MAX || dir_size == u32::MAX || dir_offset == u32::MAX {
+        return Err(NpyError::Format("zip64 archives are not supported".to_string()));
+    }
+
+    let mut dir = vec![0u8; dir_size as usize];
+    reader.seek(SeekFrom::Start(dir_offset as u64))?;
+    reader.read_exact(&mut dir)?;
+    let mut entries = Vec::with_capacity(count as usize);
+    let mut pos = 0;
+    for _ in 0..count {
+        if dir.len() < pos + 46 || dir[pos..pos + 4] != [0x50, 0x4b, 0x01, 0x02] {
+            return Err(NpyError::Format("invalid central directory".to_string()));
+        }
+        let name_len = u16_at(&dir, pos + 28) as usize;
+        let extra_len = u16_at(&dir, pos + 30) as usize;
+        let comment_len = u16_at(&dir, pos + 32) as usize;
+        let name = dir
+            .get(pos + 46..pos + 46 + name_len)
+            .ok_or_else(|| NpyError::Format("invalid central directory".to_string()))?;
+        let size = u32_at(&dir, pos + 20);
+        let offset = u32_at(&dir, pos + 42);
+        if size == u32::MAX || offset == u32::MAX {
+            return Err(NpyError::Format("zip64 archives are not supported".to_string()));
+        }
+        entries.push(ZipEntry {
+            name: String::from_utf8_lossy(name).into_owned(),
+            method: u16_at(&dir, pos + 10),
+            size: size as u64,
+            offset: offset as u64,
+        });
+        pos += 46 + name_len + extra_len + comment_len;
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests_matrix_npy {
+    use crate::core::Matrix;
+    use crate::mat;
+    use crate::npy::{npz_names, NpyError, NpyValue};
+    use std::io::Cursor;
+
+    /// NumPy 1.x の `numpy.save` と同じ形式のヘッダを持つ .npy
+    fn npy(dict: &str, data: &[u8]) -> Vec<u8> {
+        let mut dict = dict.to_string();
+        let total = 10 + dict.len() + 1;
+        dict.push_str(&" ".repeat((64 - total % 64) % 64));
+        dict.push('\n');
+        let mut res = b"\x93NUMPY\x01\x00".to_vec();
+        res.extend_from_slice(&(dict.len() as u16).to_le_bytes());
+        res.extend_from_slice(dict.as_bytes());
+        res.extend_from_slice(data);
+        res
+    }
+
+    /// 無圧縮の ZIP アーカイブ (CRC は読み込み時に検証しないため0とする)
+    fn zip(files: &[(&str, Vec<u8>, u16)]) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut dir = Vec::new();
+        for (name, data, method) in files {
+            let offset = out.len() as u32;
+            let mut common = Vec::new();
+            common.extend_from_slice(&20u16.to_le_bytes());
+            common.extend_from_slice(&0u16.to_le_bytes());
+            common.extend_from_slice(&method.to_le_bytes());
+            common.extend_from_slice(&[0; 8]);
+            common.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            common.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            common.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            common.extend_from_slice(&0u16.to_le_bytes());
+            out.extend_from_slice(&[0x50, 0x4b, 0x03, 0x04]);
+            out.extend_from_slice(&common);
+            out.extend_from_slice(name.as_bytes());
+            out.extend_from_slice(data);
+            dir.extend_from_slice(&[0x50, 0x4b, 0x01, 0x02, 20, 0]);
+            dir.extend_from_slice(&common);
+            dir.extend_from_slice(&[0; 10]);
+            dir.extend_from_slice(&offset.to_le_bytes());
+            dir.extend_from_slice(name.as_bytes());
+        }
+        let dir_offset = out.len() as u32;
+        out.extend_from_slice(&dir);
+        out.extend_from_slice(&[0x50, 0x4b, 0x05, 0x06, 0, 0, 0, 0]);
+        out.extend_from_slice(&(files.len() as u16).to_le_bytes());
+        out.extend_from_slice(&(files.len() as u16).to_le_bytes());
+        out.extend_from_slice(&(dir.len() as u32).to_le_bytes());
+        out.extend_from_slice(&dir_offset.to_le_bytes());
+        out.extend_from_slice(&[0, 0]);
+        out
+    }
+
+    fn roundtrip<T: NpyValue + PartialEq + std::fmt::Debug>(m: Matrix<T>) {
+        let mut bytes = Vec::new();
+        m.write_npy(&mut bytes).unwrap();
+        assert_eq!(bytes.len() % 64, (m.data.len() * m.data[0].len() * T::SIZE) % 64);
+        assert_eq!(Matrix::<T>::read_npy(&bytes[..]).unwrap() == m, true);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        roundtrip(mat![i8: [-128, 127]]);
+        roundtrip(mat![i16: [1, -2], [3, 4]]);
+        roundtrip(mat![i32: [i32::MIN], [i32::MAX]]);
+        roundtrip(mat![i64: [1, 2, 3]]);
+        roundtrip(mat![u8: [0, 255]]);
+        roundtrip(mat![u16: [65535]]);
+        roundtrip(mat![u32: [7, 8]]);
+        roundtrip(mat![u64: [u64::MAX, 0]]);
+        roundtrip(mat![f32: [0.1, -1.5e30]]);
+        roundtrip(mat![f64: [std::f64::consts::PI, 1e-300], [f64::INFINITY, -0.0]]);
+        roundtrip(mat![bool: [true, false], [false, true]]);
+    }
+
+    #[test]
+    fn test_header() {
+        let mut bytes = Vec::new();
+        mat![u8: [1, 2, 3]].write_npy(&mut bytes).unwrap();
+        let expected = npy("{'descr': '|u1', 'fortran_order': False, 'shape': (1, 3), }", &[1, 2, 3]);
+        assert_eq!(bytes, expected);
+        assert_eq!(bytes.len(), 128 + 3);
+    }
+
+    #[test]
+    fn test_read_variants() {
+        // 列優先
+        let data: Vec<u8> = [1i32, 4, 2, 5, 3, 6].iter().flat_map(|v| v.to_le_bytes().to_vec()).collect();
+        let bytes = npy("{'descr': '<i4', 'fortran_order': True, 'shape': (2, 3), }", &data);
+        assert_eq!(Matrix::<i32>::read_npy(&bytes[..]).unwrap() == mat![i32: [1, 2, 3], [4, 5, 6]], true);
+
+        // ビッグエンディアン
+        let data: Vec<u8> = [1.5f64, -2.0].iter().flat_map(|v| v.to_be_bytes().to_vec()).collect();
+        let bytes = npy("{'descr': '>f8', 'fortran_order': False, 'shape': (2, 1), }", &data);
+        assert_eq!(Matrix::<f64>::read_npy(&bytes[..]).unwrap() == mat![f64: [1.5], [-2.0]], true);
+
+        // バージョン 2.0 と二重引用符
+        let dict = "{\"descr\": \"|b1\", \"shape\": (1, 2), \"fortran_order\": False}\n";
+        let mut bytes = b"\x93NUMPY\x02\x00".to_vec();
+        bytes.extend_from_slice(&(dict.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(dict.as_bytes());
+        bytes.extend_from_slice(&[1, 0]);
+        assert_eq!(Matrix::<bool>::read_npy(&bytes[..]).unwrap() == mat![bool: [true, false]], true);
+
+        // 空の行列
+        let bytes = npy("{'descr': '<f4', 'fortran_order': False, 'shape': (0, 3), }", &[]);
+        assert_eq!(Matrix::<f32>::read_npy(&bytes[..]).unwrap().data.len(), 0);
+    }
+
+    #[test]
+    fn test_read_errors() {
+        let bytes = npy("{'descr': '<f8', 'fortran_order': False, 'shape': (3,), }", &[0; 24]);
+        assert!(matches!(Matrix::<f64>::read_npy(&bytes[..]), Err(NpyError::Shape(s)) if s == vec![3]));
+        let bytes = npy("{'descr': '<f8', 'fortran_order': False, 'shape': (1, 1, 1), }", &[0; 8]);
+        assert!(matches!(Matrix::<f64>::read_npy(&bytes[..]), Err(NpyError::Shape(_))));
+        let bytes = npy("{'descr': '<c16', 'fortran_order': False, 'shape': (1, 1), }", &[0; 16]);
+        assert!(matches!(Matrix::<f64>::read_npy(&bytes[..]), Err(NpyError::UnsupportedDtype(_))));
+        let bytes = npy("{'descr': '<U3', 'fortran_order': False, 'shape': (1, 1), }", &[0; 12]);
+        assert!(matches!(Matrix::<f64>::read_npy(&bytes[..]), Err(NpyError::UnsupportedDtype(_))));
+
+        let bytes = npy("{'descr': '<i8', 'fortran_order': False, 'shape': (1, 1), }", &[0; 8]);
+        match Matrix::<i32>::read_npy(&bytes[..]) {
+            Err(e @ NpyError::DtypeMismatch { .. }) => {
+                assert_eq!(e.to_string(), "dtype mismatch: expected \"<i4\", found \"<i8\"")
+            }
+            _ => panic!("expected a dtype mismatch"),
+        }
+        let bytes = npy("{'descr': '<i8', 'fortran_order': False, 'shape': (2, 2), }", &[0; 8]);
+        assert!(matches!(Matrix::<i64>::read_npy(&bytes[..]), Err(NpyError::Io(_))));
+        assert!(matches!(Matrix::<i64>::read_npy(&b"PK\x03\x04 not npy"[..]), Err(NpyError::Format(_))));
+        let bytes = npy("{'descr': '<i8', 'shape': (1, 1), }", &[0; 8]);
+        assert!(matches!(Matrix::<i64>::read_npy(&bytes[..]), Err(NpyError::Format(_))));
+
+        // 宣言された shape だけでは確保しない
+        let bytes = npy("{'descr': '<f8', 'fortran_order': False, 'shape': (100000, 100000), }", &[0; 8]);
+        assert!(matches!(Matrix::<f64>::read_npy(&bytes[..]), Err(NpyError::Io(_))));
+        let bytes = npy("{'descr': '<f8', 'fortran_order': False, 'shape': (18446744073709551615, 2), }", &[]);
+        assert!(matches!(Matrix::<f64>::read_npy(&bytes[..]), Err(NpyError::Format(_))));
+        let bytes = npy("{'descr': '<f8', 'fortran_order': False, 'shape': (18446744073709551615, 0), }", &[]);
+        assert!(matches!(Matrix::<f64>::read_npy(&bytes[..]), Err(NpyError::Format(_))));
+        let bytes = npy("{'descr': '<f8', 'fortran_order': False, 'shape': (3, 0), }", &[]);
+        assert_eq!(Matrix::<f64>::read_npy(&bytes[..]).unwrap().data, vec![Vec::<f64>::new(); 3]);
+    }
+
+    #[test]
+    fn test_npz() {
+        let mut a = Vec::new();
+        mat![f64: [1.0, 2.0], [3.0, 4.0]].write_npy(&mut a).unwrap();
+        let mut b = Vec::new();
+        mat![u16: [9]].write_npy(&mut b).unwrap();
+        let archive = zip(&[("weights.npy", a, 0), ("arr_0.npy", b.clone(), 0), ("packed.npy", b, 8)]);
+
+        assert_eq!(npz_names(Cursor::new(&archive)).unwrap(), vec!["weights", "arr_0", "packed"]);
+        let w = Matrix::<f64>::read_npz(Cursor::new(&archive), "weights").unwrap();
+        assert_eq!(w == mat![f64: [1.0, 2.0], [3.0, 4.0]], true);
+        let x = Matrix::<u16>::read_npz(Cursor::new(&archive), "arr_0.npy").unwrap();
+        assert_eq!(x == mat![u16: [9]], true);
+
+        assert!(matches!(Matrix::<f64>::read_npz(Cursor::new(&archive), "bias"), Err(NpyError::MissingArray(_))));
+        assert!(matches!(Matrix::<u16>::read_npz(Cursor::new(&archive), "packed"), Err(NpyError::Format(_))));
+        assert!(matches!(Matrix::<f32>::read_npz(Cursor::new(&archive), "weights"), Err(NpyError::DtypeMismatch { .. })));
+        assert!(matches!(Matrix::<f64>::read_npz(Cursor::new(b"not a zip"), "a"), Err(NpyError::Format(_))));
+    }
+}
@@ -1,97 +1,160 @@
-use crate::core::Matrix;
+use crate::core::{impl_elementwise_op, Matrix};
 use crate::mat;
-use std::ops::{Div, Rem, Shl, Shr};
 use PartialEq;
-use std::ops::{Add, Mul, Sub};
+use std::ops::{Mul, MulAssign};
 
-/// Addition / 行列の加算
+impl_elementwise_op!(
+    /// Addition / 行列の加算
+    ///
+    /// 行列の要素ごとの加算を行い、新規インスタンスとして結果を返却する。
+    /// 行および列の数が一致しない行列が指定された場合はパニックする。
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    /// use matrixa::mat;
+    ///
+    /// let m = mat![i32:[1,2],[3,4]];
+    /// let n = mat![i32:[-5,6],[7,-8]];
+    /// let ans = mat![i32:[-4,8],[10,-4]];
+    /// let res = m + n;
+    /// for i in 0..ans.rows() {
+    ///     for j in 0..ans.cols() {
+    ///         assert_eq!(res.row(i)[j], ans.row(i)[j]);
+    ///     }
+    /// }
+    /// ```
+    ///
+    Add, add, AddAssign, add_assign
+);
+
+impl_elementwise_op!(
+    /// Subtraction / 行列の減算
+    ///
+    /// 行列の要素ごとの減算を行い、新規インスタンスとして結果を返却する。
+    /// 行および列の数が一致しない行列が指定された場合はパニックする。
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    /// use matrixa::mat;
+    ///
+    /// let m = mat![i32:[1,2],[3,4]];
+    /// let n = mat![i32:[-5,6],[7,-8]];
+    /// let ans = mat![i32:[6,-4],[-4,12]];
+    /// let res = m - n;
+    /// for i in 0..ans.rows() {
+    ///     for j in 0..ans.cols() {
+    ///         assert_eq!(res.row(i)[j], ans.row(i)[j]);
+    ///     }
+    /// }
+    /// ```
+    ///
+    Sub, sub, SubAssign, sub_assign
+);
+
+/// 行列の積の計算ブロック長 / tile length for the matrix product
 ///
-/// 行列の要素ごとの加算を行い、新規インスタンスとして結果を返却する。
-/// 行および列の数が一致しない行列が指定された場合はパニックする。
+/// 転置した右オペランドの BLOCK x BLOCK 要素がキャッシュに収まる長さとする。
 ///
-/// ```rust
-/// use matrixa::core::Matrix;
-/// use matrixa::mat;
+const BLOCK: usize = 64;
+
+/// 並列計算時に1スレッドへ割り当てる最小の乗算回数 / minimum work per thread
+#[cfg(feature = "parallel")]
+const PARALLEL_THRESHOLD: usize = 1 << 16;
+
+/// ブロック化した行列の積 / cache-blocked matrix product
 ///
-/// let m = mat![i32:[1,2],[3,4]];
-/// let n = mat![i32:[-5,6],[7,-8]];
-/// let ans = mat![i32:[-4,8],[10,-4]];
-/// let res = m + n;
-/// for i in 0..ans.rows() {
-///     for j in 0..ans.cols() {
-///         assert_eq!(res.row(i)[j], ans.row(i)[j]);
-///     }
-/// }
-/// ```
+/// a の各行と転置済みの右オペランド bt の各行の内積を res に加算する。
+/// 各要素への加算は内側の添字の昇順で行うため、加算順序は素朴な三重ループと一致する。
 ///
-
-impl<T: std::ops::Add<Output = T>> Add for Matrix<T>
-    where
-        T: Copy + std::ops::Add<Output = T> + std::fmt::Debug + From<u8>,
+fn mul_block<T>(a: &[Vec<T>], bt: &[Vec<T>], res: &mut [Vec<T>])
+where
+    T: Copy + std::ops::Mul<Output = T> + std::ops::Add<Output = T>,
 {
-    type Output = Self;
-    fn add(self, other: Self) -> Self {
-        if !self.has_same_size_with(&other) {
-            panic!("abort");
-        }
-
-        let zero = T::from(0x0u8);
-        let mut res = Self::new();
-
-        for i in 0..self.data.len() {
-            if i >= res.data.len() {
-                res.data.push(Vec::new());
-            }
-            for j in 0..self.data[0].len() {
-                res.data[i].push(zero);
-                res.data[i][j] = self.data[i][j] + other.data[i][j];
+    let inner = bt.first().map_or(0, |row| row.len());
+    for jj in (0..bt.len()).step_by(BLOCK) {
+        let j_end = std::cmp::min(jj + BLOCK, bt.len());
+        for kk in (0..inner).step_by(BLOCK) {
+            let k_end = std::cmp::min(kk + BLOCK, inner);
+            for (a_row, res_row) in a.iter().zip(res.iter_mut()) {
+                let a_block = &a_row[kk..k_end];
+                for (datum, bt_row) in res_row[jj..j_end].iter_mut().zip(&bt[jj..j_end]) {
+                    let mut acc = *datum;
+                    for (x, y) in a_block.iter().zip(&bt_row[kk..k_end]) {
+                        acc = acc + *x * *y;
+                    }
+                    *datum = acc;
+                }
             }
         }
-        res
     }
 }
 
-/// Subtraction / 行列の減算
+/// 行列の積の行ブロック分割 / row-block dispatch of the matrix product
 ///
-/// 行列の要素ごとの減算を行い、新規インスタンスとして結果を返却する。
-/// 行および列の数が一致しない行列が指定された場合はパニックする。
+/// 解行列の行ブロックを std::thread::scope のワーカーに分割して計算する。
 ///
-/// ```rust
-/// use matrixa::core::Matrix;
-/// use matrixa::mat;
+#[cfg(feature = "parallel")]
+fn mul_rows<T>(a: &[Vec<T>], bt: &[Vec<T>], res: &mut [Vec<T>])
+where
+    T: Copy + std::ops::Mul<Output = T> + std::ops::Add<Output = T> + Send + Sync,
+{
+    let inner = bt.first().map_or(0, |row| row.len());
+    let work = a.len() * bt.len() * inner;
+    let threads = std::thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(work / PARALLEL_THRESHOLD)
+        .min(a.len());
+    if threads <= 1 {
+        return mul_block(a, bt, res);
+    }
+
+    let rows_per_thread = a.len().div_ceil(threads);
+    std::thread::scope(|s| {
+        for (a_rows, res_rows) in a.chunks(rows_per_thread).zip(res.chunks_mut(rows_per_thread)) {
+            s.spawn(move || mul_block(a_rows, bt, res_rows));
+        }
+    });
+}
+
+/// 解行列の計算関数 (a, 転置済みの右オペランド, 解)
+type Kernel<T> = fn(&[Vec<T>], &[Vec<T>], &mut [Vec<T>]);
+
+/// 行列の積の共通処理 / shared driver of the matrix product
 ///
-/// let m = mat![i32:[1,2],[3,4]];
-/// let n = mat![i32:[-5,6],[7,-8]];
-/// let ans = mat![i32:[6,-4],[-4,12]];
-/// let res = m - n;
-/// for i in 0..ans.rows() {
-///     for j in 0..ans.cols() {
-///         assert_eq!(res.row(i)[j], ans.row(i)[j]);
-///     }
-/// }
-/// ```
+/// 大きさを検証して右オペランドを転置し、kernel で解行列を計算する。
 ///
-
-impl<T: std::ops::Sub<Output = T>> Sub for Matrix<T>
-    where
-        T: Copy + std::ops::Sub<Output = T> + std::fmt::Debug + From<u8>,
+fn product<T>(a: &Matrix<T>, m: &Matrix<T>, kernel: Kernel<T>) -> Matrix<T>
+where
+    T: Copy + std::ops::Mul<Output = T> + std::ops::Add<Output = T> + std::fmt::Debug + From<u8>,
 {
-    type Output = Self;
-    fn sub(self, other: Self) -> Self {
+    a.integrity_check().unwrap();
+    m.integrity_check().unwrap();
+
+    if a.data[0].len() != m.data.len() {
+        panic!(
+            "column length of origin {} is not matched to the row length of the company {}",
+            a.data[0].len(),
+            m.data.len()
+        )
+    }
 
-        if !self.has_same_size_with(&other) {
-            panic!("abort");
-        }
+    //右オペランドを転置し、積の内側のループを連続したメモリへのアクセスとする
+    let mut bt: Vec<Vec<T>> = Vec::with_capacity(m.data[0].len());
+    for j in 0..m.data[0].len() {
+        bt.push(m.data.iter().map(|row| row[j]).collect());
+    }
 
-        let mut res = self.clone();
+    let zero = T::from(0x0u8);
+    let mut res = Matrix::new();
+    res.data = vec![vec![zero; bt.len()]; a.data.len()];
 
-        for i in 0..res.data.len() {
-            for j in 0..res.data[0].len() {
-                res.data[i][j] = self.data[i][j] - other.data[i][j];
-            }
-        }
-        res
+    //解行列の計算
+    kernel(&a.data, &bt, &mut res.data);
+
+    if a.debug {
+        println!("matrix product: {:?}", res.data);
     }
+    res
 }
 
 /// Product / 行列の積
@@ -132,155 +195,161 @@ impl<T: std::ops::Sub<Output = T>> Sub for Matrix<T>
 /// }
 /// ```
 ///
-
-impl<
-    T: std::ops::Mul<Output = T>
-    + std::ops::Sub<Output = T>
+impl<T> Mul<&Matrix<T>> for &Matrix<T>
+where
+    T: Copy
+    + std::ops::Mul<Output = T>
     + std::ops::Add<Output = T>
-    + std::fmt::Debug,
-> Mul for Matrix<T>
-    where
-        T: Copy
-        + std::ops::Mul<Output = T>
-        + std::ops::Sub<Output = T>
-        + std::ops::Add<Output = T>
-        + std::fmt::Debug
-        + From<u8>,
+    + std::fmt::Debug
+    + From<u8>,
 {
-    type Output = Self;
+    type Output = Matrix<T>;
 
-    fn mul(self, m: Self) -> Self {
-        self.integrity_check().unwrap();
-        m.integrity_check().unwrap();
-
-        if self.data.len() != m.data[0].len() {
-            panic!(
-                "row length of origin {} is not matched to the column length of the company {}",
-                self.data.len(),
-                m.data[0].len()
-            )
-        } else if self.data[0].len() != m.data.len() {
-            panic!(
-                "column length of origin {} is not matched to the row length of the company {}",
-                self.data[0].len(),
-                m.data.len()
-            )
-        }
-        //解行列のサイズ
-        let res_length: usize = self.data.len();
-
-        let mut res = Self::new();
-        let zero = T::from(0x0u8);
+    fn mul(self, m: &Matrix<T>) -> Matrix<T> {
+        product(self, m, mul_block)
+    }
+}
 
-        //解行列の計算
-        for i in 0..self.data.len() {
-            for j in 0..m.data.len() {
-                for seq in 0..res_length {
-                    if i >= res.data.len() {
-                        res.data.push(Vec::new());
-                    }
-                    if seq >= res.data[i].len() {
-                        res.data[i].push(zero);
-                    }
-                    if self.debug {
-                        println!(
-                            "i: {}, j: {}, seq: {}, where res.data is {:?}",
-                            i, j, seq, res.data
-                        );
-                    }
-                    res.data[i][seq] = res.data[i][seq] + self.data[i][j] * m.data[j][seq];
-                }
-            }
-        }
+impl<T> Mul for Matrix<T>
+where
+    T: Copy + std::ops::Mul<Output = T> + std::ops::Add<Output = T> + std::fmt::Debug + From<u8>,
+{
+    type Output = Matrix<T>;
+    fn mul(self, m: Matrix<T>) -> Matrix<T> {
+        &self * &m
+    }
+}
 
-        res
+impl<T> Mul<&Matrix<T>> for Matrix<T>
+where
+    T: Copy + std::ops::Mul<Output = T> + std::ops::Add<Output = T> + std::fmt::Debug + From<u8>,
+{
+    type Output = Matrix<T>;
+    fn mul(self, m: &Matrix<T>) -> Matrix<T> {
+        &self * m
     }
 }
 
+impl<T> Mul<Matrix<T>> for &Matrix<T>
+where
+    T: Copy + std::ops::Mul<Output = T> + std::ops::Add<Output = T> + std::fmt::Debug + From<u8>,
+{
+    type Output = Matrix<T>;
+    fn mul(self, m: Matrix<T>) -> Matrix<T> {
+        self * &m
+    }
+}
 
-/// Division / 商
-///
-/// 行列の要素ごとの商を計算し、新規インスタンスとして結果を返却する。
-///
-/// ```rust
-/// use matrixa::core::Matrix;
-/// use matrixa::mat;
+/// 行列の積の複合代入 / compound assignment of matrix product
 ///
-/// let mut m = mat![
-///     i32:
-///         [10,20,30],
-///         [40,50,70]
-/// ];
-/// let n = mat![
-///     i32:
-///         [1,2,3],
-///         [5,6,7]
-/// ];
-/// let res = mat![
-///     i32:
-///         [10,10,10],
-///         [8,8,10]
-/// ];
-///
-/// let p = m / n;
-///
-/// assert_eq!(p.rows(),2);
-/// assert_eq!(p.cols(),3);
-/// for i in 0..p.rows() {
-///     for j in 0..p.cols() {
-///         assert_eq!(p.dump()[i][j], res.dump()[i][j]);
-///     }
-/// }
-/// ```
+/// 行列の積は要素ごとの演算と異なり結果のサイズが変わり得るため、
+/// 計算結果で自身のデータを置き換える。
 ///
-impl<T: Copy + std::ops::Div<Output = T> + std::fmt::Debug> Div for Matrix<T>{
-    type Output = Self;
-    fn div(self, other: Self) -> Self::Output {
-        if !self.has_same_size_with(&other) {
-            panic!("abort");
-        }
-
-        let mut res = self.clone();
-
-        for i in 0..res.data.len() {
-            for j in 0..self.data[0].len() {
-                res.data[i][j] = res.data[i][j] / other.data[i][j];
-            }
-        }
-        res
+impl<T> MulAssign<&Matrix<T>> for Matrix<T>
+where
+    T: Copy + std::ops::Mul<Output = T> + std::ops::Add<Output = T> + std::fmt::Debug + From<u8>,
+{
+    fn mul_assign(&mut self, m: &Matrix<T>) {
+        let res = &*self * m;
+        self.data = res.data;
     }
 }
 
-/// 左ビットシフト演算 / left shift operator
-impl<T: Copy + std::ops::Shl<Output = T>> Shl for Matrix<T> {
-    type Output = Self;
-
-    fn shl(self, other: Self) -> Self::Output {
-        let mut res = self.clone();
-        for i in 0..res.data.len() {
-            for j in 0..res.data[i].len() {
-                res.data[i][j] = res.data[i][j] << other.data[i][j];
-            }
-        }
-        res
+impl<T> MulAssign<Matrix<T>> for Matrix<T>
+where
+    T: Copy + std::ops::Mul<Output = T> + std::ops::Add<Output = T> + std::fmt::Debug + From<u8>,
+{
+    fn mul_assign(&mut self, m: Matrix<T>) {
+        *self *= &m;
     }
 }
 
-/// 右ビットシフト演算 / left shift operator
-impl<T: Copy + std::ops::Shr<Output = T>> Shr for Matrix<T> {
-    type Output = Self;
+/// 並列化した行列の積 / parallel matrix product
+///
+/// `parallel` フィーチャの有効時に利用可能。
+/// `*` および `prod` は元の型に Send + Sync を要求しない逐次計算のままとし、
+/// 並列計算は本メソッドで明示的に選択する。
+///
+#[cfg(feature = "parallel")]
+impl<T> Matrix<T>
+where
+    T: Copy + std::ops::Mul<Output = T> + std::ops::Add<Output = T> + std::fmt::Debug + From<u8> + Send + Sync,
+{
+    /// 並列化した行列の積
+    ///
+    /// 解行列の行ブロックを std::thread::scope のワーカーに分割して計算する。
+    /// 計算量が小さい場合は逐次計算とする。結果は `prod` と一致する。
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    /// use matrixa::mat;
+    ///
+    /// let m = mat![i32: [1, 2, 3], [4, 5, 7]];
+    /// let n = mat![i32: [1, 3], [5, 7], [10, 10]];
+    /// assert_eq!(m.par_prod(n).unwrap() == mat![i32: [41, 47], [99, 117]], true);
+    /// ```
+    ///
+    pub fn par_prod(&self, m: Matrix<T>) -> Result<Self, &str> {
+        self.integrity_check().unwrap();
+        m.integrity_check().unwrap();
 
-    fn shr(self, other: Self) -> Self::Output {
-        let mut res = self.clone();
-        for i in 0..res.data.len() {
-            for j in 0..res.data[i].len() {
-                res.data[i][j] = res.data[i][j] >> other.data[i][j];
-            }
+        if self.data[0].len() != m.data.len() {
+            return Err("col length not matched to the row length of argument");
         }
-        res
+
+        Ok(product(self, &m, mul_rows))
     }
 }
 
+impl_elementwise_op!(
+    /// Division / 商
+    ///
+    /// 行列の要素ごとの商を計算し、新規インスタンスとして結果を返却する。
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    /// use matrixa::mat;
+    ///
+    /// let mut m = mat![
+    ///     i32:
+    ///         [10,20,30],
+    ///         [40,50,70]
+    /// ];
+    /// let n = mat![
+    ///     i32:
+    ///         [1,2,3],
+    ///         [5,6,7]
+    /// ];
+    /// let res = mat![
+    ///     i32:
+    ///         [10,10,10],
+    ///         [8,8,10]
+    /// ];
+    ///
+    /// let p = m / n;
+    ///
+    /// assert_eq!(p.rows(),2);
+    /// assert_eq!(p.cols(),3);
+    /// for i in 0..p.rows() {
+    ///     for j in 0..p.cols() {
+    ///         assert_eq!(p.dump()[i][j], res.dump()[i][j]);
+    ///     }
+    /// }
+    /// ```
+    ///
+    Div, div, DivAssign, div_assign
+);
+
+impl_elementwise_op!(
+    /// 左ビットシフト演算 / left shift operator
+    Shl, shl, ShlAssign, shl_assign
+);
+
+impl_elementwise_op!(
+    /// 右ビットシフト演算 / right shift operator
+    Shr, shr, ShrAssign, shr_assign
+);
+
 /// 数値計算用共通メソッド群 / methods for numeric calculation
 ///
 /// 整数型、浮動小数点型、虚数型に対する演算処理
@@ -482,13 +551,11 @@ where
         self.integrity_check().unwrap();
         m.integrity_check().unwrap();
 
-        if self.data.len() != m.data[0].len() {
-            return Err("row length not matched to the col length of argument");
-        } else if self.data[0].len() != m.data.len() {
+        if self.data[0].len() != m.data.len() {
             return Err("col length not matched to the row length of argument");
         }
 
-        Ok(self.clone() * m)
+        Ok(self * &m)
     }
 
     /// hadamard product / アダマール積
@@ -785,28 +852,13 @@ impl<T: std::ops::Rem<Output = T>> Matrix<T>
 
 }
 
-impl<T: std::ops::Rem<Output = T>> Rem for Matrix<T>
-where
-    T: Copy + std::ops::Rem<Output = T> + std::fmt::Display + std::fmt::Debug,
-{
-    type Output = Self;
+impl_elementwise_op!(
     /// 行列の要素ごとの剰余
     ///
     /// 整数型の行列のみサポート
     ///
-    fn rem(self, other: Self) -> Self {
-
-        let mut res = Matrix::<T>::new();
-
-        for i in 0..self.data.len() {
-            res.data.push(Vec::new());
-            for j in 0..self.data[0].len() {
-                res.data[i].push(self.data[i][j] % other.data[i][j]);
-            }
-        }
-        res
-    }
-}
+    Rem, rem, RemAssign, rem_assign
+);
 
 #[cfg(test)]
 mod tests_matrix_numeric_operator {
@@ -845,6 +897,44 @@ mod tests_matrix_numeric_operator {
         assert_eq!(m / n == res, true)
     }
 
+    #[test]
+    fn test_operator_on_references() {
+        let m = mat![i32: [1,2,3],[4,5,6],[7,8,9]];
+        let n = mat![i32: [2,3,4],[5,6,7],[8,9,10]];
+        assert_eq!(&m + &n == mat![i32: [3,5,7],[9,11,13],[15,17,19]], true);
+        assert_eq!(&m - &n == mat![i32: [-1,-1,-1],[-1,-1,-1],[-1,-1,-1]], true);
+        assert_eq!(&m * &n == mat![i32: [36,42,48],[81,96,111],[126,150,174]], true);
+        assert_eq!(&n / &m == mat![i32: [2,1,1],[1,1,1],[1,1,1]], true);
+        assert_eq!(&n % &m == mat![i32: [0,1,1],[1,1,1],[1,1,1]], true);
+        assert_eq!(m.clone() + &n == &m + n.clone(), true);
+        // 参照による演算の後も両オペランドを利用できる
+        assert_eq!(m.rows(), 3);
+        assert_eq!(n.rows(), 3);
+    }
+
+    #[test]
+    fn test_compound_assignment() {
+        let mut m = mat![i32: [1,2,3],[4,5,6],[7,8,9]];
+        let n = mat![i32: [2,3,4],[5,6,7],[8,9,10]];
+        m += &n;
+        assert_eq!(m == mat![i32: [3,5,7],[9,11,13],[15,17,19]], true);
+        m -= &n;
+        assert_eq!(m == mat![i32: [1,2,3],[4,5,6],[7,8,9]], true);
+        m *= &n;
+        assert_eq!(m == mat![i32: [36,42,48],[81,96,111],[126,150,174]], true);
+        m /= mat![i32: [2,2,2],[3,3,3],[6,6,6]];
+        assert_eq!(m == mat![i32: [18,21,24],[27,32,37],[21,25,29]], true);
+        m %= &n;
+        assert_eq!(m == mat![i32: [0,0,0],[2,2,2],[5,7,9]], true);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_compound_assignment_unmatched() {
+        let mut m = mat![i32: [1,2,3],[4,5,6]];
+        m += mat![i32: [1,2],[3,4]];
+    }
+
     #[test]
     fn test_percent(){
         let m = mat![i32: [1,2,3],[4,5,6],[-7,-8,-9]];
@@ -869,6 +959,16 @@ mod tests_matrix_bitshift_operator {
         assert_eq!(m << b == res, true)
     }
 
+    #[test]
+    fn test_shift_assign() {
+        let mut m = mat![i32: [1,2,3],[4,5,6],[7,8,9]];
+        let b = mat![i32: [1,1,1],[1,1,1],[1,1,2]];
+        m <<= &b;
+        assert_eq!(m == mat![i32: [2,4,6],[8,10,12],[14,16,36]], true);
+        m >>= &b;
+        assert_eq!(m == mat![i32: [1,2,3],[4,5,6],[7,8,9]], true);
+    }
+
     #[test]
     fn test_shr() {
         let m = mat![i32: [2,2,3],[4,5,6],[7,8,9]];
@@ -946,6 +1046,58 @@ mod tests_matrix_numeric_manipulation {
         assert_eq!(m == result, true);
     }
 
+    #[test]
+    fn test_prod_rectangular() {
+        let m = mat![
+            i32:
+                [1,2,3],
+                [4,5,7]
+        ];
+        let n = mat![
+            i32:
+                [1,0,2,1],
+                [0,1,1,3],
+                [2,2,0,1]
+        ];
+        let res = mat![
+            i32:
+                [7,8,4,10],
+                [18,19,13,26]
+        ];
+        assert_eq!(m.prod(n).unwrap() == res, true);
+    }
+
+    #[test]
+    fn test_prod_blocked_matches_naive() {
+        // ブロック長を跨ぐサイズで素朴な三重ループと同一の結果となること
+        let (rows, inner, cols) = (70, 130, 90);
+        let mut m = Matrix::<f64>::new();
+        let mut n = Matrix::<f64>::new();
+        for i in 0..rows {
+            m.push((0..inner).map(|k| ((i * 31 + k * 17) % 23) as f64 / 7.0).collect()).unwrap();
+        }
+        for k in 0..inner {
+            n.push((0..cols).map(|j| ((k * 13 + j * 29) % 19) as f64 / 3.0).collect()).unwrap();
+        }
+
+        let mut naive = vec![vec![0.0; cols]; rows];
+        for i in 0..rows {
+            for k in 0..inner {
+                for j in 0..cols {
+                    naive[i][j] = naive[i][j] + m.data[i][k] * n.data[k][j];
+                }
+            }
+        }
+
+        let p = &m * &n;
+        assert_eq!(p.rows(), rows);
+        assert_eq!(p.cols(), cols);
+        assert_eq!(p.data, naive);
+
+        #[cfg(feature = "parallel")]
+        assert_eq!(m.par_prod(n).unwrap().data, naive);
+    }
+
     #[test]
     #[should_panic]
     fn test_prod_error_unmatched() {
@@ -0,0 +1,252 @@
+//! rayon による並列演算 / parallel operations backed by rayon
+//!
+//! `rayon` フィーチャの有効時に利用可能な、行単位で並列化した要素ごとの変換・集約と、
+//! list.rs の文字列操作の並列版を定義する。
+//!
+//! ```rust
+//! use matrixa::core::Matrix;
+//! use matrixa::mat;
+//!
+//! let m = mat![i32: [1,2,3],[4,5,6]];
+//! let doubled = m.par_map(|x| x * 2);
+//! assert_eq!(doubled == mat![i32: [2,4,6],[8,10,12]], true);
+//! assert_eq!(m.par_fold(0, |acc, x| acc + x, |a, b| a + b), 21);
+//! ```
+//!
+
+use crate::core::Matrix;
+use rayon::prelude::*;
+
+/// [行列一般] 並列演算メソッド群
+///
+impl<T: Sync> Matrix<T> {
+    /// 並列写像 / parallel map
+    ///
+    /// 各元に関数fを適用した結果を元とする新規の行列を返却する。
+    ///
+    pub fn par_map<U, F>(&self, f: F) -> Matrix<U>
+    where
+        U: Send,
+        F: Fn(&T) -> U + Sync + Send,
+    {
+        let mut res = Matrix::new();
+        res.data = self
+            .data
+            .par_iter()
+            .map(|row| row.iter().map(&f).collect())
+            .collect();
+        res
+    }
+
+    /// 並列要素ごと二項演算 / parallel element-wise zip
+    ///
+    /// selfと引数行列の同一位置の元に関数fを適用した結果を元とする新規の行列を返却する。
+    /// 行および列の数が一致しない行列が指定された場合はパニックする。
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    /// use matrixa::mat;
+    ///
+    /// let m = mat![i32: [1,2],[3,4]];
+    /// let n = mat![f64: [0.5,0.5],[2.0,0.25]];
+    /// let res = m.par_zip_with(&n, |x, y| *x as f64 * y);
+    /// assert_eq!(res == mat![f64: [0.5,1.0],[6.0,1.0]], true);
+    /// ```
+    ///
+    pub fn par_zip_with<U, V, F>(&self, other: &Matrix<U>, f: F) -> Matrix<V>
+    where
+        U: Sync,
+        V: Send,
+        F: Fn(&T, &U) -> V + Sync + Send,
+    {
+        if self.data.len() != other.data.len()
+            || self
+                .data
+                .iter()
+                .zip(other.data.iter())
+                .any(|(row, other_row)| row.len() != other_row.len())
+        {
+            panic!("matrix size not matched for the element-wise operation");
+        }
+        let mut res = Matrix::new();
+        res.data = self
+            .data
+            .par_iter()
+            .zip(other.data.par_iter())
+            .map(|(row, other_row)| {
+                row.iter()
+                    .zip(other_row.iter())
+                    .map(|(x, y)| f(x, y))
+                    .collect()
+            })
+            .collect();
+        res
+    }
+
+    /// 並列畳み込み / parallel fold
+    ///
+    /// 各行の元をidentityを初期値としてfoldで畳み込み、行ごとの結果をreduceで集約する。
+    /// identityはreduceの単位元でなければならない。
+    ///
+    pub fn par_fold<A, F, R>(&self, identity: A, fold: F, reduce: R) -> A
+    where
+        A: Clone + Send + Sync,
+        F: Fn(A, &T) -> A + Sync + Send,
+        R: Fn(A, A) -> A + Sync + Send,
+    {
+        self.data
+            .par_iter()
+            .map(|row| row.iter().fold(identity.clone(), &fold))
+            .reduce(|| identity.clone(), &reduce)
+    }
+}
+
+impl<T: Send> Matrix<T> {
+    /// 並列更新 / parallel in-place update
+    ///
+    /// 各元に関数fを適用して自身のデータを更新し、自身への参照を返却する。
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    /// use matrixa::mat;
+    ///
+    /// let mut m = mat![i32: [1,2,3],[4,5,6]];
+    /// m.par_apply(|x| *x %= 4);
+    /// assert_eq!(m == mat![i32: [1,2,3],[0,1,2]], true);
+    /// ```
+    ///
+    pub fn par_apply<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(&mut T) + Sync + Send,
+    {
+        self.data
+            .par_iter_mut()
+            .for_each(|row| row.iter_mut().for_each(&f));
+        self
+    }
+}
+
+/// [文字列行列] 並列文字列操作メソッド群
+///
+/// list.rs に定義された判定・変換の並列版。
+///
+impl Matrix<String> {
+    /// 文字列一致判定(並列)
+    ///
+    pub fn par_contains(&self, pat: &str) -> Matrix<bool> {
+        self.par_map(|s| s.contains(pat))
+    }
+
+    /// 先頭文字列判定(並列)
+    ///
+    pub fn par_starts_with(&self, pat: &str) -> Matrix<bool> {
+        self.par_map(|s| s.starts_with(pat))
+    }
+
+    /// 終端文字列判定(並列)
+    ///
+    pub fn par_ends_with(&self, pat: &str) -> Matrix<bool> {
+        self.par_map(|s| s.ends_with(pat))
+    }
+
+    /// 空文字列判定(並列)
+    ///
+    pub fn par_is_empty(&self) -> Matrix<bool> {
+        self.par_map(|s| s.is_empty())
+    }
+
+    /// ASCII文字列判定(並列)
+    ///
+    pub fn par_is_ascii(&self) -> Matrix<bool> {
+        self.par_map(|s| s.is_ascii())
+    }
+
+    /// 文字列長行列の取得(並列)
+    ///
+    pub fn par_to_strlen(&self) -> Matrix<usize> {
+        self.par_map(|s| s.len())
+    }
+
+    /// 文字列置換(並列)
+    ///
+    pub fn par_replace(&mut self, from: &str, to: &str) -> &mut Self {
+        self.par_apply(|s| *s = s.replace(from, to))
+    }
+
+    /// 先頭及び終端空白文字除去(並列)
+    ///
+    pub fn par_trim(&mut self) -> &mut Self {
+        self.par_apply(|s| *s = String::from(s.trim()))
+    }
+}
+
+#[cfg(test)]
+mod tests_matrix_parallel {
+    use crate::core::Matrix;
+    use crate::mat;
+
+    #[test]
+    fn test_par_map() {
+        let m = mat![i32: [1,2,3],[4,5,6],[7,8,9]];
+        let res = mat![i32: [2,3,4],[5,6,7],[8,9,10]];
+        assert_eq!(m.par_map(|x| x + 1) == res, true);
+    }
+
+    #[test]
+    fn test_par_zip_with() {
+        let m = mat![i32: [1,2,3],[4,5,6]];
+        let n = mat![i32: [6,5,4],[3,2,1]];
+        let res = mat![i32: [7,7,7],[7,7,7]];
+        assert_eq!(m.par_zip_with(&n, |x, y| x + y) == res, true);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_par_zip_with_unmatched() {
+        let m = mat![i32: [1,2,3],[4,5,6]];
+        let n = mat![i32: [1,2],[3,4]];
+        m.par_zip_with(&n, |x, y| x + y);
+    }
+
+    #[test]
+    fn test_par_fold() {
+        let mut m = Matrix::<u64>::new();
+        for i in 0..100 {
+            m.push((0..100).map(|j| i * 100 + j).collect()).unwrap();
+        }
+        assert_eq!(m.par_fold(0, |acc, x| acc + x, |a, b| a + b), 49995000);
+        assert_eq!(m.par_fold(0, |acc, x| acc.max(*x), |a, b| a.max(b)), 9999);
+    }
+
+    #[test]
+    fn test_par_string_predicates() {
+        let s = mat![
+            &str:
+            ["akasaka","sakamoto","kosaka"],
+            ["ikasama","isasaka",""],
+            ["kawasaki","ishikawa","平川"]
+        ].to_string();
+        assert_eq!(s.par_contains("aka") == s.contains("aka"), true);
+        assert_eq!(s.par_starts_with("i") == s.starts_with("i"), true);
+        assert_eq!(s.par_ends_with("a") == s.ends_with("a"), true);
+        assert_eq!(s.par_is_empty() == s.is_empty(), true);
+        assert_eq!(s.par_is_ascii() == s.is_ascii(), true);
+        assert_eq!(s.par_to_strlen() == s.to_strlen(), true);
+    }
+
+    #[test]
+    fn test_par_string_transforms() {
+        let mut s = mat![
+            &str:
+            [" 可燃ごみ ", "燃えないごみ "],
+            ["資源ごみ", " 粗大ごみ"]
+        ].to_string();
+        let res = mat![
+            &str:
+            ["可燃ツイート", "燃えないツイート"],
+            ["資源ツイート", "粗大ツイート"]
+        ].to_string();
+        s.par_trim().par_replace("ごみ", "ツイート");
+        assert_eq!(s == res, true);
+    }
+}
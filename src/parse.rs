@@ -0,0 +1,282 @@
+//! 文字列行列の型変換 / parsing string matrices
+//!
+//! core.rs の `to_string()` の逆変換として、`Matrix<String>` の各元を `FromStr` を実装する型に変換する。
+//! `parse` は失敗した全ての元を位置と元の文字列付きで `ParseErrors` に収集し、
+//! `parse_lossy` は失敗した元をNoneとした `Matrix<Option<U>>` を返却する。
+//! `NumberFormat` を指定すると、桁区切り文字や小数点記号が異なる表記の数値も変換できる。
+//!
+//! ```rust
+//! use matrixa::core::Matrix;
+//! use matrixa::mat;
+//! use matrixa::parse::NumberFormat;
+//!
+//! let s = mat![&str: ["1", "2.5"], ["x", "-4"]].to_string();
+//! let errors = s.parse::<f64>().err().unwrap();
+//! assert_eq!(errors.errors.len(), 1);
+//! assert_eq!((errors.errors[0].row, errors.errors[0].col), (1, 0));
+//! assert_eq!(s.parse_lossy::<f64>().dump()[1], vec![None, Some(-4.0)]);
+//!
+//! let de = mat![&str: ["1.234,5", " 7,25 "]].to_string();
+//! let m = de.parse_with::<f64>(&NumberFormat::european()).unwrap();
+//! assert_eq!(m.dump()[0], vec![1234.5, 7.25]);
+//! ```
+//!
+
+use crate::core::Matrix;
+use std::fmt;
+use std::str::FromStr;
+
+/// 変換に失敗した元 / a cell that failed to parse
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub row: usize,
+    pub col: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {}) {:?}: {}", self.row, self.col, self.text, self.message)
+    }
+}
+
+/// 変換に失敗した元の一覧 / all cells that failed to parse
+///
+/// 行優先の順序で保持する。
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseErrors {
+    pub errors: Vec<ParseError>,
+}
+
+impl fmt::Display for ParseErrors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} cell(s) failed to parse", self.errors.len())?;
+        for e in self.errors.iter() {
+            write!(f, "\n  {}", e)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseErrors {}
+
+/// 数値表記 / number format
+///
+/// 変換前に前後の空白を除き、桁区切り文字を取り除き、小数点記号を `.` に置き換える。
+/// 桁区切り文字を含む位置が不正な表記は変換に失敗する。
+/// 既定値は空白の除去のみを行い、桁区切り文字なし、小数点記号 `.` とする。
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumberFormat {
+    pub thousands_separator: Option<char>,
+    pub decimal_separator: char,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        NumberFormat {
+            thousands_separator: None,
+            decimal_separator: '.',
+        }
+    }
+}
+
+impl NumberFormat {
+    /// 既定値による設定生成
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 桁区切り `,`、小数点 `.` の表記 (1,234.5)
+    pub fn english() -> Self {
+        Self::new().thousands_separator(',')
+    }
+
+    /// 桁区切り `.`、小数点 `,` の表記 (1.234,5)
+    pub fn european() -> Self {
+        Self::new().thousands_separator('.').decimal_separator(',')
+    }
+
+    /// 桁区切り文字
+    pub fn thousands_separator(mut self, sep: char) -> Self {
+        self.thousands_separator = Some(sep);
+        self
+    }
+
+    /// 小数点記号
+    pub fn decimal_separator(mut self, sep: char) -> Self {
+        self.decimal_separator = sep;
+        self
+    }
+
+    /// 表記を `FromStr` が受理する形式に正規化する
+    ///
+    /// 桁区切り文字は整数部で3桁ごとに置かれている場合のみ受理し、それ以外の位置にある場合はNoneを返却する。
+    ///
+    pub fn normalize(&self, text: &str) -> Option<String> {
+        let text = text.trim();
+        let (int, frac) = match text.split_once(self.decimal_separator) {
+            Some((int, frac)) => (int, Some(frac)),
+            None => (text, None),
+        };
+        let mut res = String::new();
+        match self.thousands_separator {
+            Some(sep) if int.contains(sep) => {
+                let groups: Vec<&str> = int.split(sep).collect();
+                let digits = |g: &str| g.chars().filter(|c| c.is_ascii_digit()).count();
+                let first = digits(groups[0]);
+                if first == 0 || first > 3 || groups[1..].iter().any(|g| g.len() != 3 || digits(g) != 3) {
+                    return None;
+                }
+                res.extend(groups);
+            }
+            _ => res.push_str(int),
+        }
+        if let Some(frac) = frac {
+            if self.thousands_separator.is_some_and(|sep| frac.contains(sep)) {
+                return None;
+            }
+            res.push('.');
+            res.push_str(frac);
+        }
+        Some(res)
+    }
+}
+
+/// 型変換 / parsing
+///
+impl Matrix<String> {
+    /// 各元を U に変換する
+    ///
+    /// 変換に失敗した元が一つでもある場合は、失敗した全ての元を収集した `ParseErrors` を返却する。
+    /// 元の文字列はそのまま `FromStr` に渡す。
+    ///
+    pub fn parse<U: FromStr>(&self) -> Result<Matrix<U>, ParseErrors>
+    where
+        U::Err: fmt::Display,
+    {
+        self.parse_cells(|s| Some(s.to_string()))
+    }
+
+    /// 数値表記を指定して各元を U に変換する
+    ///
+    pub fn parse_with<U: FromStr>(&self, format: &NumberFormat) -> Result<Matrix<U>, ParseErrors>
+    where
+        U::Err: fmt::Display,
+    {
+        self.parse_cells(|s| format.normalize(s))
+    }
+
+    /// 各元を U に変換し、失敗した元をNoneとする
+    ///
+    pub fn parse_lossy<U: FromStr>(&self) -> Matrix<Option<U>> {
+        self.map_cells(|s| s.parse().ok())
+    }
+
+    /// 数値表記を指定して各元を U に変換し、失敗した元をNoneとする
+    ///
+    pub fn parse_lossy_with<U: FromStr>(&self, format: &NumberFormat) -> Matrix<Option<U>> {
+        self.map_cells(|s| format.normalize(s).and_then(|s| s.parse().ok()))
+    }
+
+    fn map_cells<U, F: Fn(&str) -> U>(&self, f: F) -> Matrix<U> {
+        let mut res = Matrix::new();
+        for row in self.data.iter() {
+            res.data.push(row.iter().map(|s| f(s)).collect());
+        }
+        res
+    }
+
+    fn parse_cells<U, F>(&self, normalize: F) -> Result<Matrix<U>, ParseErrors>
+    where
+        U: FromStr,
+        U::Err: fmt::Display,
+        F: Fn(&str) -> Option<String>,
+    {
+        let mut res = Matrix::new();
+        let mut errors = Vec::new();
+        for (i, row) in self.data.iter().enumerate() {
+            let mut parsed = Vec::with_capacity(row.len());
+            for (j, text) in row.iter().enumerate() {
+                let result = match normalize(text) {
+                    Some(s) => s.parse::<U>().map_err(|e| e.to_string()),
+                    None => Err("invalid position of the thousands separator".to_string()),
+                };
+                match result {
+                    Ok(v) => parsed.push(v),
+                    Err(message) => errors.push(ParseError {
+                        row: i,
+                        col: j,
+                        text: text.clone(),
+                        message,
+                    }),
+                }
+            }
+            res.data.push(parsed);
+        }
+        if errors.is_empty() {
+            Ok(res)
+        } else {
+            Err(ParseErrors { errors })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_matrix_parse {
+    use crate::core::Matrix;
+    use crate::mat;
+    use crate::parse::NumberFormat;
+
+    #[test]
+    fn test_round_trip() {
+        let m = mat![i32: [1, -2, 3], [40, 50, -60]];
+        let parsed = m.to_string().parse::<i32>().unwrap();
+        assert_eq!(parsed == m, true);
+        let f = mat![f64: [0.5, -1e-3]];
+        assert_eq!(f.to_string().parse::<f64>().unwrap() == f, true);
+    }
+
+    #[test]
+    fn test_errors() {
+        let s = mat![&str: ["1", "two", "3"], ["", "5", "6.0"]].to_string();
+        let e = s.parse::<i64>().err().unwrap();
+        let cells: Vec<(usize, usize, &str)> = e.errors.iter().map(|e| (e.row, e.col, e.text.as_str())).collect();
+        assert_eq!(cells, vec![(0, 1, "two"), (1, 0, ""), (1, 2, "6.0")]);
+        assert!(e.to_string().starts_with("3 cell(s) failed to parse"));
+        assert!(e.to_string().contains("(0, 1) \"two\""));
+        assert!(s.parse::<f64>().is_err());
+        assert!(mat![&str: [" 1"]].to_string().parse::<i32>().is_err());
+    }
+
+    #[test]
+    fn test_lossy() {
+        let s = mat![&str: ["true", "no"], ["false", "TRUE"]].to_string();
+        let b = s.parse_lossy::<bool>();
+        assert_eq!(b.dump()[0], vec![Some(true), None]);
+        assert_eq!(b.dump()[1], vec![Some(false), None]);
+    }
+
+    #[test]
+    fn test_number_format() {
+        let en = mat![&str: ["1,234,567.25", " 42 "], ["-0.5", "1.234,5"]].to_string();
+        let lossy = en.parse_lossy_with::<f64>(&NumberFormat::english());
+        assert_eq!(lossy.dump()[0], vec![Some(1234567.25), Some(42.0)]);
+        assert_eq!(lossy.dump()[1], vec![Some(-0.5), None]);
+        let e = en.parse_with::<f64>(&NumberFormat::english()).err().unwrap();
+        assert_eq!((e.errors[0].row, e.errors[0].col), (1, 1));
+
+        let fr = NumberFormat::new().thousands_separator(' ').decimal_separator(',');
+        assert_eq!(fr.normalize("1 234 567,8").unwrap(), "1234567.8");
+        assert_eq!(fr.normalize("12 34,5"), None);
+        assert_eq!(fr.normalize("-1 234"), Some("-1234".to_string()));
+        let s = mat![&str: ["1 000", "2,5"]].to_string();
+        assert_eq!(s.parse_with::<f32>(&fr).unwrap().dump()[0], vec![1000.0, 2.5]);
+        assert_eq!(NumberFormat::default().normalize(" 1,5 ").unwrap(), "1,5");
+        assert_eq!(e.errors[0].message, "invalid position of the thousands separator");
+    }
+}
@@ -0,0 +1,215 @@
+//! シリアライズ / serde support
+//!
+//! `serde` フィーチャの有効時に `Matrix<T>` へ `Serialize` と `Deserialize` を実装する。
+//! 既定の表現は行の配列の配列 (`[[1, 2], [3, 4]]`) とし、
+//! `flat` モジュールを `#[serde(with = "matrixa::serialize::flat")]` で指定すると
+//! 行数・列数と行優先の一次元配列からなる `{"rows": 2, "cols": 2, "data": [1, 2, 3, 4]}` の表現となる。
+//! いずれの表現でも、デシリアライズ時に行列が長方形であることを検証し、不正な場合はエラーとする。
+//! 列数0で行を持つ行列 (`[[], []]`) はいずれの表現でもエラーとする。
+//!
+//! ```rust
+//! use matrixa::core::Matrix;
+//! use matrixa::mat;
+//!
+//! let m = mat![i32: [1, 2], [3, 4]];
+//! let json = serde_json::to_string(&m).unwrap();
+//! assert_eq!(json, "[[1,2],[3,4]]");
+//! let back: Matrix<i32> = serde_json::from_str(&json).unwrap();
+//! assert_eq!(back == m, true);
+//!
+//! let ragged = serde_json::from_str::<Matrix<i32>>("[[1,2],[3]]");
+//! assert!(ragged.is_err());
+//! ```
+//!
+
+use crate::core::Matrix;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// 行の一覧から行列を生成する
+///
+/// 行の長さが揃わない場合は行番号と長さを含むエラーを返却する。
+/// 平坦な表現と受け付ける行列を揃えるため、列数0で行を持つ場合もエラーとする。
+///
+fn from_rows<T, E: Error>(rows: Vec<Vec<T>>) -> Result<Matrix<T>, E> {
+    let cols = rows.first().map_or(0, |row| row.len());
+    if cols == 0 && !rows.is_empty() {
+        return Err(E::custom(format!("{} row(s) with 0 columns are not allowed", rows.len())));
+    }
+    if let Some((i, row)) = rows.iter().enumerate().find(|(_, row)| row.len() != cols) {
+        return Err(E::custom(format!("row {} has {} element(s), expected {}", i, row.len(), cols)));
+    }
+    Ok(Matrix::from_rows_unchecked(rows))
+}
+
+/// 行の配列の配列としてシリアライズする
+///
+impl<T: Serialize> Serialize for Matrix<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.data.serialize(serializer)
+    }
+}
+
+/// 行の配列の配列からデシリアライズする
+///
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Matrix<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        from_rows(Vec::<Vec<T>>::deserialize(deserializer)?)
+    }
+}
+
+/// 行数・列数と一次元配列による表現 / `{rows, cols, data}` representation
+///
+/// ```rust
+/// use matrixa::core::Matrix;
+/// use matrixa::mat;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Payload {
+///     #[serde(with = "matrixa::serialize::flat")]
+///     weights: Matrix<f64>,
+/// }
+///
+/// let p = Payload { weights: mat![f64: [1.0, 2.0, 3.0], [4.0, 5.0, 6.0]] };
+/// let json = serde_json::to_string(&p).unwrap();
+/// assert_eq!(json, r#"{"weights":{"rows":2,"cols":3,"data":[1.0,2.0,3.0,4.0,5.0,6.0]}}"#);
+/// let back: Payload = serde_json::from_str(&json).unwrap();
+/// assert_eq!(back.weights == p.weights, true);
+///
+/// let bad = r#"{"weights":{"rows":2,"cols":3,"data":[1.0,2.0]}}"#;
+/// assert!(serde_json::from_str::<Payload>(bad).is_err());
+/// ```
+///
+pub mod flat {
+    use super::from_rows;
+    use crate::core::Matrix;
+    use serde::de::Error as _;
+    use serde::ser::Error as _;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize)]
+    struct FlatRef<'a, T> {
+        rows: usize,
+        cols: usize,
+        data: Vec<&'a T>,
+    }
+
+    #[derive(Deserialize)]
+    struct Flat<T> {
+        rows: usize,
+        cols: usize,
+        data: Vec<T>,
+    }
+
+    /// `{rows, cols, data}` としてシリアライズする
+    ///
+    /// 行の長さが揃わない行列と、列数0で行を持つ行列はエラーとする。
+    ///
+    pub fn serialize<T: Serialize, S: Serializer>(m: &Matrix<T>, serializer: S) -> Result<S::Ok, S::Error> {
+        let cols = m.data.first().map_or(0, |row| row.len());
+        if m.data.iter().any(|row| row.len() != cols) {
+            return Err(S::Error::custom("matrix rows have different lengths"));
+        }
+        if cols == 0 && !m.data.is_empty() {
+            return Err(S::Error::custom("matrix rows have no elements"));
+        }
+        FlatRef { rows: m.data.len(), cols, data: m.data.iter().flatten().collect() }.serialize(serializer)
+    }
+
+    /// `{rows, cols, data}` からデシリアライズする
+    ///
+    /// data の長さが rows × cols と一致しない場合はエラーとする。
+    /// 列数0で行を持つ表現は、data によらず任意の行数を指定できてしまうためエラーとする。
+    ///
+    pub fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(deserializer: D) -> Result<Matrix<T>, D::Error> {
+        let Flat { rows, cols, data } = Flat::<T>::deserialize(deserializer)?;
+        if cols == 0 && rows > 0 {
+            return Err(D::Error::custom(format!("{} row(s) with 0 columns are not allowed", rows)));
+        }
+        if rows.checked_mul(cols) != Some(data.len()) {
+            return Err(D::Error::custom(format!(
+                "{} element(s) cannot form a {}x{} matrix",
+                data.len(),
+                rows,
+                cols
+            )));
+        }
+        let mut data = data.into_iter();
+        let rows = (0..rows).map(|_| data.by_ref().take(cols).collect()).collect();
+        from_rows(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests_matrix_serde {
+    use crate::core::Matrix;
+    use crate::mat;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize)]
+    struct Flat {
+        #[serde(with = "crate::serialize::flat")]
+        m: Matrix<String>,
+    }
+
+    #[test]
+    fn test_nested() {
+        let s = mat![&str: ["a", "\"b\""], ["日本", ""]].to_string();
+        let json = serde_json::to_string(&s).unwrap();
+        assert_eq!(json, r#"[["a","\"b\""],["日本",""]]"#);
+        let back: Matrix<String> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back == s, true);
+
+        let empty: Matrix<f64> = serde_json::from_str("[]").unwrap();
+        assert_eq!(empty.data.len(), 0);
+    }
+
+    #[test]
+    fn test_nested_errors() {
+        let e = serde_json::from_str::<Matrix<i32>>("[[1, 2], [3, 4], [5]]").err().unwrap();
+        assert!(e.to_string().starts_with("row 2 has 1 element(s), expected 2"));
+        assert!(serde_json::from_str::<Matrix<i32>>("[1, 2]").is_err());
+        assert!(serde_json::from_str::<Matrix<u8>>("[[256]]").is_err());
+        let e = serde_json::from_str::<Matrix<i32>>("[[], []]").err().unwrap();
+        assert!(e.to_string().starts_with("2 row(s) with 0 columns are not allowed"));
+        assert!(serde_json::from_str::<Matrix<i32>>("[[], [1]]").is_err());
+        assert_eq!(serde_json::from_str::<Matrix<i32>>("[]").unwrap().data.len(), 0);
+    }
+
+    #[test]
+    fn test_iterates_after_deserialize() {
+        let m: Matrix<i32> = serde_json::from_str("[[1, 2], [3, 4]]").unwrap();
+        assert_eq!(m.integrity_check().is_ok(), true);
+        assert_eq!(m.collect::<Vec<Vec<i32>>>(), vec![vec![1, 2], vec![3, 4]]);
+    }
+
+    #[test]
+    fn test_flat() {
+        let f = Flat { m: mat![&str: ["a", "b", "c"]].to_string() };
+        let json = serde_json::to_string(&f).unwrap();
+        assert_eq!(json, r#"{"m":{"rows":1,"cols":3,"data":["a","b","c"]}}"#);
+        let back: Flat = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.m == f.m, true);
+
+        let e = serde_json::from_str::<Flat>(r#"{"m":{"rows":2,"cols":2,"data":["a","b","c"]}}"#).err().unwrap();
+        assert!(e.to_string().starts_with("3 element(s) cannot form a 2x2 matrix"));
+        let zero: Flat = serde_json::from_str(r#"{"m":{"rows":0,"cols":5,"data":[]}}"#).unwrap();
+        assert_eq!(zero.m.data.len(), 0);
+        let zero: Flat = serde_json::from_str(r#"{"m":{"rows":0,"cols":0,"data":[]}}"#).unwrap();
+        assert_eq!(zero.m.data.len(), 0);
+
+        // 列数0の行数は data の長さで検証できないため、巨大な行数による割り当てを行わずにエラーとする
+        let huge = r#"{"m":{"rows":18446744073709551615,"cols":0,"data":[]}}"#;
+        let e = serde_json::from_str::<Flat>(huge).err().unwrap();
+        assert!(e.to_string().starts_with("18446744073709551615 row(s) with 0 columns are not allowed"));
+        assert!(serde_json::from_str::<Flat>(r#"{"m":{"rows":1,"cols":0,"data":[]}}"#).is_err());
+        let mut empty_rows = Matrix::<String>::new();
+        empty_rows.data = vec![vec![], vec![]];
+        assert!(serde_json::to_string(&Flat { m: empty_rows }).is_err());
+
+        let mut ragged = Matrix::<String>::new();
+        ragged.data = vec![vec!["a".to_string()], vec![]];
+        assert!(serde_json::to_string(&Flat { m: ragged }).is_err());
+    }
+}
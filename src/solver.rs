@@ -0,0 +1,742 @@
+//! 反復解法 / iterative solvers
+//!
+//! 線形方程式 Ax = b を、係数行列の逆行列を求めずに反復法で解く。
+//! 係数行列は `LinearOperator` トレイトを実装する任意の型で与えることができ、
+//! 密行列 `Matrix<f64>` および sparse.rs の各疎行列型について実装している。
+//!
+//! * `cg`: 共役勾配法 (対称正定値行列向け)
+//! * `bicgstab`: 安定化双共役勾配法
+//! * `gmres`: リスタート付き一般化最小残差法
+//!
+//! ```rust
+//! use matrixa::core::Matrix;
+//! use matrixa::mat;
+//! use matrixa::solver::{cg, Preconditioner, SolverOptions};
+//!
+//! let a = mat![f64: [4.0,1.0],[1.0,3.0]];
+//! let opts = SolverOptions::new().tol(1e-12).preconditioner(Preconditioner::Jacobi);
+//! let res = cg(&a, &[1.0, 2.0], &opts).unwrap();
+//! assert!(res.converged);
+//! assert!((res.x[0] - 1.0 / 11.0).abs() < 1e-10);
+//! assert!((res.x[1] - 7.0 / 11.0).abs() < 1e-10);
+//! ```
+//!
+
+use crate::core::Matrix;
+use crate::sparse::{CooMatrix, CscMatrix, CsrMatrix, SparseMatrix};
+
+/// 線形作用素 / linear operator
+///
+/// 反復解法に与える正方行列の抽象。ベクトルとの積 `apply` のみを必須とし、
+/// 前処理に必要な対角成分やCSR形式を提供できる型はそれぞれを実装する。
+///
+pub trait LinearOperator {
+    /// 作用素の次元(正方行列の行数)
+    fn dim(&self) -> usize;
+
+    /// 列数
+    ///
+    /// 反復解法は `dim` と一致しない (正方でない) 作用素をエラーとする。
+    /// 行の長さが揃わない密行列では None を返却する。既定では正方とみなして `dim` を返却する。
+    ///
+    fn col_dim(&self) -> Option<usize> {
+        Some(self.dim())
+    }
+
+    /// ベクトルとの積 Ax
+    fn apply(&self, x: &[f64]) -> Vec<f64>;
+
+    /// 対角成分 (Jacobi前処理用)
+    fn diagonal(&self) -> Option<Vec<f64>> {
+        None
+    }
+
+    /// CSR形式の係数行列 (ILU(0)前処理用)
+    fn to_csr(&self) -> Option<CsrMatrix<f64>> {
+        None
+    }
+}
+
+impl LinearOperator for Matrix<f64> {
+    fn dim(&self) -> usize {
+        self.data.len()
+    }
+
+    fn col_dim(&self) -> Option<usize> {
+        let cols = self.data.first().map_or(0, |row| row.len());
+        if self.data.iter().all(|row| row.len() == cols) {
+            Some(cols)
+        } else {
+            None
+        }
+    }
+
+    fn apply(&self, x: &[f64]) -> Vec<f64> {
+        self.data.iter().map(|row| dot(row, x)).collect()
+    }
+
+    fn diagonal(&self) -> Option<Vec<f64>> {
+        Some((0..self.data.len()).map(|i| self.data[i][i]).collect())
+    }
+
+    fn to_csr(&self) -> Option<CsrMatrix<f64>> {
+        Some(CsrMatrix::from_dense(self))
+    }
+}
+
+impl LinearOperator for CsrMatrix<f64> {
+    fn dim(&self) -> usize {
+        self.rows()
+    }
+
+    fn col_dim(&self) -> Option<usize> {
+        Some(self.cols())
+    }
+
+    fn apply(&self, x: &[f64]) -> Vec<f64> {
+        self.mul_vec(x).expect("[linear operator error] vector length not matched")
+    }
+
+    fn diagonal(&self) -> Option<Vec<f64>> {
+        Some((0..self.rows()).map(|i| self.get(i, i)).collect())
+    }
+
+    fn to_csr(&self) -> Option<CsrMatrix<f64>> {
+        Some(self.clone())
+    }
+}
+
+impl LinearOperator for CscMatrix<f64> {
+    fn dim(&self) -> usize {
+        self.rows()
+    }
+
+    fn col_dim(&self) -> Option<usize> {
+        Some(self.cols())
+    }
+
+    fn apply(&self, x: &[f64]) -> Vec<f64> {
+        self.mul_vec(x).expect("[linear operator error] vector length not matched")
+    }
+
+    fn diagonal(&self) -> Option<Vec<f64>> {
+        Some((0..self.rows()).map(|i| self.get(i, i)).collect())
+    }
+
+    fn to_csr(&self) -> Option<CsrMatrix<f64>> {
+        Some(CscMatrix::to_csr(self))
+    }
+}
+
+impl LinearOperator for CooMatrix<f64> {
+    fn dim(&self) -> usize {
+        self.rows()
+    }
+
+    fn col_dim(&self) -> Option<usize> {
+        Some(self.cols())
+    }
+
+    fn apply(&self, x: &[f64]) -> Vec<f64> {
+        self.mul_vec(x).expect("[linear operator error] vector length not matched")
+    }
+
+    fn diagonal(&self) -> Option<Vec<f64>> {
+        Some((0..self.rows()).map(|i| self.get(i, i)).collect())
+    }
+
+    fn to_csr(&self) -> Option<CsrMatrix<f64>> {
+        Some(CooMatrix::to_csr(self))
+    }
+}
+
+/// 前処理の種別 / preconditioner kind
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Preconditioner {
+    /// 前処理なし
+    None,
+    /// 対角スケーリング
+    Jacobi,
+    /// フィルインなし不完全LU分解
+    Ilu0,
+}
+
+/// 反復解法の設定 / solver options
+///
+/// 収束判定の相対残差 ||b - Ax|| / ||b|| の閾値、最大反復回数、
+/// GMRESのリスタート長および前処理の種別を保持する。
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolverOptions {
+    pub tol: f64,
+    pub max_iter: usize,
+    pub restart: usize,
+    pub preconditioner: Preconditioner,
+}
+
+impl Default for SolverOptions {
+    fn default() -> Self {
+        SolverOptions {
+            tol: 1e-8,
+            max_iter: 1000,
+            restart: 30,
+            preconditioner: Preconditioner::None,
+        }
+    }
+}
+
+impl SolverOptions {
+    /// 既定値による設定生成
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 相対残差の閾値
+    pub fn tol(mut self, tol: f64) -> Self {
+        self.tol = tol;
+        self
+    }
+
+    /// 最大反復回数
+    pub fn max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+
+    /// GMRESのリスタート長
+    pub fn restart(mut self, restart: usize) -> Self {
+        self.restart = restart;
+        self
+    }
+
+    /// 前処理の種別
+    pub fn preconditioner(mut self, preconditioner: Preconditioner) -> Self {
+        self.preconditioner = preconditioner;
+        self
+    }
+}
+
+/// 反復解法の結果 / solver result
+///
+/// 近似解、反復回数、最終的な相対残差および収束の可否を保持する。
+/// 最大反復回数に達した場合も、その時点の近似解を converged = false として返却する。
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolverResult {
+    pub x: Vec<f64>,
+    pub iterations: usize,
+    pub residual: f64,
+    pub converged: bool,
+}
+
+/// 構築済みの前処理
+enum Prepared {
+    Identity,
+    Jacobi(Vec<f64>),
+    Ilu0 {
+        row_ptr: Vec<usize>,
+        col_idx: Vec<usize>,
+        values: Vec<f64>,
+        diag: Vec<usize>,
+    },
+}
+
+impl Prepared {
+    fn new<A: LinearOperator + ?Sized>(a: &A, kind: Preconditioner) -> Result<Self, &'static str> {
+        match kind {
+            Preconditioner::None => Ok(Prepared::Identity),
+            Preconditioner::Jacobi => {
+                let d = a
+                    .diagonal()
+                    .ok_or("the operator does not provide its diagonal for the Jacobi preconditioner")?;
+                if d.contains(&0.0) {
+                    return Err("zero diagonal element detected for the Jacobi preconditioner");
+                }
+                Ok(Prepared::Jacobi(d))
+            }
+            Preconditioner::Ilu0 => {
+                let csr = a
+                    .to_csr()
+                    .ok_or("the operator does not provide a CSR matrix for the ILU(0) preconditioner")?;
+                Self::ilu0(csr)
+            }
+        }
+    }
+
+    /// ILU(0)分解
+    ///
+    /// 係数行列の非ゼロパターン上でLU分解を行う。Lの対角は1とし、
+    /// L と U を同じ配列に格納する。
+    ///
+    fn ilu0(csr: CsrMatrix<f64>) -> Result<Self, &'static str> {
+        let n = csr.rows();
+        let row_ptr = csr.row_ptr().clone();
+        let col_idx = csr.col_idx().clone();
+        let mut values = csr.values().clone();
+
+        let mut diag = Vec::with_capacity(n);
+        for i in 0..n {
+            match col_idx[row_ptr[i]..row_ptr[i + 1]].binary_search(&i) {
+                Ok(k) => diag.push(row_ptr[i] + k),
+                Err(_) => return Err("missing diagonal element for the ILU(0) preconditioner"),
+            }
+        }
+
+        for i in 1..n {
+            for kk in row_ptr[i]..diag[i] {
+                let k = col_idx[kk];
+                let pivot = values[diag[k]];
+                if pivot == 0.0 {
+                    return Err("zero pivot detected in the ILU(0) factorization");
+                }
+                values[kk] /= pivot;
+                let l = values[kk];
+                for jj in (kk + 1)..row_ptr[i + 1] {
+                    let j = col_idx[jj];
+                    if let Ok(p) = col_idx[(diag[k] + 1)..row_ptr[k + 1]].binary_search(&j) {
+                        values[jj] -= l * values[diag[k] + 1 + p];
+                    }
+                }
+            }
+        }
+        if diag.iter().any(|d| values[*d] == 0.0) {
+            return Err("zero pivot detected in the ILU(0) factorization");
+        }
+
+        Ok(Prepared::Ilu0 {
+            row_ptr,
+            col_idx,
+            values,
+            diag,
+        })
+    }
+
+    /// 前処理の適用 z = M^-1 r
+    fn apply(&self, r: &[f64]) -> Vec<f64> {
+        match self {
+            Prepared::Identity => r.to_vec(),
+            Prepared::Jacobi(d) => r.iter().zip(d.iter()).map(|(x, y)| x / y).collect(),
+            Prepared::Ilu0 {
+                row_ptr,
+                col_idx,
+                values,
+                diag,
+            } => {
+                let n = r.len();
+                let mut z = r.to_vec();
+                for i in 0..n {
+                    for k in row_ptr[i]..diag[i] {
+                        z[i] -= values[k] * z[col_idx[k]];
+                    }
+                }
+                for i in (0..n).rev() {
+                    for k in (diag[i] + 1)..row_ptr[i + 1] {
+                        z[i] -= values[k] * z[col_idx[k]];
+                    }
+                    z[i] /= values[diag[i]];
+                }
+                z
+            }
+        }
+    }
+}
+
+fn dot(x: &[f64], y: &[f64]) -> f64 {
+    x.iter().zip(y.iter()).map(|(a, b)| a * b).sum()
+}
+
+fn norm(x: &[f64]) -> f64 {
+    dot(x, x).sqrt()
+}
+
+/// y += alpha * x
+fn axpy(alpha: f64, x: &[f64], y: &mut [f64]) {
+    for (yi, xi) in y.iter_mut().zip(x.iter()) {
+        *yi += alpha * xi;
+    }
+}
+
+/// 残差 b - Ax
+fn residual<A: LinearOperator + ?Sized>(a: &A, x: &[f64], b: &[f64]) -> Vec<f64> {
+    b.iter().zip(a.apply(x).iter()).map(|(bi, ai)| bi - ai).collect()
+}
+
+/// 入力検証と右辺ノルムの取得
+fn prepare<A: LinearOperator + ?Sized>(
+    a: &A,
+    b: &[f64],
+    opts: &SolverOptions,
+) -> Result<(Prepared, f64), &'static str> {
+    if a.col_dim() != Some(a.dim()) {
+        return Err("operator must be square");
+    }
+    if a.dim() != b.len() {
+        return Err("right-hand side length not matched to the dimension of the operator");
+    }
+    if opts.tol.is_nan() || opts.tol <= 0.0 {
+        return Err("tolerance must be positive");
+    }
+    Ok((Prepared::new(a, opts.preconditioner)?, norm(b)))
+}
+
+/// 共役勾配法 / conjugate gradient
+///
+/// 対称正定値の係数行列について、前処理付き共役勾配法で Ax = b を解く。
+/// 初期値はゼロベクトルとする。
+///
+pub fn cg<A: LinearOperator + ?Sized>(
+    a: &A,
+    b: &[f64],
+    opts: &SolverOptions,
+) -> Result<SolverResult, &'static str> {
+    let (m, b_norm) = prepare(a, b, opts)?;
+    let mut x = vec![0.0; b.len()];
+    if b_norm == 0.0 {
+        return Ok(SolverResult { x, iterations: 0, residual: 0.0, converged: true });
+    }
+
+    let mut r = b.to_vec();
+    let mut z = m.apply(&r);
+    let mut p = z.clone();
+    let mut rz = dot(&r, &z);
+    let mut res = 1.0;
+
+    for it in 1..=opts.max_iter {
+        let ap = a.apply(&p);
+        let pap = dot(&p, &ap);
+        if pap == 0.0 {
+            return Err("breakdown in the conjugate gradient method");
+        }
+        let alpha = rz / pap;
+        axpy(alpha, &p, &mut x);
+        axpy(-alpha, &ap, &mut r);
+
+        res = norm(&r) / b_norm;
+        if res < opts.tol {
+            return Ok(SolverResult { x, iterations: it, residual: res, converged: true });
+        }
+
+        z = m.apply(&r);
+        let rz_new = dot(&r, &z);
+        let beta = rz_new / rz;
+        rz = rz_new;
+        for (pi, zi) in p.iter_mut().zip(z.iter()) {
+            *pi = zi + beta * *pi;
+        }
+    }
+    Ok(SolverResult { x, iterations: opts.max_iter, residual: res, converged: false })
+}
+
+/// 安定化双共役勾配法 / BiCGSTAB
+///
+/// 非対称な係数行列について、右前処理付きBiCGSTAB法で Ax = b を解く。
+/// 初期値はゼロベクトルとする。
+///
+pub fn bicgstab<A: LinearOperator + ?Sized>(
+    a: &A,
+    b: &[f64],
+    opts: &SolverOptions,
+) -> Result<SolverResult, &'static str> {
+    let (m, b_norm) = prepare(a, b, opts)?;
+    let n = b.len();
+    let mut x = vec![0.0; n];
+    if b_norm == 0.0 {
+        return Ok(SolverResult { x, iterations: 0, residual: 0.0, converged: true });
+    }
+
+    let mut r = b.to_vec();
+    let r_hat = r.clone();
+    let (mut rho, mut alpha, mut omega) = (1.0, 1.0, 1.0);
+    let mut v = vec![0.0; n];
+    let mut p = vec![0.0; n];
+    let mut res = 1.0;
+
+    for it in 1..=opts.max_iter {
+        let rho_new = dot(&r_hat, &r);
+        if rho_new == 0.0 {
+            return Err("breakdown in the BiCGSTAB method");
+        }
+        let beta = (rho_new / rho) * (alpha / omega);
+        for i in 0..n {
+            p[i] = r[i] + beta * (p[i] - omega * v[i]);
+        }
+
+        let p_hat = m.apply(&p);
+        v = a.apply(&p_hat);
+        let rv = dot(&r_hat, &v);
+        if rv == 0.0 {
+            return Err("breakdown in the BiCGSTAB method");
+        }
+        alpha = rho_new / rv;
+        let mut s = r.clone();
+        axpy(-alpha, &v, &mut s);
+
+        res = norm(&s) / b_norm;
+        if res < opts.tol {
+            axpy(alpha, &p_hat, &mut x);
+            return Ok(SolverResult { x, iterations: it, residual: res, converged: true });
+        }
+
+        let s_hat = m.apply(&s);
+        let t = a.apply(&s_hat);
+        let tt = dot(&t, &t);
+        if tt == 0.0 {
+            return Err("breakdown in the BiCGSTAB method");
+        }
+        omega = dot(&t, &s) / tt;
+        axpy(alpha, &p_hat, &mut x);
+        axpy(omega, &s_hat, &mut x);
+        r = s;
+        axpy(-omega, &t, &mut r);
+
+        res = norm(&r) / b_norm;
+        if res < opts.tol {
+            return Ok(SolverResult { x, iterations: it, residual: res, converged: true });
+        }
+        if omega == 0.0 {
+            return Err("breakdown in the BiCGSTAB method");
+        }
+        rho = rho_new;
+    }
+    Ok(SolverResult { x, iterations: opts.max_iter, residual: res, converged: false })
+}
+
+/// 一般化最小残差法 / restarted GMRES
+///
+/// 任意の正則な係数行列について、右前処理付きのリスタート付きGMRES法で Ax = b を解く。
+/// Krylov部分空間の次元が `restart` に達するごとにリスタートし、
+/// 反復回数には全サイクルの内部反復の合計を報告する。
+///
+pub fn gmres<A: LinearOperator + ?Sized>(
+    a: &A,
+    b: &[f64],
+    opts: &SolverOptions,
+) -> Result<SolverResult, &'static str> {
+    let (m, b_norm) = prepare(a, b, opts)?;
+    if opts.restart == 0 {
+        return Err("restart length must not be zero");
+    }
+    let n = b.len();
+    let mut x = vec![0.0; n];
+    if b_norm == 0.0 {
+        return Ok(SolverResult { x, iterations: 0, residual: 0.0, converged: true });
+    }
+
+    let restart = std::cmp::min(opts.restart, n);
+    let mut iterations = 0;
+
+    while iterations < opts.max_iter {
+        let r = residual(a, &x, b);
+        let beta = norm(&r);
+        let mut res = beta / b_norm;
+        if res < opts.tol {
+            return Ok(SolverResult { x, iterations, residual: res, converged: true });
+        }
+
+        let mut basis: Vec<Vec<f64>> = vec![r.iter().map(|ri| ri / beta).collect()];
+        let mut h = vec![vec![0.0; restart]; restart + 1];
+        let (mut cs, mut sn) = (vec![0.0; restart], vec![0.0; restart]);
+        let mut g = vec![0.0; restart + 1];
+        g[0] = beta;
+
+        let mut k = 0;
+        while k < restart && iterations < opts.max_iter {
+            let mut w = a.apply(&m.apply(&basis[k]));
+            for (i, v) in basis.iter().enumerate() {
+                h[i][k] = dot(&w, v);
+                axpy(-h[i][k], v, &mut w);
+            }
+            h[k + 1][k] = norm(&w);
+
+            for i in 0..k {
+                let tmp = cs[i] * h[i][k] + sn[i] * h[i + 1][k];
+                h[i + 1][k] = -sn[i] * h[i][k] + cs[i] * h[i + 1][k];
+                h[i][k] = tmp;
+            }
+            let denom = (h[k][k] * h[k][k] + h[k + 1][k] * h[k + 1][k]).sqrt();
+            if denom == 0.0 {
+                return Err("breakdown in the GMRES method");
+            }
+            cs[k] = h[k][k] / denom;
+            sn[k] = h[k + 1][k] / denom;
+            let w_norm = h[k + 1][k];
+            h[k][k] = denom;
+            h[k + 1][k] = 0.0;
+            g[k + 1] = -sn[k] * g[k];
+            g[k] *= cs[k];
+
+            iterations += 1;
+            k += 1;
+            res = g[k].abs() / b_norm;
+            if res < opts.tol || w_norm == 0.0 {
+                break;
+            }
+            basis.push(w.iter().map(|wi| wi / w_norm).collect());
+        }
+
+        //上三角系 Hy = g を解き、x += M^-1 (V y) として更新する
+        let mut y = vec![0.0; k];
+        for i in (0..k).rev() {
+            let mut acc = g[i];
+            for j in (i + 1)..k {
+                acc -= h[i][j] * y[j];
+            }
+            y[i] = acc / h[i][i];
+        }
+        let mut update = vec![0.0; n];
+        for (v, yi) in basis.iter().zip(y.iter()) {
+            axpy(*yi, v, &mut update);
+        }
+        axpy(1.0, &m.apply(&update), &mut x);
+
+        if res < opts.tol {
+            res = norm(&residual(a, &x, b)) / b_norm;
+            return Ok(SolverResult { x, iterations, residual: res, converged: true });
+        }
+    }
+    let res = norm(&residual(a, &x, b)) / b_norm;
+    Ok(SolverResult { x, iterations, residual: res, converged: false })
+}
+
+#[cfg(test)]
+mod tests_iterative_solver {
+    use crate::core::Matrix;
+    use crate::mat;
+    use crate::solver::{bicgstab, cg, gmres, Preconditioner, SolverOptions};
+    use crate::sparse::{CooMatrix, CscMatrix, CsrMatrix};
+
+    /// 1次元ポアソン方程式の離散化による三重対角行列
+    fn poisson(n: usize) -> CsrMatrix<f64> {
+        let mut coo = CooMatrix::<f64>::new(n, n);
+        for i in 0..n {
+            coo.push(i, i, 2.0).unwrap();
+            if i > 0 {
+                coo.push(i, i - 1, -1.0).unwrap();
+            }
+            if i + 1 < n {
+                coo.push(i, i + 1, -1.0).unwrap();
+            }
+        }
+        coo.to_csr()
+    }
+
+    fn assert_close(x: &[f64], y: &[f64], tol: f64) {
+        for (a, b) in x.iter().zip(y.iter()) {
+            assert!((a - b).abs() < tol, "{} != {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_cg_dense() {
+        let a = mat![f64: [4.0,1.0,0.0],[1.0,3.0,1.0],[0.0,1.0,2.0]];
+        let x = vec![1.0, -2.0, 3.0];
+        let b = a.data.iter().map(|row| row.iter().zip(x.iter()).map(|(p, q)| p * q).sum()).collect::<Vec<f64>>();
+        for kind in [Preconditioner::None, Preconditioner::Jacobi, Preconditioner::Ilu0].iter() {
+            let res = cg(&a, &b, &SolverOptions::new().tol(1e-12).preconditioner(*kind)).unwrap();
+            assert!(res.converged);
+            assert!(res.residual < 1e-12);
+            assert!(res.iterations <= 3);
+            assert_close(&res.x, &x, 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_cg_sparse() {
+        let n = 50;
+        let a = poisson(n);
+        let b = vec![1.0; n];
+        let plain = cg(&a, &b, &SolverOptions::new().tol(1e-10)).unwrap();
+        assert!(plain.converged);
+        let ilu = cg(&a, &b, &SolverOptions::new().tol(1e-10).preconditioner(Preconditioner::Ilu0)).unwrap();
+        assert!(ilu.converged);
+        // 三重対角行列ではILU(0)が完全なLU分解となり、1反復で収束する
+        assert_eq!(ilu.iterations, 1);
+        // 解析解 x_i = (i+1)(n-i)/2
+        let expected = (0..n).map(|i| ((i + 1) * (n - i)) as f64 / 2.0).collect::<Vec<f64>>();
+        assert_close(&plain.x, &expected, 1e-6);
+        assert_close(&ilu.x, &expected, 1e-6);
+    }
+
+    #[test]
+    fn test_bicgstab_nonsymmetric() {
+        let a = mat![f64: [4.0,1.0,0.0],[2.0,5.0,1.0],[0.0,-1.0,3.0]];
+        let x = vec![1.0, 2.0, -1.0];
+        let b = vec![6.0, 11.0, -5.0];
+        for kind in [Preconditioner::None, Preconditioner::Jacobi, Preconditioner::Ilu0].iter() {
+            let res = bicgstab(&a, &b, &SolverOptions::new().tol(1e-12).preconditioner(*kind)).unwrap();
+            assert!(res.converged);
+            assert_close(&res.x, &x, 1e-9);
+        }
+        let csc = CscMatrix::from_dense(&a);
+        let res = bicgstab(&csc, &b, &SolverOptions::new().tol(1e-12)).unwrap();
+        assert_close(&res.x, &x, 1e-9);
+    }
+
+    #[test]
+    fn test_gmres() {
+        let a = mat![f64: [4.0,1.0,0.0,1.0],[2.0,5.0,1.0,0.0],[0.0,-1.0,3.0,1.0],[1.0,0.0,2.0,6.0]];
+        let x = vec![1.0, 2.0, -1.0, 0.5];
+        let b = vec![6.5, 11.0, -4.5, 2.0];
+        for kind in [Preconditioner::None, Preconditioner::Jacobi, Preconditioner::Ilu0].iter() {
+            let res = gmres(&a, &b, &SolverOptions::new().tol(1e-12).preconditioner(*kind)).unwrap();
+            assert!(res.converged);
+            assert!(res.residual < 1e-12);
+            assert_close(&res.x, &x, 1e-9);
+        }
+        // リスタート長を短くしても収束する
+        let res = gmres(&poisson(20), &vec![1.0; 20], &SolverOptions::new().tol(1e-10).restart(5).max_iter(5000)).unwrap();
+        assert!(res.converged);
+        assert!(res.iterations > 5);
+    }
+
+    #[test]
+    fn test_max_iter() {
+        let a = poisson(30);
+        let res = cg(&a, &vec![1.0; 30], &SolverOptions::new().tol(1e-14).max_iter(3)).unwrap();
+        assert!(!res.converged);
+        assert_eq!(res.iterations, 3);
+        assert!(res.residual > 1e-14);
+    }
+
+    #[test]
+    fn test_errors() {
+        let a = mat![f64: [0.0,1.0],[1.0,0.0]];
+        assert!(cg(&a, &[1.0], &SolverOptions::new()).is_err());
+        assert!(cg(&a, &[1.0, 1.0], &SolverOptions::new().preconditioner(Preconditioner::Jacobi)).is_err());
+        assert!(gmres(&a, &[1.0, 1.0], &SolverOptions::new().preconditioner(Preconditioner::Ilu0)).is_err());
+        assert!(gmres(&a, &[1.0, 1.0], &SolverOptions::new().restart(0)).is_err());
+        let res = gmres(&a, &[1.0, 2.0], &SolverOptions::new()).unwrap();
+        assert_close(&res.x, &[2.0, 1.0], 1e-9);
+        let zero = cg(&a, &[0.0, 0.0], &SolverOptions::new()).unwrap();
+        assert_eq!(zero.iterations, 0);
+        assert_eq!(zero.x, vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_non_square() {
+        let tall = mat![f64: [1.0],[2.0]];
+        let jacobi = SolverOptions::new().preconditioner(Preconditioner::Jacobi);
+        assert_eq!(cg(&tall, &[1.0, 2.0], &jacobi), Err("operator must be square"));
+        assert_eq!(gmres(&tall, &[1.0, 2.0], &SolverOptions::new()), Err("operator must be square"));
+        let mut ragged = mat![f64: [1.0,0.0],[0.0,1.0]];
+        ragged.data[1].pop();
+        assert_eq!(bicgstab(&ragged, &[1.0, 1.0], &SolverOptions::new()), Err("operator must be square"));
+
+        let wide = CsrMatrix::from_dense(&mat![f64: [1.0,0.0,2.0],[0.0,1.0,0.0]]);
+        assert_eq!(bicgstab(&wide, &[1.0, 1.0], &SolverOptions::new()), Err("operator must be square"));
+        let wide = CscMatrix::from_dense(&mat![f64: [1.0,0.0,2.0],[0.0,1.0,0.0]]);
+        assert_eq!(cg(&wide, &[1.0, 1.0], &jacobi), Err("operator must be square"));
+        let wide = CooMatrix::from_dense(&mat![f64: [1.0,0.0,2.0],[0.0,1.0,0.0]]);
+        assert_eq!(gmres(&wide, &[1.0, 1.0], &SolverOptions::new()), Err("operator must be square"));
+    }
+
+    #[test]
+    fn test_coo_operator() {
+        let coo = CooMatrix::from_dense(&mat![f64: [2.0,0.0],[0.0,4.0]]);
+        let res = cg(&coo, &[2.0, 2.0], &SolverOptions::new().preconditioner(Preconditioner::Jacobi)).unwrap();
+        assert_eq!(res.iterations, 1);
+        assert_close(&res.x, &[1.0, 0.5], 1e-12);
+    }
+}
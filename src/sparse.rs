@@ -0,0 +1,949 @@
+//! 疎行列 / sparse matrices
+//!
+//! ほとんどの元がゼロである行列を、非ゼロ要素のみを保持する形式で扱う。
+//! 座標形式(COO)、圧縮行形式(CSR)、圧縮列形式(CSC)の3種類を提供し、
+//! いずれも `SparseMatrix<T>` トレイトを実装する。
+//! ゼロ値には数値行列と同様に `T::from(0u8)` を用いる。
+//!
+//! ```rust
+//! use matrixa::core::Matrix;
+//! use matrixa::mat;
+//! use matrixa::sparse::{CsrMatrix, SparseMatrix};
+//!
+//! let m = mat![i32: [1,0,0],[0,0,2],[0,3,0]];
+//! let s = CsrMatrix::from_dense(&m);
+//! assert_eq!(s.nnz(), 3);
+//! assert_eq!(s.to_dense() == m, true);
+//!
+//! let v = mat![i32: [1,1],[1,0],[0,1]];
+//! assert_eq!(s.prod_dense(&v).unwrap() == mat![i32: [1,1],[0,2],[3,0]], true);
+//! ```
+//!
+
+use crate::core::Matrix;
+use std::ops::Add;
+
+/// 疎行列共通インターフェース / common interface of sparse matrices
+///
+pub trait SparseMatrix<T> {
+    /// 行数
+    fn rows(&self) -> usize;
+
+    /// 列数
+    fn cols(&self) -> usize;
+
+    /// 保持している非ゼロ要素数
+    ///
+    /// 演算や形式の変換の結果としてゼロとなった元は保持しない。
+    /// COO形式では `push` で追加した三つ組をそのまま数える。
+    fn nnz(&self) -> usize;
+
+    /// 元の取得
+    ///
+    /// 行p, 列q の元を返却する。保持されていない元についてはゼロ値を返却し、
+    /// 範囲外の位置を指定した場合はパニックする。
+    fn get(&self, p: usize, q: usize) -> T;
+
+    /// 密行列への変換
+    fn to_dense(&self) -> Matrix<T>;
+
+    /// ベクトルとの積
+    ///
+    /// 列数と同じ長さのベクトルxについて Ax を計算し、Result型に格納して返却する。
+    fn mul_vec(&self, x: &[T]) -> Result<Vec<T>, &str>;
+
+    /// 密行列との積
+    ///
+    /// 疎行列と密行列の積を計算し、Result型に格納した新規の密行列を返却する。
+    fn prod_dense(&self, m: &Matrix<T>) -> Result<Matrix<T>, &str>;
+}
+
+/// 座標形式疎行列 / coordinate (COO) sparse matrix
+///
+/// 非ゼロ要素を (行, 列, 値) の三つ組として保持する。
+/// 同一位置への重複した要素は、変換時および取得時に加算される。
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct CooMatrix<T> {
+    rows: usize,
+    cols: usize,
+    row_idx: Vec<usize>,
+    col_idx: Vec<usize>,
+    values: Vec<T>,
+}
+
+/// 圧縮行形式疎行列 / compressed sparse row (CSR) matrix
+///
+/// 行ごとに列番号の昇順で非ゼロ要素を保持する。
+/// 行iの要素は `col_idx[row_ptr[i]..row_ptr[i+1]]` および同範囲の `values` に格納される。
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsrMatrix<T> {
+    rows: usize,
+    cols: usize,
+    row_ptr: Vec<usize>,
+    col_idx: Vec<usize>,
+    values: Vec<T>,
+}
+
+/// 圧縮列形式疎行列 / compressed sparse column (CSC) matrix
+///
+/// 列ごとに行番号の昇順で非ゼロ要素を保持する。
+/// 列jの要素は `row_idx[col_ptr[j]..col_ptr[j+1]]` および同範囲の `values` に格納される。
+/// 格納形式は転置行列のCSR形式と一致する。
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct CscMatrix<T> {
+    rows: usize,
+    cols: usize,
+    col_ptr: Vec<usize>,
+    row_idx: Vec<usize>,
+    values: Vec<T>,
+}
+
+impl<T> CooMatrix<T>
+where
+    T: Copy + Add<Output = T> + std::ops::Mul<Output = T> + PartialEq + From<u8>,
+{
+    /// 空の疎行列生成
+    ///
+    /// rows x cols の全要素ゼロの疎行列を生成する。
+    ///
+    pub fn new(rows: usize, cols: usize) -> Self {
+        CooMatrix {
+            rows,
+            cols,
+            row_idx: Vec::new(),
+            col_idx: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    /// 要素追加
+    ///
+    /// 行p, 列q に値を追加し、Result型に格納した自身への参照を返却する。
+    /// 範囲外の位置を指定した場合はErrを返却する。
+    ///
+    /// ```rust
+    /// use matrixa::sparse::{CooMatrix, SparseMatrix};
+    ///
+    /// let mut s = CooMatrix::<i32>::new(2, 3);
+    /// s.push(0, 1, 5).unwrap().push(1, 2, 7).unwrap();
+    /// assert_eq!(s.nnz(), 2);
+    /// assert_eq!(s.get(1, 2), 7);
+    /// assert!(s.push(2, 0, 1).is_err());
+    /// ```
+    ///
+    pub fn push(&mut self, p: usize, q: usize, val: T) -> Result<&mut Self, &str> {
+        if p >= self.rows || q >= self.cols {
+            return Err("position is out of order for the sparse matrix");
+        }
+        self.row_idx.push(p);
+        self.col_idx.push(q);
+        self.values.push(val);
+        Ok(self)
+    }
+
+    /// 密行列からの変換
+    ///
+    /// ゼロでない元のみを行優先の順に保持する疎行列を生成する。
+    ///
+    pub fn from_dense(m: &Matrix<T>) -> Self {
+        let zero = T::from(0x0u8);
+        let mut res = CooMatrix::new(m.data.len(), m.data.first().map_or(0, |row| row.len()));
+        for (i, row) in m.data.iter().enumerate() {
+            for (j, datum) in row.iter().enumerate() {
+                if *datum != zero {
+                    res.row_idx.push(i);
+                    res.col_idx.push(j);
+                    res.values.push(*datum);
+                }
+            }
+        }
+        res
+    }
+
+    /// 三つ組の取得
+    ///
+    /// 保持している要素を (行, 列, 値) の組として返却する。
+    ///
+    pub fn triplets(&self) -> Vec<(usize, usize, T)> {
+        self.row_idx
+            .iter()
+            .zip(self.col_idx.iter())
+            .zip(self.values.iter())
+            .map(|((p, q), v)| (*p, *q, *v))
+            .collect()
+    }
+
+    /// 転置
+    ///
+    pub fn transpose(&self) -> Self {
+        CooMatrix {
+            rows: self.cols,
+            cols: self.rows,
+            row_idx: self.col_idx.clone(),
+            col_idx: self.row_idx.clone(),
+            values: self.values.clone(),
+        }
+    }
+
+    /// CSR形式への変換
+    ///
+    /// 各行の要素を列番号の昇順に整列し、重複する位置の要素を加算する。
+    /// 加算した結果がゼロとなる要素は保持しない。
+    ///
+    pub fn to_csr(&self) -> CsrMatrix<T> {
+        let zero = T::from(0x0u8);
+        let mut order: Vec<usize> = (0..self.values.len()).collect();
+        order.sort_by_key(|&k| (self.row_idx[k], self.col_idx[k]));
+
+        let mut res = CsrMatrix::empty(self.rows, self.cols);
+        let mut k = 0;
+        while k < order.len() {
+            let pos = (self.row_idx[order[k]], self.col_idx[order[k]]);
+            let mut v = zero;
+            while k < order.len() && (self.row_idx[order[k]], self.col_idx[order[k]]) == pos {
+                v = v + self.values[order[k]];
+                k += 1;
+            }
+            if v != zero {
+                res.row_ptr[pos.0 + 1] += 1;
+                res.col_idx.push(pos.1);
+                res.values.push(v);
+            }
+        }
+        for i in 0..self.rows {
+            res.row_ptr[i + 1] += res.row_ptr[i];
+        }
+        res
+    }
+
+    /// CSC形式への変換
+    ///
+    pub fn to_csc(&self) -> CscMatrix<T> {
+        CscMatrix::from_transposed_csr(self.transpose().to_csr())
+    }
+}
+
+impl<T> SparseMatrix<T> for CooMatrix<T>
+where
+    T: Copy + Add<Output = T> + std::ops::Mul<Output = T> + PartialEq + From<u8>,
+{
+    fn rows(&self) -> usize {
+        self.rows
+    }
+
+    fn cols(&self) -> usize {
+        self.cols
+    }
+
+    fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    fn get(&self, p: usize, q: usize) -> T {
+        if p >= self.rows || q >= self.cols {
+            panic!("position ({}, {}) is out of order for the sparse matrix", p, q);
+        }
+        let mut res = T::from(0x0u8);
+        for k in 0..self.values.len() {
+            if self.row_idx[k] == p && self.col_idx[k] == q {
+                res = res + self.values[k];
+            }
+        }
+        res
+    }
+
+    fn to_dense(&self) -> Matrix<T> {
+        let zero = T::from(0x0u8);
+        let mut res = Matrix::new();
+        res.data = vec![vec![zero; self.cols]; self.rows];
+        for k in 0..self.values.len() {
+            let datum = &mut res.data[self.row_idx[k]][self.col_idx[k]];
+            *datum = *datum + self.values[k];
+        }
+        res
+    }
+
+    fn mul_vec(&self, x: &[T]) -> Result<Vec<T>, &str> {
+        if x.len() != self.cols {
+            return Err("vector length not matched to the col length of the sparse matrix");
+        }
+        let mut res = vec![T::from(0x0u8); self.rows];
+        for k in 0..self.values.len() {
+            let p = self.row_idx[k];
+            res[p] = res[p] + self.values[k] * x[self.col_idx[k]];
+        }
+        Ok(res)
+    }
+
+    fn prod_dense(&self, m: &Matrix<T>) -> Result<Matrix<T>, &str> {
+        if m.data.len() != self.cols {
+            return Err("row length of argument not matched to the col length of the sparse matrix");
+        }
+        let mut res = Matrix::new();
+        res.data = vec![vec![T::from(0x0u8); m.data.first().map_or(0, |row| row.len())]; self.rows];
+        for k in 0..self.values.len() {
+            let v = self.values[k];
+            for (datum, b) in res.data[self.row_idx[k]].iter_mut().zip(m.data[self.col_idx[k]].iter()) {
+                *datum = *datum + v * *b;
+            }
+        }
+        Ok(res)
+    }
+}
+
+impl<T> CsrMatrix<T>
+where
+    T: Copy + Add<Output = T> + std::ops::Mul<Output = T> + PartialEq + From<u8>,
+{
+    /// 全要素ゼロのCSR行列
+    fn empty(rows: usize, cols: usize) -> Self {
+        CsrMatrix {
+            rows,
+            cols,
+            row_ptr: vec![0; rows + 1],
+            col_idx: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    /// 密行列からの変換
+    ///
+    /// ゼロでない元のみを保持する疎行列を生成する。
+    ///
+    pub fn from_dense(m: &Matrix<T>) -> Self {
+        let zero = T::from(0x0u8);
+        let mut res = CsrMatrix::empty(m.data.len(), m.data.first().map_or(0, |row| row.len()));
+        for (i, row) in m.data.iter().enumerate() {
+            for (j, datum) in row.iter().enumerate() {
+                if *datum != zero {
+                    res.col_idx.push(j);
+                    res.values.push(*datum);
+                }
+            }
+            res.row_ptr[i + 1] = res.values.len();
+        }
+        res
+    }
+
+    /// 行ポインタ配列の参照
+    pub fn row_ptr(&self) -> &Vec<usize> {
+        &self.row_ptr
+    }
+
+    /// 列番号配列の参照
+    pub fn col_idx(&self) -> &Vec<usize> {
+        &self.col_idx
+    }
+
+    /// 値配列の参照
+    pub fn values(&self) -> &Vec<T> {
+        &self.values
+    }
+
+    /// COO形式への変換
+    ///
+    pub fn to_coo(&self) -> CooMatrix<T> {
+        let mut res = CooMatrix::new(self.rows, self.cols);
+        for i in 0..self.rows {
+            for k in self.row_ptr[i]..self.row_ptr[i + 1] {
+                res.row_idx.push(i);
+                res.col_idx.push(self.col_idx[k]);
+                res.values.push(self.values[k]);
+            }
+        }
+        res
+    }
+
+    /// CSC形式への変換
+    ///
+    /// 列ごとの要素数を数え上げ、行番号の昇順を保ったまま各列に配置する。
+    ///
+    pub fn to_csc(&self) -> CscMatrix<T> {
+        let mut col_ptr = vec![0; self.cols + 1];
+        for q in self.col_idx.iter() {
+            col_ptr[q + 1] += 1;
+        }
+        for j in 0..self.cols {
+            col_ptr[j + 1] += col_ptr[j];
+        }
+
+        let mut next = col_ptr.clone();
+        let mut row_idx = vec![0; self.values.len()];
+        let mut values = vec![T::from(0x0u8); self.values.len()];
+        for i in 0..self.rows {
+            for k in self.row_ptr[i]..self.row_ptr[i + 1] {
+                let q = self.col_idx[k];
+                row_idx[next[q]] = i;
+                values[next[q]] = self.values[k];
+                next[q] += 1;
+            }
+        }
+        CscMatrix {
+            rows: self.rows,
+            cols: self.cols,
+            col_ptr,
+            row_idx,
+            values,
+        }
+    }
+
+    /// 転置
+    ///
+    /// 転置行列をCSR形式の新規インスタンスとして返却する。
+    ///
+    pub fn transpose(&self) -> Self {
+        let csc = self.to_csc();
+        CsrMatrix {
+            rows: csc.cols,
+            cols: csc.rows,
+            row_ptr: csc.col_ptr,
+            col_idx: csc.row_idx,
+            values: csc.values,
+        }
+    }
+
+    /// 疎行列同士の積
+    ///
+    /// 2つのCSR行列の積を計算し、Result型に格納したCSR行列を返却する。
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    /// use matrixa::mat;
+    /// use matrixa::sparse::{CsrMatrix, SparseMatrix};
+    ///
+    /// let a = CsrMatrix::from_dense(&mat![i32: [1,0,2],[0,3,0]]);
+    /// let b = CsrMatrix::from_dense(&mat![i32: [0,1],[4,0],[5,0]]);
+    /// let c = a.prod(&b).unwrap();
+    /// assert_eq!(c.to_dense() == mat![i32: [10,1],[12,0]], true);
+    /// ```
+    ///
+    pub fn prod(&self, other: &CsrMatrix<T>) -> Result<CsrMatrix<T>, &str> {
+        if self.cols != other.rows {
+            return Err("col length not matched to the row length of argument");
+        }
+        let zero = T::from(0x0u8);
+        let mut res = CsrMatrix::empty(self.rows, other.cols);
+        let mut acc = vec![zero; other.cols];
+        let mut marker = vec![usize::MAX; other.cols];
+        let mut row_cols: Vec<usize> = Vec::new();
+
+        for i in 0..self.rows {
+            row_cols.clear();
+            for k in self.row_ptr[i]..self.row_ptr[i + 1] {
+                let a = self.values[k];
+                let r = self.col_idx[k];
+                for l in other.row_ptr[r]..other.row_ptr[r + 1] {
+                    let j = other.col_idx[l];
+                    if marker[j] != i {
+                        marker[j] = i;
+                        acc[j] = a * other.values[l];
+                        row_cols.push(j);
+                    } else {
+                        acc[j] = acc[j] + a * other.values[l];
+                    }
+                }
+            }
+            row_cols.sort_unstable();
+            for j in row_cols.iter().filter(|j| acc[**j] != zero) {
+                res.col_idx.push(*j);
+                res.values.push(acc[*j]);
+            }
+            res.row_ptr[i + 1] = res.values.len();
+        }
+        Ok(res)
+    }
+}
+
+impl<T> SparseMatrix<T> for CsrMatrix<T>
+where
+    T: Copy + Add<Output = T> + std::ops::Mul<Output = T> + PartialEq + From<u8>,
+{
+    fn rows(&self) -> usize {
+        self.rows
+    }
+
+    fn cols(&self) -> usize {
+        self.cols
+    }
+
+    fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    fn get(&self, p: usize, q: usize) -> T {
+        if p >= self.rows || q >= self.cols {
+            panic!("position ({}, {}) is out of order for the sparse matrix", p, q);
+        }
+        let range = self.row_ptr[p]..self.row_ptr[p + 1];
+        match self.col_idx[range.clone()].binary_search(&q) {
+            Ok(k) => self.values[range.start + k],
+            Err(_) => T::from(0x0u8),
+        }
+    }
+
+    fn to_dense(&self) -> Matrix<T> {
+        let mut res = Matrix::new();
+        res.data = vec![vec![T::from(0x0u8); self.cols]; self.rows];
+        for (i, row) in res.data.iter_mut().enumerate() {
+            for k in self.row_ptr[i]..self.row_ptr[i + 1] {
+                row[self.col_idx[k]] = self.values[k];
+            }
+        }
+        res
+    }
+
+    fn mul_vec(&self, x: &[T]) -> Result<Vec<T>, &str> {
+        if x.len() != self.cols {
+            return Err("vector length not matched to the col length of the sparse matrix");
+        }
+        let mut res = Vec::with_capacity(self.rows);
+        for i in 0..self.rows {
+            let mut acc = T::from(0x0u8);
+            for k in self.row_ptr[i]..self.row_ptr[i + 1] {
+                acc = acc + self.values[k] * x[self.col_idx[k]];
+            }
+            res.push(acc);
+        }
+        Ok(res)
+    }
+
+    fn prod_dense(&self, m: &Matrix<T>) -> Result<Matrix<T>, &str> {
+        if m.data.len() != self.cols {
+            return Err("row length of argument not matched to the col length of the sparse matrix");
+        }
+        let mut res = Matrix::new();
+        res.data = vec![vec![T::from(0x0u8); m.data.first().map_or(0, |row| row.len())]; self.rows];
+        for (i, res_row) in res.data.iter_mut().enumerate() {
+            for k in self.row_ptr[i]..self.row_ptr[i + 1] {
+                let v = self.values[k];
+                for (datum, b) in res_row.iter_mut().zip(m.data[self.col_idx[k]].iter()) {
+                    *datum = *datum + v * *b;
+                }
+            }
+        }
+        Ok(res)
+    }
+}
+
+impl<T> CscMatrix<T>
+where
+    T: Copy + Add<Output = T> + std::ops::Mul<Output = T> + PartialEq + From<u8>,
+{
+    /// 転置行列のCSR形式からの生成
+    ///
+    /// CSC形式の格納形式は転置行列のCSR形式と一致するため、配列をそのまま引き継ぐ。
+    ///
+    fn from_transposed_csr(csr: CsrMatrix<T>) -> Self {
+        CscMatrix {
+            rows: csr.cols,
+            cols: csr.rows,
+            col_ptr: csr.row_ptr,
+            row_idx: csr.col_idx,
+            values: csr.values,
+        }
+    }
+
+    /// 転置行列のCSR形式への変換
+    fn to_transposed_csr(&self) -> CsrMatrix<T> {
+        CsrMatrix {
+            rows: self.cols,
+            cols: self.rows,
+            row_ptr: self.col_ptr.clone(),
+            col_idx: self.row_idx.clone(),
+            values: self.values.clone(),
+        }
+    }
+
+    /// 密行列からの変換
+    ///
+    /// ゼロでない元のみを保持する疎行列を生成する。
+    ///
+    pub fn from_dense(m: &Matrix<T>) -> Self {
+        CsrMatrix::from_dense(m).to_csc()
+    }
+
+    /// 列ポインタ配列の参照
+    pub fn col_ptr(&self) -> &Vec<usize> {
+        &self.col_ptr
+    }
+
+    /// 行番号配列の参照
+    pub fn row_idx(&self) -> &Vec<usize> {
+        &self.row_idx
+    }
+
+    /// 値配列の参照
+    pub fn values(&self) -> &Vec<T> {
+        &self.values
+    }
+
+    /// COO形式への変換
+    ///
+    pub fn to_coo(&self) -> CooMatrix<T> {
+        self.to_transposed_csr().to_coo().transpose()
+    }
+
+    /// CSR形式への変換
+    ///
+    pub fn to_csr(&self) -> CsrMatrix<T> {
+        self.to_transposed_csr().transpose()
+    }
+
+    /// 転置
+    ///
+    /// 転置行列をCSC形式の新規インスタンスとして返却する。
+    ///
+    pub fn transpose(&self) -> Self {
+        CscMatrix::from_transposed_csr(self.to_csr())
+    }
+
+    /// 疎行列同士の積
+    ///
+    /// 2つのCSC行列の積を (AB)^T = B^T A^T として転置行列のCSR形式上で計算し、
+    /// Result型に格納したCSC行列を返却する。
+    ///
+    pub fn prod(&self, other: &CscMatrix<T>) -> Result<CscMatrix<T>, &str> {
+        if self.cols != other.rows {
+            return Err("col length not matched to the row length of argument");
+        }
+        let res = other
+            .to_transposed_csr()
+            .prod(&self.to_transposed_csr())
+            .expect("[sparse product error] failed to multiply transposed matrices");
+        Ok(CscMatrix::from_transposed_csr(res))
+    }
+}
+
+impl<T> SparseMatrix<T> for CscMatrix<T>
+where
+    T: Copy + Add<Output = T> + std::ops::Mul<Output = T> + PartialEq + From<u8>,
+{
+    fn rows(&self) -> usize {
+        self.rows
+    }
+
+    fn cols(&self) -> usize {
+        self.cols
+    }
+
+    fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    fn get(&self, p: usize, q: usize) -> T {
+        if p >= self.rows || q >= self.cols {
+            panic!("position ({}, {}) is out of order for the sparse matrix", p, q);
+        }
+        let range = self.col_ptr[q]..self.col_ptr[q + 1];
+        match self.row_idx[range.clone()].binary_search(&p) {
+            Ok(k) => self.values[range.start + k],
+            Err(_) => T::from(0x0u8),
+        }
+    }
+
+    fn to_dense(&self) -> Matrix<T> {
+        let mut res = Matrix::new();
+        res.data = vec![vec![T::from(0x0u8); self.cols]; self.rows];
+        for j in 0..self.cols {
+            for k in self.col_ptr[j]..self.col_ptr[j + 1] {
+                res.data[self.row_idx[k]][j] = self.values[k];
+            }
+        }
+        res
+    }
+
+    fn mul_vec(&self, x: &[T]) -> Result<Vec<T>, &str> {
+        if x.len() != self.cols {
+            return Err("vector length not matched to the col length of the sparse matrix");
+        }
+        let mut res = vec![T::from(0x0u8); self.rows];
+        for (j, xj) in x.iter().enumerate() {
+            for k in self.col_ptr[j]..self.col_ptr[j + 1] {
+                let p = self.row_idx[k];
+                res[p] = res[p] + self.values[k] * *xj;
+            }
+        }
+        Ok(res)
+    }
+
+    fn prod_dense(&self, m: &Matrix<T>) -> Result<Matrix<T>, &str> {
+        if m.data.len() != self.cols {
+            return Err("row length of argument not matched to the col length of the sparse matrix");
+        }
+        let mut res = Matrix::new();
+        res.data = vec![vec![T::from(0x0u8); m.data.first().map_or(0, |row| row.len())]; self.rows];
+        for (j, b_row) in m.data.iter().enumerate() {
+            for k in self.col_ptr[j]..self.col_ptr[j + 1] {
+                let v = self.values[k];
+                for (datum, b) in res.data[self.row_idx[k]].iter_mut().zip(b_row.iter()) {
+                    *datum = *datum + v * *b;
+                }
+            }
+        }
+        Ok(res)
+    }
+}
+
+/// 疎行列の加算 / sparse addition
+///
+/// 同一位置の元を加算した新規インスタンスを返却する。
+/// 和がゼロとなる要素は保持しない。
+/// 行および列の数が一致しない行列が指定された場合はパニックする。
+///
+impl<T> Add for &CooMatrix<T>
+where
+    T: Copy + Add<Output = T> + std::ops::Mul<Output = T> + PartialEq + From<u8>,
+{
+    type Output = CooMatrix<T>;
+    fn add(self, other: &CooMatrix<T>) -> CooMatrix<T> {
+        if self.rows != other.rows || self.cols != other.cols {
+            panic!("sparse matrix size not matched for the addition");
+        }
+        let mut res = self.clone();
+        res.row_idx.extend_from_slice(&other.row_idx);
+        res.col_idx.extend_from_slice(&other.col_idx);
+        res.values.extend_from_slice(&other.values);
+        res.to_csr().to_coo()
+    }
+}
+
+/// 疎行列の加算 / sparse addition
+///
+/// 各行の列番号を昇順に併合した新規インスタンスを返却する。
+/// 同一位置の和がゼロとなる要素は保持しない。
+/// 行および列の数が一致しない行列が指定された場合はパニックする。
+///
+/// ```rust
+/// use matrixa::core::Matrix;
+/// use matrixa::mat;
+/// use matrixa::sparse::{CsrMatrix, SparseMatrix};
+///
+/// let a = CsrMatrix::from_dense(&mat![i32: [1,0,2],[0,3,0]]);
+/// let b = CsrMatrix::from_dense(&mat![i32: [0,1,1],[0,0,4]]);
+/// assert_eq!((&a + &b).to_dense() == mat![i32: [1,1,3],[0,3,4]], true);
+/// ```
+///
+impl<T> Add for &CsrMatrix<T>
+where
+    T: Copy + Add<Output = T> + std::ops::Mul<Output = T> + PartialEq + From<u8>,
+{
+    type Output = CsrMatrix<T>;
+    fn add(self, other: &CsrMatrix<T>) -> CsrMatrix<T> {
+        if self.rows != other.rows || self.cols != other.cols {
+            panic!("sparse matrix size not matched for the addition");
+        }
+        let zero = T::from(0x0u8);
+        let mut res = CsrMatrix::empty(self.rows, self.cols);
+        for i in 0..self.rows {
+            let (mut k, k_end) = (self.row_ptr[i], self.row_ptr[i + 1]);
+            let (mut l, l_end) = (other.row_ptr[i], other.row_ptr[i + 1]);
+            while k < k_end || l < l_end {
+                if l == l_end || (k < k_end && self.col_idx[k] < other.col_idx[l]) {
+                    res.col_idx.push(self.col_idx[k]);
+                    res.values.push(self.values[k]);
+                    k += 1;
+                } else if k == k_end || other.col_idx[l] < self.col_idx[k] {
+                    res.col_idx.push(other.col_idx[l]);
+                    res.values.push(other.values[l]);
+                    l += 1;
+                } else {
+                    let sum = self.values[k] + other.values[l];
+                    if sum != zero {
+                        res.col_idx.push(self.col_idx[k]);
+                        res.values.push(sum);
+                    }
+                    k += 1;
+                    l += 1;
+                }
+            }
+            res.row_ptr[i + 1] = res.values.len();
+        }
+        res
+    }
+}
+
+/// 疎行列の加算 / sparse addition
+///
+/// 転置行列のCSR形式上で併合した新規インスタンスを返却する。
+/// 行および列の数が一致しない行列が指定された場合はパニックする。
+///
+impl<T> Add for &CscMatrix<T>
+where
+    T: Copy + Add<Output = T> + std::ops::Mul<Output = T> + PartialEq + From<u8>,
+{
+    type Output = CscMatrix<T>;
+    fn add(self, other: &CscMatrix<T>) -> CscMatrix<T> {
+        if self.rows != other.rows || self.cols != other.cols {
+            panic!("sparse matrix size not matched for the addition");
+        }
+        CscMatrix::from_transposed_csr(&self.to_transposed_csr() + &other.to_transposed_csr())
+    }
+}
+
+#[cfg(test)]
+mod tests_sparse_matrix {
+    use crate::core::Matrix;
+    use crate::mat;
+    use crate::sparse::{CooMatrix, CscMatrix, CsrMatrix, SparseMatrix};
+
+    fn sample() -> Matrix<i32> {
+        mat![
+            i32:
+                [0,0,3,0],
+                [1,0,0,0],
+                [0,0,0,0],
+                [0,2,0,4]
+        ]
+    }
+
+    #[test]
+    fn test_dense_round_trip() {
+        let m = sample();
+        let coo = CooMatrix::from_dense(&m);
+        let csr = CsrMatrix::from_dense(&m);
+        let csc = CscMatrix::from_dense(&m);
+        assert_eq!(coo.nnz(), 4);
+        assert_eq!(csr.nnz(), 4);
+        assert_eq!(csc.nnz(), 4);
+        assert_eq!(coo.to_dense() == m, true);
+        assert_eq!(csr.to_dense() == m, true);
+        assert_eq!(csc.to_dense() == m, true);
+        assert_eq!(csr.row_ptr(), &vec![0, 1, 2, 2, 4]);
+        assert_eq!(csc.col_ptr(), &vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_format_conversion() {
+        let m = sample();
+        let csr = CsrMatrix::from_dense(&m);
+        assert_eq!(csr.to_csc() == CscMatrix::from_dense(&m), true);
+        assert_eq!(csr.to_csc().to_csr() == csr, true);
+        assert_eq!(csr.to_coo().to_csr() == csr, true);
+        assert_eq!(csr.to_coo() == CooMatrix::from_dense(&m), true);
+        assert_eq!(CscMatrix::from_dense(&m).to_coo().to_dense() == m, true);
+    }
+
+    #[test]
+    fn test_coo_duplicates() {
+        let mut coo = CooMatrix::<i32>::new(2, 2);
+        coo.push(1, 0, 2).unwrap()
+            .push(0, 1, 1).unwrap()
+            .push(1, 0, 3).unwrap();
+        assert_eq!(coo.nnz(), 3);
+        assert_eq!(coo.get(1, 0), 5);
+        let csr = coo.to_csr();
+        assert_eq!(csr.nnz(), 2);
+        assert_eq!(csr.to_dense() == mat![i32: [0,1],[5,0]], true);
+        assert_eq!(coo.to_csc().to_dense() == mat![i32: [0,1],[5,0]], true);
+
+        // 加算してゼロとなる位置は保持しない
+        coo.push(0, 1, -1).unwrap();
+        assert_eq!(coo.to_csr().nnz(), 1);
+        assert_eq!(coo.to_csc().nnz(), 1);
+        assert_eq!(coo.to_csr().to_dense() == mat![i32: [0,0],[5,0]], true);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_coo_push_out_of_order() {
+        CooMatrix::<i32>::new(2, 2).push(0, 2, 1).unwrap();
+    }
+
+    #[test]
+    fn test_get() {
+        let m = sample();
+        let csr = CsrMatrix::from_dense(&m);
+        let csc = CscMatrix::from_dense(&m);
+        for i in 0..4 {
+            for j in 0..4 {
+                assert_eq!(csr.get(i, j), m.data[i][j]);
+                assert_eq!(csc.get(i, j), m.data[i][j]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_transpose() {
+        let m = mat![i32: [1,0,2],[0,0,3]];
+        let mut t = m.clone();
+        t.transpose();
+        assert_eq!(CsrMatrix::from_dense(&m).transpose().to_dense() == t, true);
+        assert_eq!(CscMatrix::from_dense(&m).transpose().to_dense() == t, true);
+        assert_eq!(CooMatrix::from_dense(&m).transpose().to_dense() == t, true);
+    }
+
+    #[test]
+    fn test_prod_dense() {
+        let m = sample();
+        let d = mat![i32: [1,2],[3,4],[5,6],[7,8]];
+        let res = &m * &d;
+        assert_eq!(CsrMatrix::from_dense(&m).prod_dense(&d).unwrap() == res, true);
+        assert_eq!(CscMatrix::from_dense(&m).prod_dense(&d).unwrap() == res, true);
+        assert_eq!(CooMatrix::from_dense(&m).prod_dense(&d).unwrap() == res, true);
+        assert_eq!(CsrMatrix::from_dense(&m).prod_dense(&mat![i32: [1,2]]).is_err(), true);
+        assert_eq!(CsrMatrix::from_dense(&m).mul_vec(&[1, 3, 5, 7]).unwrap(), vec![15, 1, 0, 34]);
+        assert_eq!(CscMatrix::from_dense(&m).mul_vec(&[1, 3, 5, 7]).unwrap(), vec![15, 1, 0, 34]);
+    }
+
+    #[test]
+    fn test_prod_sparse() {
+        let a = mat![i32: [1,0,2],[0,3,0]];
+        let b = mat![i32: [0,1,0,0],[4,0,0,1],[5,0,0,0]];
+        let res = &a * &b;
+        let csr = CsrMatrix::from_dense(&a).prod(&CsrMatrix::from_dense(&b)).unwrap();
+        let csc = CscMatrix::from_dense(&a).prod(&CscMatrix::from_dense(&b)).unwrap();
+        assert_eq!(csr.to_dense() == res, true);
+        assert_eq!(csc.to_dense() == res, true);
+        assert_eq!(CsrMatrix::from_dense(&a).prod(&CsrMatrix::from_dense(&a)).is_err(), true);
+
+        // 打ち消し合う積は保持しない
+        let a = mat![i32: [1,1],[0,1]];
+        let b = mat![i32: [1,2],[-1,0]];
+        let csr = CsrMatrix::from_dense(&a).prod(&CsrMatrix::from_dense(&b)).unwrap();
+        let csc = CscMatrix::from_dense(&a).prod(&CscMatrix::from_dense(&b)).unwrap();
+        assert_eq!(csr.to_dense() == mat![i32: [0,2],[-1,0]], true);
+        assert_eq!(csr.nnz(), 2);
+        assert_eq!(csc.to_dense() == mat![i32: [0,2],[-1,0]], true);
+        assert_eq!(csc.nnz(), 2);
+    }
+
+    #[test]
+    fn test_add() {
+        let a = sample();
+        let b = mat![
+            i32:
+                [1,0,-3,0],
+                [0,0,0,5],
+                [0,0,0,0],
+                [0,0,0,1]
+        ];
+        let res = &a + &b;
+        assert_eq!((&CsrMatrix::from_dense(&a) + &CsrMatrix::from_dense(&b)).to_dense() == res, true);
+        assert_eq!((&CscMatrix::from_dense(&a) + &CscMatrix::from_dense(&b)).to_dense() == res, true);
+        assert_eq!((&CooMatrix::from_dense(&a) + &CooMatrix::from_dense(&b)).to_dense() == res, true);
+
+        // 打ち消し合う要素は保持しない
+        let csr = &CsrMatrix::from_dense(&a) + &CsrMatrix::from_dense(&b);
+        assert_eq!(csr.nnz(), 5);
+        let neg = CsrMatrix::from_dense(&mat![i32: [0,0,-3,0],[-1,0,0,0],[0,0,0,0],[0,-2,0,-4]]);
+        assert_eq!((&CsrMatrix::from_dense(&a) + &neg).nnz(), 0);
+        let neg = CscMatrix::from_dense(&mat![i32: [0,0,-3,0],[-1,0,0,0],[0,0,0,0],[0,-2,0,-4]]);
+        assert_eq!((&CscMatrix::from_dense(&a) + &neg).nnz(), 0);
+        let coo = &CooMatrix::from_dense(&a) + &CooMatrix::from_dense(&b);
+        assert_eq!(coo.nnz(), 5);
+        assert_eq!(coo.to_dense() == res, true);
+        let neg = CooMatrix::from_dense(&mat![i32: [0,0,-3,0],[-1,0,0,0],[0,0,0,0],[0,-2,0,-4]]);
+        assert_eq!((&CooMatrix::from_dense(&a) + &neg).nnz(), 0);
+    }
+
+    #[test]
+    fn test_float() {
+        let m = mat![f64: [0.0,1.5],[2.5,0.0]];
+        let csr = CsrMatrix::from_dense(&m);
+        assert_eq!(csr.nnz(), 2);
+        assert_eq!(csr.mul_vec(&[2.0, 4.0]).unwrap(), vec![6.0, 5.0]);
+    }
+}
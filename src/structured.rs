@@ -0,0 +1,1071 @@
+//! 構造行列 / structured matrices
+//!
+//! 対角行列、三角行列、対称行列、帯行列、三重対角行列を、
+//! 構造上ゼロまたは重複となる元を保持しない形式で扱う。
+//! いずれも `StructuredMatrix<T>` トレイトを実装し、
+//! 構造に応じた計算量で行列式、連立一次方程式の解、ベクトルとの積を計算する。
+//! 密行列 `Matrix<T>` との間は `from_dense` / `to_dense` で相互に変換でき、
+//! 構造に合致しない密行列からの変換はErrを返却する。
+//!
+//! ```rust
+//! use matrixa::core::Matrix;
+//! use matrixa::mat;
+//! use matrixa::structured::{StructuredMatrix, Tridiagonal};
+//!
+//! let m = mat![f64: [2.0,-1.0,0.0],[-1.0,2.0,-1.0],[0.0,-1.0,2.0]];
+//! let t = Tridiagonal::from_dense(&m).unwrap();
+//! assert_eq!(t.det(), 4.0);
+//! let x = t.solve(&[1.0, 0.0, 1.0]).unwrap();
+//! assert!(x.iter().all(|v| (v - 1.0).abs() < 1e-12));
+//! assert_eq!(t.to_dense() == m, true);
+//! ```
+//!
+
+use crate::core::Matrix;
+use std::ops::{Add, Div, Mul, Sub};
+
+/// 構造行列共通インターフェース / common interface of structured matrices
+///
+/// solve および帯行列の det は除算を伴うため、浮動小数点型での利用を想定する。
+///
+pub trait StructuredMatrix<T> {
+    /// 次元(正方行列の行数)
+    fn dim(&self) -> usize;
+
+    /// 元の取得
+    ///
+    /// 行p, 列q の元を返却する。範囲外の位置を指定した場合はパニックする。
+    fn get(&self, p: usize, q: usize) -> T;
+
+    /// 行列式
+    fn det(&self) -> T;
+
+    /// 連立一次方程式 Ax = b の解
+    ///
+    /// 特異な行列についてはErrを返却する。
+    fn solve(&self, b: &[T]) -> Result<Vec<T>, &str>;
+
+    /// ベクトルとの積 Ax
+    fn mul_vec(&self, x: &[T]) -> Result<Vec<T>, &str>;
+
+    /// 密行列への変換
+    fn to_dense(&self) -> Matrix<T> {
+        let mut res = Matrix::new();
+        for i in 0..self.dim() {
+            res.data.push((0..self.dim()).map(|j| self.get(i, j)).collect());
+        }
+        res
+    }
+
+    /// 密行列との積
+    ///
+    /// 引数行列の各列についてベクトルとの積を計算し、Result型に格納した新規の密行列を返却する。
+    fn prod_dense(&self, m: &Matrix<T>) -> Result<Matrix<T>, &str>
+    where
+        T: Copy,
+    {
+        if m.data.len() != self.dim() {
+            return Err("row length of argument not matched to the dimension of the matrix");
+        }
+        let cols = m.data.first().map_or(0, |row| row.len());
+        let mut res = Matrix::new();
+        res.data = (0..self.dim()).map(|_| Vec::with_capacity(cols)).collect();
+        for j in 0..cols {
+            let col: Vec<T> = m.data.iter().map(|row| row[j]).collect();
+            for (row, datum) in res.data.iter_mut().zip(self.mul_vec(&col)?) {
+                row.push(datum);
+            }
+        }
+        Ok(res)
+    }
+}
+
+/// 密行列の正方性検証
+fn square_dim<T>(m: &Matrix<T>) -> Result<usize, &'static str> {
+    let n = m.data.len();
+    if m.data.iter().any(|row| row.len() != n) {
+        return Err("not a square matrix");
+    }
+    Ok(n)
+}
+
+/// 絶対値 (部分ピボット選択用)
+fn abs<T>(x: T) -> T
+where
+    T: Copy + Sub<Output = T> + PartialOrd + From<u8>,
+{
+    let zero = T::from(0x0u8);
+    if x < zero {
+        zero - x
+    } else {
+        x
+    }
+}
+
+fn check_len<T>(x: &[T], n: usize) -> Result<(), &'static str> {
+    if x.len() != n {
+        return Err("vector length not matched to the dimension of the matrix");
+    }
+    Ok(())
+}
+
+/// 対角行列 / diagonal matrix
+///
+/// 対角成分のみを保持する。
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiagonalMatrix<T> {
+    diag: Vec<T>,
+}
+
+/// 上三角行列 / upper triangular matrix
+///
+/// 対角成分を含む上三角部分を行優先で詰めて保持する。
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpperTriangular<T> {
+    n: usize,
+    data: Vec<T>,
+}
+
+/// 下三角行列 / lower triangular matrix
+///
+/// 対角成分を含む下三角部分を行優先で詰めて保持する。
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct LowerTriangular<T> {
+    n: usize,
+    data: Vec<T>,
+}
+
+/// 対称行列 / symmetric matrix
+///
+/// 対角成分を含む下三角部分のみを行優先で詰めて保持する(packed storage)。
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymmetricMatrix<T> {
+    n: usize,
+    data: Vec<T>,
+}
+
+/// 帯行列 / banded matrix
+///
+/// 下側帯幅 kl、上側帯幅 ku の帯の内側の元のみを保持する。
+/// 行iは列 i-kl から i+ku までの kl+ku+1 個の元を持ち、行列の範囲外となる位置はゼロで埋める。
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct BandedMatrix<T> {
+    n: usize,
+    kl: usize,
+    ku: usize,
+    data: Vec<Vec<T>>,
+}
+
+/// 三重対角行列 / tridiagonal matrix
+///
+/// 下副対角、対角、上副対角の3本のベクトルとして保持する。
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tridiagonal<T> {
+    lower: Vec<T>,
+    diag: Vec<T>,
+    upper: Vec<T>,
+}
+
+impl<T> DiagonalMatrix<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + PartialEq + From<u8>,
+{
+    /// 対角成分からの生成
+    ///
+    pub fn new(diag: Vec<T>) -> Self {
+        DiagonalMatrix { diag }
+    }
+
+    /// 密行列からの変換
+    ///
+    /// 非対角成分にゼロでない元を含む場合はErrを返却する。
+    ///
+    pub fn from_dense(m: &Matrix<T>) -> Result<Self, &str> {
+        let n = square_dim(m)?;
+        let zero = T::from(0x0u8);
+        for i in 0..n {
+            for j in 0..n {
+                if i != j && m.data[i][j] != zero {
+                    return Err("non-zero off-diagonal element for a diagonal matrix");
+                }
+            }
+        }
+        Ok(DiagonalMatrix::new((0..n).map(|i| m.data[i][i]).collect()))
+    }
+
+    /// 対角成分の参照
+    pub fn diag(&self) -> &Vec<T> {
+        &self.diag
+    }
+}
+
+impl<T> StructuredMatrix<T> for DiagonalMatrix<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + PartialEq + From<u8>,
+{
+    fn dim(&self) -> usize {
+        self.diag.len()
+    }
+
+    fn get(&self, p: usize, q: usize) -> T {
+        if p >= self.dim() || q >= self.dim() {
+            panic!("position ({}, {}) is out of order for the matrix", p, q);
+        }
+        if p == q {
+            self.diag[p]
+        } else {
+            T::from(0x0u8)
+        }
+    }
+
+    fn det(&self) -> T {
+        self.diag.iter().fold(T::from(0x1u8), |acc, d| acc * *d)
+    }
+
+    fn solve(&self, b: &[T]) -> Result<Vec<T>, &str> {
+        check_len(b, self.dim())?;
+        if self.diag.contains(&T::from(0x0u8)) {
+            return Err("singular matrix");
+        }
+        Ok(b.iter().zip(self.diag.iter()).map(|(x, d)| *x / *d).collect())
+    }
+
+    fn mul_vec(&self, x: &[T]) -> Result<Vec<T>, &str> {
+        check_len(x, self.dim())?;
+        Ok(x.iter().zip(self.diag.iter()).map(|(x, d)| *x * *d).collect())
+    }
+}
+
+impl<T> UpperTriangular<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + PartialEq + From<u8>,
+{
+    /// 行iの先頭(対角成分)の格納位置
+    fn offset(&self, i: usize) -> usize {
+        i * (2 * self.n - i + 1) / 2
+    }
+
+    /// 密行列からの変換
+    ///
+    /// 対角より下にゼロでない元を含む場合はErrを返却する。
+    ///
+    pub fn from_dense(m: &Matrix<T>) -> Result<Self, &str> {
+        let n = square_dim(m)?;
+        let zero = T::from(0x0u8);
+        let mut data = Vec::with_capacity(n * (n + 1) / 2);
+        for (i, row) in m.data.iter().enumerate() {
+            if row[..i].iter().any(|x| *x != zero) {
+                return Err("non-zero element below the diagonal for an upper triangular matrix");
+            }
+            data.extend_from_slice(&row[i..]);
+        }
+        Ok(UpperTriangular { n, data })
+    }
+
+    /// 転置
+    ///
+    pub fn transpose(&self) -> LowerTriangular<T> {
+        let mut data = Vec::with_capacity(self.data.len());
+        for i in 0..self.n {
+            for j in 0..=i {
+                data.push(self.data[self.offset(j) + i - j]);
+            }
+        }
+        LowerTriangular { n: self.n, data }
+    }
+}
+
+impl<T> StructuredMatrix<T> for UpperTriangular<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + PartialEq + From<u8>,
+{
+    fn dim(&self) -> usize {
+        self.n
+    }
+
+    fn get(&self, p: usize, q: usize) -> T {
+        if p >= self.n || q >= self.n {
+            panic!("position ({}, {}) is out of order for the matrix", p, q);
+        }
+        if q < p {
+            T::from(0x0u8)
+        } else {
+            self.data[self.offset(p) + q - p]
+        }
+    }
+
+    fn det(&self) -> T {
+        (0..self.n).fold(T::from(0x1u8), |acc, i| acc * self.data[self.offset(i)])
+    }
+
+    /// 後退代入による解
+    fn solve(&self, b: &[T]) -> Result<Vec<T>, &str> {
+        check_len(b, self.n)?;
+        let zero = T::from(0x0u8);
+        let mut x = vec![zero; self.n];
+        for i in (0..self.n).rev() {
+            let off = self.offset(i);
+            let mut acc = b[i];
+            for (j, xj) in x.iter().enumerate().skip(i + 1) {
+                acc = acc - self.data[off + j - i] * *xj;
+            }
+            if self.data[off] == zero {
+                return Err("singular matrix");
+            }
+            x[i] = acc / self.data[off];
+        }
+        Ok(x)
+    }
+
+    fn mul_vec(&self, x: &[T]) -> Result<Vec<T>, &str> {
+        check_len(x, self.n)?;
+        let mut res = Vec::with_capacity(self.n);
+        for i in 0..self.n {
+            let off = self.offset(i);
+            let mut acc = T::from(0x0u8);
+            for (j, xj) in x.iter().enumerate().skip(i) {
+                acc = acc + self.data[off + j - i] * *xj;
+            }
+            res.push(acc);
+        }
+        Ok(res)
+    }
+}
+
+impl<T> LowerTriangular<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + PartialEq + From<u8>,
+{
+    /// 行iの先頭の格納位置
+    fn offset(i: usize) -> usize {
+        i * (i + 1) / 2
+    }
+
+    /// 密行列からの変換
+    ///
+    /// 対角より上にゼロでない元を含む場合はErrを返却する。
+    ///
+    pub fn from_dense(m: &Matrix<T>) -> Result<Self, &str> {
+        let n = square_dim(m)?;
+        let zero = T::from(0x0u8);
+        let mut data = Vec::with_capacity(n * (n + 1) / 2);
+        for (i, row) in m.data.iter().enumerate() {
+            if row[(i + 1)..].iter().any(|x| *x != zero) {
+                return Err("non-zero element above the diagonal for a lower triangular matrix");
+            }
+            data.extend_from_slice(&row[..=i]);
+        }
+        Ok(LowerTriangular { n, data })
+    }
+
+    /// 転置
+    ///
+    pub fn transpose(&self) -> UpperTriangular<T> {
+        let mut data = Vec::with_capacity(self.data.len());
+        for i in 0..self.n {
+            for j in i..self.n {
+                data.push(self.data[Self::offset(j) + i]);
+            }
+        }
+        UpperTriangular { n: self.n, data }
+    }
+}
+
+impl<T> StructuredMatrix<T> for LowerTriangular<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + PartialEq + From<u8>,
+{
+    fn dim(&self) -> usize {
+        self.n
+    }
+
+    fn get(&self, p: usize, q: usize) -> T {
+        if p >= self.n || q >= self.n {
+            panic!("position ({}, {}) is out of order for the matrix", p, q);
+        }
+        if q > p {
+            T::from(0x0u8)
+        } else {
+            self.data[Self::offset(p) + q]
+        }
+    }
+
+    fn det(&self) -> T {
+        (0..self.n).fold(T::from(0x1u8), |acc, i| acc * self.data[Self::offset(i) + i])
+    }
+
+    /// 前進代入による解
+    fn solve(&self, b: &[T]) -> Result<Vec<T>, &str> {
+        check_len(b, self.n)?;
+        let zero = T::from(0x0u8);
+        let mut x = Vec::with_capacity(self.n);
+        for (i, bi) in b.iter().enumerate() {
+            let off = Self::offset(i);
+            let mut acc = *bi;
+            for (j, xj) in x.iter().enumerate() {
+                acc = acc - self.data[off + j] * *xj;
+            }
+            if self.data[off + i] == zero {
+                return Err("singular matrix");
+            }
+            x.push(acc / self.data[off + i]);
+        }
+        Ok(x)
+    }
+
+    fn mul_vec(&self, x: &[T]) -> Result<Vec<T>, &str> {
+        check_len(x, self.n)?;
+        let mut res = Vec::with_capacity(self.n);
+        for i in 0..self.n {
+            let off = Self::offset(i);
+            let mut acc = T::from(0x0u8);
+            for (j, xj) in x[..=i].iter().enumerate() {
+                acc = acc + self.data[off + j] * *xj;
+            }
+            res.push(acc);
+        }
+        Ok(res)
+    }
+}
+
+impl<T> SymmetricMatrix<T>
+where
+    T: Copy
+    + Add<Output = T>
+    + Sub<Output = T>
+    + Mul<Output = T>
+    + Div<Output = T>
+    + PartialEq
+    + PartialOrd
+    + From<u8>,
+{
+    /// 密行列からの変換
+    ///
+    /// 対称でない行列についてはErrを返却する。
+    ///
+    pub fn from_dense(m: &Matrix<T>) -> Result<Self, &str> {
+        let n = square_dim(m)?;
+        let mut data = Vec::with_capacity(n * (n + 1) / 2);
+        for i in 0..n {
+            for j in 0..=i {
+                if m.data[i][j] != m.data[j][i] {
+                    return Err("not a symmetric matrix");
+                }
+                data.push(m.data[i][j]);
+            }
+        }
+        Ok(SymmetricMatrix { n, data })
+    }
+
+    /// 詰めて保持した下三角部分の参照
+    pub fn packed(&self) -> &Vec<T> {
+        &self.data
+    }
+
+    fn index(p: usize, q: usize) -> usize {
+        let (i, j) = if p >= q { (p, q) } else { (q, p) };
+        i * (i + 1) / 2 + j
+    }
+}
+
+impl<T> StructuredMatrix<T> for SymmetricMatrix<T>
+where
+    T: Copy
+    + Add<Output = T>
+    + Sub<Output = T>
+    + Mul<Output = T>
+    + Div<Output = T>
+    + PartialEq
+    + PartialOrd
+    + From<u8>,
+{
+    fn dim(&self) -> usize {
+        self.n
+    }
+
+    fn get(&self, p: usize, q: usize) -> T {
+        if p >= self.n || q >= self.n {
+            panic!("position ({}, {}) is out of order for the matrix", p, q);
+        }
+        self.data[Self::index(p, q)]
+    }
+
+    /// 部分ピボット選択付きのガウスの消去法による行列式
+    fn det(&self) -> T {
+        BandedMatrix::from_structured(self, self.n.saturating_sub(1), self.n.saturating_sub(1)).det()
+    }
+
+    /// 部分ピボット選択付きのガウスの消去法による解
+    fn solve(&self, b: &[T]) -> Result<Vec<T>, &str> {
+        check_len(b, self.n)?;
+        BandedMatrix::from_structured(self, self.n.saturating_sub(1), self.n.saturating_sub(1))
+            .solve(b)
+            .map_err(|_| "singular matrix")
+    }
+
+    fn mul_vec(&self, x: &[T]) -> Result<Vec<T>, &str> {
+        check_len(x, self.n)?;
+        let mut res = Vec::with_capacity(self.n);
+        for i in 0..self.n {
+            let mut acc = T::from(0x0u8);
+            for (j, xj) in x.iter().enumerate() {
+                acc = acc + self.data[Self::index(i, j)] * *xj;
+            }
+            res.push(acc);
+        }
+        Ok(res)
+    }
+}
+
+impl<T> BandedMatrix<T>
+where
+    T: Copy
+    + Add<Output = T>
+    + Sub<Output = T>
+    + Mul<Output = T>
+    + Div<Output = T>
+    + PartialEq
+    + PartialOrd
+    + From<u8>,
+{
+    /// 全要素ゼロの帯行列生成
+    ///
+    pub fn new(n: usize, kl: usize, ku: usize) -> Self {
+        BandedMatrix {
+            n,
+            kl,
+            ku,
+            data: vec![vec![T::from(0x0u8); kl + ku + 1]; n],
+        }
+    }
+
+    fn from_structured<S: StructuredMatrix<T>>(m: &S, kl: usize, ku: usize) -> Self {
+        let mut res = BandedMatrix::new(m.dim(), kl, ku);
+        for i in 0..res.n {
+            for j in i.saturating_sub(kl)..std::cmp::min(res.n, i + ku + 1) {
+                res.data[i][j + kl - i] = m.get(i, j);
+            }
+        }
+        res
+    }
+
+    /// 密行列からの変換
+    ///
+    /// ゼロでない元を含む最小の帯幅 kl, ku を求め、帯行列として保持する。
+    ///
+    pub fn from_dense(m: &Matrix<T>) -> Result<Self, &str> {
+        square_dim(m)?;
+        let zero = T::from(0x0u8);
+        let (mut kl, mut ku) = (0, 0);
+        for (i, row) in m.data.iter().enumerate() {
+            for (j, datum) in row.iter().enumerate() {
+                if *datum != zero {
+                    if i > j {
+                        kl = std::cmp::max(kl, i - j);
+                    } else {
+                        ku = std::cmp::max(ku, j - i);
+                    }
+                }
+            }
+        }
+        Self::with_bandwidth(m, kl, ku)
+    }
+
+    /// 帯幅を指定した密行列からの変換
+    ///
+    /// 帯の外側にゼロでない元を含む場合はErrを返却する。
+    ///
+    pub fn with_bandwidth(m: &Matrix<T>, kl: usize, ku: usize) -> Result<Self, &str> {
+        let n = square_dim(m)?;
+        let zero = T::from(0x0u8);
+        let mut res = BandedMatrix::new(n, kl, ku);
+        for (i, row) in m.data.iter().enumerate() {
+            for (j, datum) in row.iter().enumerate() {
+                if j + kl >= i && j <= i + ku {
+                    res.data[i][j + kl - i] = *datum;
+                } else if *datum != zero {
+                    return Err("non-zero element outside of the band");
+                }
+            }
+        }
+        Ok(res)
+    }
+
+    /// 下側帯幅
+    pub fn kl(&self) -> usize {
+        self.kl
+    }
+
+    /// 上側帯幅
+    pub fn ku(&self) -> usize {
+        self.ku
+    }
+
+    /// 元の設定
+    ///
+    /// 行p, 列q に値を設定し、Result型に格納した自身への参照を返却する。
+    /// 帯の外側の位置を指定した場合はErrを返却する。
+    ///
+    pub fn set(&mut self, p: usize, q: usize, val: T) -> Result<&mut Self, &str> {
+        if p >= self.n || q >= self.n || q + self.kl < p || q > p + self.ku {
+            return Err("position is out of the band");
+        }
+        self.data[p][q + self.kl - p] = val;
+        Ok(self)
+    }
+
+    /// 部分ピボット選択付きの帯LU分解
+    ///
+    /// 消去後の各行を (先頭列, 元) の組として返却する。行交換により上側帯幅は最大 kl+ku に広がる。
+    /// 右辺が与えられた場合は同じ行操作を適用し、行交換の回数の偶奇を合わせて返却する。
+    ///
+    #[allow(clippy::type_complexity)]
+    fn eliminate(&self, mut rhs: Option<&mut Vec<T>>) -> Result<(Vec<(usize, Vec<T>)>, bool), &'static str> {
+        let n = self.n;
+        let zero = T::from(0x0u8);
+        let mut rows: Vec<(usize, Vec<T>)> = (0..n)
+            .map(|i| {
+                let start = i.saturating_sub(self.kl);
+                let end = std::cmp::min(n, i + self.ku + 1);
+                (start, (start..end).map(|j| self.data[i][j + self.kl - i]).collect())
+            })
+            .collect();
+        let at = |row: &(usize, Vec<T>), j: usize| -> T {
+            if j >= row.0 && j < row.0 + row.1.len() {
+                row.1[j - row.0]
+            } else {
+                zero
+            }
+        };
+
+        let mut odd = false;
+        for k in 0..n {
+            let last = std::cmp::min(n - 1, k + self.kl);
+            let mut p = k;
+            for i in (k + 1)..=last {
+                if abs(at(&rows[i], k)) > abs(at(&rows[p], k)) {
+                    p = i;
+                }
+            }
+            if at(&rows[p], k) == zero {
+                return Err("singular matrix");
+            }
+            if p != k {
+                rows.swap(k, p);
+                if let Some(b) = rhs.as_mut() {
+                    b.swap(k, p);
+                }
+                odd = !odd;
+            }
+
+            let (head, tail) = rows.split_at_mut(k + 1);
+            let pivot_row = &head[k];
+            let pivot = at(pivot_row, k);
+            let pivot_end = pivot_row.0 + pivot_row.1.len();
+            for (offset, row) in tail[..(last - k)].iter_mut().enumerate() {
+                let f = at(row, k) / pivot;
+                if f == zero {
+                    continue;
+                }
+                while row.0 + row.1.len() < pivot_end {
+                    row.1.push(zero);
+                }
+                for j in k..pivot_end {
+                    let idx = j - row.0;
+                    row.1[idx] = row.1[idx] - f * at(pivot_row, j);
+                }
+                if let Some(b) = rhs.as_mut() {
+                    b[k + 1 + offset] = b[k + 1 + offset] - f * b[k];
+                }
+            }
+        }
+        Ok((rows, odd))
+    }
+}
+
+impl<T> StructuredMatrix<T> for BandedMatrix<T>
+where
+    T: Copy
+    + Add<Output = T>
+    + Sub<Output = T>
+    + Mul<Output = T>
+    + Div<Output = T>
+    + PartialEq
+    + PartialOrd
+    + From<u8>,
+{
+    fn dim(&self) -> usize {
+        self.n
+    }
+
+    fn get(&self, p: usize, q: usize) -> T {
+        if p >= self.n || q >= self.n {
+            panic!("position ({}, {}) is out of order for the matrix", p, q);
+        }
+        if q + self.kl < p || q > p + self.ku {
+            T::from(0x0u8)
+        } else {
+            self.data[p][q + self.kl - p]
+        }
+    }
+
+    /// 帯LU分解による行列式 (O(n kl (kl+ku)))
+    ///
+    /// 消去の際に除算を行うため、整数型では切り捨てにより正確な値とならない場合がある。
+    ///
+    fn det(&self) -> T {
+        let zero = T::from(0x0u8);
+        match self.eliminate(None) {
+            Err(_) => zero,
+            Ok((rows, odd)) => {
+                let det = rows
+                    .iter()
+                    .enumerate()
+                    .fold(T::from(0x1u8), |acc, (k, row)| acc * row.1[k - row.0]);
+                if odd {
+                    zero - det
+                } else {
+                    det
+                }
+            }
+        }
+    }
+
+    /// 帯LU分解による解 (O(n kl (kl+ku)))
+    ///
+    /// 整数型では除算が切り捨てとなるため、厳密な解は得られない。
+    ///
+    fn solve(&self, b: &[T]) -> Result<Vec<T>, &str> {
+        check_len(b, self.n)?;
+        let mut rhs = b.to_vec();
+        let (rows, _) = self.eliminate(Some(&mut rhs))?;
+        let mut x = vec![T::from(0x0u8); self.n];
+        for i in (0..self.n).rev() {
+            let (start, row) = &rows[i];
+            let mut acc = rhs[i];
+            for j in (i + 1)..(start + row.len()) {
+                acc = acc - row[j - start] * x[j];
+            }
+            x[i] = acc / row[i - start];
+        }
+        Ok(x)
+    }
+
+    fn mul_vec(&self, x: &[T]) -> Result<Vec<T>, &str> {
+        check_len(x, self.n)?;
+        let mut res = Vec::with_capacity(self.n);
+        for i in 0..self.n {
+            let mut acc = T::from(0x0u8);
+            let end = std::cmp::min(self.n, i + self.ku + 1);
+            for (j, xj) in x.iter().enumerate().take(end).skip(i.saturating_sub(self.kl)) {
+                acc = acc + self.data[i][j + self.kl - i] * *xj;
+            }
+            res.push(acc);
+        }
+        Ok(res)
+    }
+}
+
+impl<T> Tridiagonal<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + PartialEq + From<u8>,
+{
+    /// 副対角・対角からの生成
+    ///
+    /// 副対角の長さが対角の長さより1短くない場合はErrを返却する。
+    ///
+    pub fn new(lower: Vec<T>, diag: Vec<T>, upper: Vec<T>) -> Result<Self, &'static str> {
+        let off = diag.len().saturating_sub(1);
+        if lower.len() != off || upper.len() != off {
+            return Err("sub-diagonal length must be one less than the diagonal length");
+        }
+        Ok(Tridiagonal { lower, diag, upper })
+    }
+
+    /// 密行列からの変換
+    ///
+    /// 三重対角の外側にゼロでない元を含む場合はErrを返却する。
+    ///
+    pub fn from_dense(m: &Matrix<T>) -> Result<Self, &str> {
+        let n = square_dim(m)?;
+        let zero = T::from(0x0u8);
+        for (i, row) in m.data.iter().enumerate() {
+            for (j, datum) in row.iter().enumerate() {
+                if (j + 1 < i || j > i + 1) && *datum != zero {
+                    return Err("non-zero element outside of the tridiagonal band");
+                }
+            }
+        }
+        Ok(Tridiagonal {
+            lower: (1..n).map(|i| m.data[i][i - 1]).collect(),
+            diag: (0..n).map(|i| m.data[i][i]).collect(),
+            upper: (1..n).map(|i| m.data[i - 1][i]).collect(),
+        })
+    }
+
+    /// 下副対角の参照
+    pub fn lower(&self) -> &Vec<T> {
+        &self.lower
+    }
+
+    /// 対角の参照
+    pub fn diag(&self) -> &Vec<T> {
+        &self.diag
+    }
+
+    /// 上副対角の参照
+    pub fn upper(&self) -> &Vec<T> {
+        &self.upper
+    }
+}
+
+impl<T> StructuredMatrix<T> for Tridiagonal<T>
+where
+    T: Copy
+    + Add<Output = T>
+    + Sub<Output = T>
+    + Mul<Output = T>
+    + Div<Output = T>
+    + PartialEq
+    + PartialOrd
+    + From<u8>,
+{
+    fn dim(&self) -> usize {
+        self.diag.len()
+    }
+
+    fn get(&self, p: usize, q: usize) -> T {
+        if p >= self.dim() || q >= self.dim() {
+            panic!("position ({}, {}) is out of order for the matrix", p, q);
+        }
+        if p == q {
+            self.diag[p]
+        } else if p == q + 1 {
+            self.lower[q]
+        } else if q == p + 1 {
+            self.upper[p]
+        } else {
+            T::from(0x0u8)
+        }
+    }
+
+    /// 三項漸化式による行列式 (O(n))
+    ///
+    /// 除算を行わないため、整数型でも正確な値となる。
+    ///
+    fn det(&self) -> T {
+        let (mut prev, mut cur) = (T::from(0x1u8), T::from(0x1u8));
+        for i in 0..self.dim() {
+            let next = if i == 0 {
+                self.diag[0]
+            } else {
+                self.diag[i] * cur - self.lower[i - 1] * self.upper[i - 1] * prev
+            };
+            prev = cur;
+            cur = next;
+        }
+        cur
+    }
+
+    /// Thomas法による解 (O(n))
+    ///
+    /// 途中でゼロのピボットが現れた場合は、部分ピボット選択付きの帯LU分解 (kl = ku = 1、O(n)) で解き直す。
+    /// 特異な行列についてはErrを返却する。
+    /// 整数型では各段の除算が切り捨てとなるため、厳密な解は得られない。
+    ///
+    fn solve(&self, b: &[T]) -> Result<Vec<T>, &str> {
+        let n = self.dim();
+        check_len(b, n)?;
+        let zero = T::from(0x0u8);
+        let mut cp = Vec::with_capacity(n);
+        let mut dp: Vec<T> = Vec::with_capacity(n);
+        for i in 0..n {
+            let beta = if i == 0 {
+                self.diag[0]
+            } else {
+                self.diag[i] - self.lower[i - 1] * cp[i - 1]
+            };
+            if beta == zero {
+                return BandedMatrix::from_structured(self, 1, 1)
+                    .solve(b)
+                    .map_err(|_| "singular matrix");
+            }
+            if i + 1 < n {
+                cp.push(self.upper[i] / beta);
+            }
+            let d = if i == 0 { b[0] } else { b[i] - self.lower[i - 1] * dp[i - 1] };
+            dp.push(d / beta);
+        }
+        let mut x = dp;
+        for i in (0..n.saturating_sub(1)).rev() {
+            x[i] = x[i] - cp[i] * x[i + 1];
+        }
+        Ok(x)
+    }
+
+    fn mul_vec(&self, x: &[T]) -> Result<Vec<T>, &str> {
+        let n = self.dim();
+        check_len(x, n)?;
+        let mut res = Vec::with_capacity(n);
+        for i in 0..n {
+            let mut acc = self.diag[i] * x[i];
+            if i > 0 {
+                acc = acc + self.lower[i - 1] * x[i - 1];
+            }
+            if i + 1 < n {
+                acc = acc + self.upper[i] * x[i + 1];
+            }
+            res.push(acc);
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests_structured_matrix {
+    use crate::core::Matrix;
+    use crate::mat;
+    use crate::structured::{
+        BandedMatrix, DiagonalMatrix, LowerTriangular, StructuredMatrix, SymmetricMatrix, Tridiagonal,
+        UpperTriangular,
+    };
+
+    fn assert_close(x: &[f64], y: &[f64]) {
+        for (a, b) in x.iter().zip(y.iter()) {
+            assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_diagonal() {
+        let m = mat![f64: [2.0,0.0,0.0],[0.0,4.0,0.0],[0.0,0.0,-1.0]];
+        let d = DiagonalMatrix::from_dense(&m).unwrap();
+        assert_eq!(d.to_dense() == m, true);
+        assert_eq!(d.det(), -8.0);
+        assert_eq!(d.solve(&[2.0, 2.0, 3.0]).unwrap(), vec![1.0, 0.5, -3.0]);
+        assert_eq!(d.mul_vec(&[1.0, 1.0, 1.0]).unwrap(), vec![2.0, 4.0, -1.0]);
+        assert_eq!(DiagonalMatrix::from_dense(&mat![f64: [1.0,1.0],[0.0,1.0]]).is_err(), true);
+        assert_eq!(DiagonalMatrix::new(vec![1.0, 0.0]).solve(&[1.0, 1.0]).is_err(), true);
+    }
+
+    #[test]
+    fn test_triangular() {
+        let m = mat![f64: [2.0,1.0,-1.0],[0.0,3.0,2.0],[0.0,0.0,4.0]];
+        let u = UpperTriangular::from_dense(&m).unwrap();
+        assert_eq!(u.to_dense() == m, true);
+        assert_eq!(u.det(), 24.0);
+        let x = [1.0, -1.0, 2.0];
+        let b = u.mul_vec(&x).unwrap();
+        assert_eq!(b, vec![-1.0, 1.0, 8.0]);
+        assert_close(&u.solve(&b).unwrap(), &x);
+
+        let l = u.transpose();
+        let mut t = m.clone();
+        t.transpose();
+        assert_eq!(l.to_dense() == t, true);
+        assert_eq!(LowerTriangular::from_dense(&t).unwrap() == l, true);
+        assert_eq!(l.det(), 24.0);
+        let b = l.mul_vec(&x).unwrap();
+        assert_close(&l.solve(&b).unwrap(), &x);
+        assert_eq!(l.transpose() == u, true);
+
+        assert_eq!(UpperTriangular::from_dense(&t).is_err(), true);
+        assert_eq!(LowerTriangular::from_dense(&m).is_err(), true);
+    }
+
+    #[test]
+    fn test_triangular_integer() {
+        let m = mat![i32: [1,0,0],[2,3,0],[4,5,6]];
+        let l = LowerTriangular::from_dense(&m).unwrap();
+        assert_eq!(l.det(), 18);
+        assert_eq!(l.prod_dense(&mat![i32: [1],[1],[1]]).unwrap() == mat![i32: [1],[5],[15]], true);
+    }
+
+    #[test]
+    fn test_symmetric() {
+        let m = mat![f64: [0.0,1.0,2.0],[1.0,0.0,3.0],[2.0,3.0,0.0]];
+        let s = SymmetricMatrix::from_dense(&m).unwrap();
+        assert_eq!(s.packed().len(), 6);
+        assert_eq!(s.to_dense() == m, true);
+        assert!((s.det() - m.det()).abs() < 1e-12);
+        let x = [1.0, 2.0, 3.0];
+        let b = s.mul_vec(&x).unwrap();
+        assert_eq!(b, vec![8.0, 10.0, 8.0]);
+        assert_close(&s.solve(&b).unwrap(), &x);
+        assert_eq!(SymmetricMatrix::from_dense(&mat![f64: [1.0,2.0],[3.0,1.0]]).is_err(), true);
+    }
+
+    #[test]
+    fn test_banded() {
+        let m = mat![
+            f64:
+                [1.0,2.0,0.0,0.0,0.0],
+                [3.0,1.0,1.0,0.0,0.0],
+                [1.0,4.0,2.0,5.0,0.0],
+                [0.0,2.0,6.0,1.0,1.0],
+                [0.0,0.0,1.0,3.0,2.0]
+        ];
+        let b = BandedMatrix::from_dense(&m).unwrap();
+        assert_eq!(b.kl(), 2);
+        assert_eq!(b.ku(), 1);
+        assert_eq!(b.to_dense() == m, true);
+        assert!((b.det() - m.det()).abs() < 1e-9);
+        let x = [1.0, -2.0, 3.0, 0.5, -1.0];
+        let rhs = b.mul_vec(&x).unwrap();
+        assert_eq!(rhs, (&m * &mat![f64: [1.0],[-2.0],[3.0],[0.5],[-1.0]]).col(0));
+        assert_close(&b.solve(&rhs).unwrap(), &x);
+        assert_eq!(BandedMatrix::with_bandwidth(&m, 1, 1).is_err(), true);
+    }
+
+    #[test]
+    fn test_banded_singular() {
+        let mut b = BandedMatrix::<f64>::new(3, 1, 1);
+        b.set(0, 0, 1.0).unwrap().set(0, 1, 2.0).unwrap().set(1, 0, 2.0).unwrap().set(1, 1, 4.0).unwrap();
+        assert_eq!(b.set(2, 0, 1.0).is_err(), true);
+        assert_eq!(b.det(), 0.0);
+        assert_eq!(b.solve(&[1.0, 1.0, 1.0]).is_err(), true);
+    }
+
+    #[test]
+    fn test_tridiagonal() {
+        let n = 6;
+        let t = Tridiagonal::new(vec![-1.0; n - 1], vec![2.0; n], vec![-1.0; n - 1]).unwrap();
+        // 1次元ラプラシアンの行列式は n+1
+        assert_eq!(t.det(), 7.0);
+        let x = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let b = t.mul_vec(&x).unwrap();
+        assert_eq!(b, vec![0.0, 0.0, 0.0, 0.0, 0.0, 7.0]);
+        assert_close(&t.solve(&b).unwrap(), &x);
+        assert_eq!(Tridiagonal::from_dense(&t.to_dense()).unwrap() == t, true);
+        assert_eq!(Tridiagonal::<f64>::new(vec![1.0], vec![1.0], vec![]).is_err(), true);
+        assert_eq!(Tridiagonal::from_dense(&mat![f64: [1.0,0.0,1.0],[0.0,1.0,0.0],[0.0,0.0,1.0]]).is_err(), true);
+    }
+
+    #[test]
+    fn test_tridiagonal_pivoting() {
+        let t = Tridiagonal::new(vec![1.0], vec![0.0, 0.0], vec![1.0]).unwrap();
+        assert_close(&t.solve(&[2.0, 3.0]).unwrap(), &[3.0, 2.0]);
+
+        // 第2段でゼロのピボットが現れる非特異な行列
+        let m = mat![f64: [1.0,1.0,0.0,0.0],[1.0,1.0,2.0,0.0],[0.0,1.0,3.0,1.0],[0.0,0.0,2.0,5.0]];
+        let t = Tridiagonal::from_dense(&m).unwrap();
+        let x = [1.0, -2.0, 0.5, 3.0];
+        let b = t.mul_vec(&x).unwrap();
+        assert_close(&t.solve(&b).unwrap(), &x);
+
+        let singular = Tridiagonal::new(vec![1.0], vec![1.0, 1.0], vec![1.0]).unwrap();
+        assert_eq!(singular.solve(&[1.0, 1.0]), Err("singular matrix"));
+    }
+
+    #[test]
+    fn test_tridiagonal_integer_det() {
+        let m = mat![i64: [2,3,0],[1,4,5],[0,6,7]];
+        assert_eq!(Tridiagonal::from_dense(&m).unwrap().det(), m.det());
+    }
+}
@@ -0,0 +1,322 @@
+//! Unicode文字列操作 / Unicode-aware string operations
+//!
+//! list.rs の `to_strlen` はバイト長を、`pop_char` は `char` 単位の操作を行うため、
+//! 結合文字や絵文字を含む文字列、全角文字を含む日本語の表データでは見た目と一致しない。
+//! 本モジュールでは書記素クラスタ (grapheme cluster) と東アジアの文字幅 (East Asian Width) に基づく
+//! 文字数の計測、表示幅による切り詰めと桁揃え、大文字・小文字変換を提供する。
+//! `unicode-normalization` フィーチャの有効時はNFC/NFKC正規化も利用できる。
+//!
+//! ```rust
+//! use matrixa::core::Matrix;
+//! use matrixa::mat;
+//!
+//! let mut m = mat![&str: ["東京", "Osaka"], ["が\u{3099}", "café"]].to_string();
+//! assert_eq!(m.to_strlen().dump()[0], vec![6, 5]);
+//! assert_eq!(m.char_count().dump()[1], vec![2, 4]);
+//! assert_eq!(m.grapheme_count().dump()[1], vec![1, 4]);
+//! assert_eq!(m.display_width().dump()[0], vec![4, 5]);
+//! m.pad_right(6, ' ');
+//! assert_eq!(m.dump()[0], vec!["東京  ", "Osaka "]);
+//! ```
+//!
+
+use crate::core::Matrix;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// 単語の先頭文字を大文字に、残りを小文字に変換する
+fn titlecase(s: &str) -> String {
+    let mut res = String::with_capacity(s.len());
+    for word in s.split_word_bounds() {
+        let mut chars = word.chars();
+        match chars.next() {
+            Some(first) if first.is_alphabetic() => {
+                res.extend(first.to_uppercase());
+                res.push_str(&chars.as_str().to_lowercase());
+            }
+            _ => res.push_str(word),
+        }
+    }
+    res
+}
+
+/// 表示幅が width 以下となるよう書記素クラスタ単位で切り詰める
+fn truncate(s: &mut String, width: usize) {
+    let mut total = 0;
+    for (idx, g) in s.grapheme_indices(true) {
+        total += g.width();
+        if total > width {
+            s.truncate(idx);
+            return;
+        }
+    }
+}
+
+/// 表示幅 width に不足する分の fill の個数
+///
+/// fill が全角文字の場合、幅を超えない個数とする。
+///
+fn fill_count(s: &str, width: usize, fill: char) -> usize {
+    let fill_width = fill.width().unwrap_or(0);
+    if fill_width == 0 {
+        panic!("fill character {:?} has no display width", fill);
+    }
+    width.saturating_sub(s.width()) / fill_width
+}
+
+/// Unicode文字列操作 / Unicode-aware string operations
+///
+impl Matrix<String> {
+    fn measure<F: Fn(&str) -> usize>(&self, f: F) -> Matrix<usize> {
+        let mut res = Matrix::new();
+        for row in self.data.iter() {
+            res.data.push(row.iter().map(|s| f(s)).collect());
+        }
+        res
+    }
+
+    fn edit_cells<F: Fn(&mut String)>(&mut self, f: F) -> &mut Self {
+        for s in self.data.iter_mut().flatten() {
+            f(s);
+        }
+        self
+    }
+
+    /// 大文字への変換
+    ///
+    pub fn to_uppercase(&mut self) -> &mut Self {
+        self.edit_cells(|s| *s = s.to_uppercase())
+    }
+
+    /// 小文字への変換
+    ///
+    pub fn to_lowercase(&mut self) -> &mut Self {
+        self.edit_cells(|s| *s = s.to_lowercase())
+    }
+
+    /// 単語ごとの先頭大文字への変換
+    ///
+    /// Unicodeの単語境界で区切った各単語の先頭文字を大文字に、残りを小文字に変換する。
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    /// use matrixa::mat;
+    ///
+    /// let mut m = mat![&str: ["hello WORLD", "élan vital"]].to_string();
+    /// m.to_titlecase();
+    /// assert_eq!(m.dump()[0], vec!["Hello World", "Élan Vital"]);
+    /// ```
+    ///
+    pub fn to_titlecase(&mut self) -> &mut Self {
+        self.edit_cells(|s| *s = titlecase(s))
+    }
+
+    /// 各元の文字 (`char`) の数
+    ///
+    pub fn char_count(&self) -> Matrix<usize> {
+        self.measure(|s| s.chars().count())
+    }
+
+    /// 各元の書記素クラスタの数
+    ///
+    /// 結合文字や異体字セレクタ、ZWJで連結した絵文字は一文字として数える。
+    ///
+    pub fn grapheme_count(&self) -> Matrix<usize> {
+        self.measure(|s| s.graphemes(true).count())
+    }
+
+    /// 各元の表示幅
+    ///
+    /// 全角文字 (East Asian Wide/Fullwidth) は2、結合文字は0として数える。
+    /// 曖昧幅 (Ambiguous) の文字は1とする。
+    ///
+    pub fn display_width(&self) -> Matrix<usize> {
+        self.measure(|s| s.width())
+    }
+
+    /// 末尾の書記素クラスタの削除
+    ///
+    /// `pop_char` と異なり、結合文字を含む一文字をまとめて削除する。
+    ///
+    pub fn pop_grapheme(&mut self) -> &mut Self {
+        self.edit_cells(|s| {
+            if let Some((idx, _)) = s.grapheme_indices(true).next_back() {
+                s.truncate(idx);
+            }
+        })
+    }
+
+    /// 表示幅による切り詰め
+    ///
+    /// 各元を表示幅が width 以下となるよう、書記素クラスタの境界で切り詰める。
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    /// use matrixa::mat;
+    ///
+    /// let mut m = mat![&str: ["日本語テキスト", "abcdef"]].to_string();
+    /// m.truncate_width(5);
+    /// assert_eq!(m.dump()[0], vec!["日本", "abcde"]);
+    /// ```
+    ///
+    pub fn truncate_width(&mut self, width: usize) -> &mut Self {
+        self.edit_cells(|s| truncate(s, width))
+    }
+
+    /// 左側の桁埋め
+    ///
+    /// 表示幅が width に満たない元の左側を fill で埋め、右揃えとする。
+    /// fill が全角文字の場合は width を超えない範囲で埋める。
+    /// 表示幅0の fill を指定した場合はpanicする。
+    ///
+    pub fn pad_left(&mut self, width: usize, fill: char) -> &mut Self {
+        self.edit_cells(|s| {
+            let n = fill_count(s, width, fill);
+            s.insert_str(0, &fill.to_string().repeat(n));
+        })
+    }
+
+    /// 右側の桁埋め
+    ///
+    /// 表示幅が width に満たない元の右側を fill で埋め、左揃えとする。
+    ///
+    pub fn pad_right(&mut self, width: usize, fill: char) -> &mut Self {
+        self.edit_cells(|s| {
+            let n = fill_count(s, width, fill);
+            s.push_str(&fill.to_string().repeat(n));
+        })
+    }
+
+    /// 両側の桁埋め
+    ///
+    /// 表示幅が width に満たない元の両側を fill で埋め、中央揃えとする。
+    /// 埋める数が奇数の場合は右側を一つ多くする。
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    /// use matrixa::mat;
+    ///
+    /// let mut m = mat![&str: ["名前", "id"]].to_string();
+    /// m.pad_center(7, '*');
+    /// assert_eq!(m.dump()[0], vec!["*名前**", "**id***"]);
+    /// ```
+    ///
+    pub fn pad_center(&mut self, width: usize, fill: char) -> &mut Self {
+        self.edit_cells(|s| {
+            let n = fill_count(s, width, fill);
+            let fill = fill.to_string();
+            *s = format!("{}{}{}", fill.repeat(n / 2), s, fill.repeat(n - n / 2));
+        })
+    }
+}
+
+/// Unicode正規化 / Unicode normalization
+///
+/// `unicode-normalization` フィーチャの有効時に利用可能。
+///
+#[cfg(feature = "unicode-normalization")]
+impl Matrix<String> {
+    /// NFC (正規分解後の正規合成) への正規化
+    ///
+    /// ```rust
+    /// use matrixa::core::Matrix;
+    /// use matrixa::mat;
+    ///
+    /// let mut m = mat![&str: ["か\u{3099}", "e\u{301}"]].to_string();
+    /// m.to_nfc();
+    /// assert_eq!(m.dump()[0], vec!["が", "é"]);
+    /// ```
+    ///
+    pub fn to_nfc(&mut self) -> &mut Self {
+        use unicode_normalization::UnicodeNormalization;
+        self.edit_cells(|s| *s = s.nfc().collect())
+    }
+
+    /// NFKC (互換分解後の正規合成) への正規化
+    ///
+    /// 半角カナを全角に、全角英数字を半角に揃える用途に用いる。
+    ///
+    pub fn to_nfkc(&mut self) -> &mut Self {
+        use unicode_normalization::UnicodeNormalization;
+        self.edit_cells(|s| *s = s.nfkc().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests_matrix_unicode {
+    use crate::core::Matrix;
+    use crate::mat;
+
+    #[test]
+    fn test_case_mapping() {
+        let mut m = mat![&str: ["straße", "ǆemal"], ["it's a TEST", "日本語 text"]].to_string();
+        m.to_uppercase();
+        assert_eq!(m.dump()[0], vec!["STRASSE", "ǄEMAL"]);
+        m.to_lowercase();
+        assert_eq!(m.dump()[1], vec!["it's a test", "日本語 text"]);
+        m.to_titlecase();
+        assert_eq!(m.dump()[0], vec!["Strasse", "Ǆemal"]);
+        assert_eq!(m.dump()[1], vec!["It's A Test", "日本語 Text"]);
+    }
+
+    #[test]
+    fn test_counts() {
+        let m = mat![&str: ["👨‍👩‍👧", "ｶﾀ"], ["", "Ａ1"]].to_string();
+        assert_eq!(m.char_count().dump()[0], vec![5, 2]);
+        assert_eq!(m.grapheme_count().dump()[0], vec![1, 2]);
+        assert_eq!(m.grapheme_count().dump()[1], vec![0, 2]);
+        assert_eq!(m.display_width().dump()[0], vec![2, 2]);
+        assert_eq!(m.display_width().dump()[1], vec![0, 3]);
+    }
+
+    #[test]
+    fn test_pop_grapheme() {
+        let mut m = mat![&str: ["ae\u{301}", "", "🇯🇵"]].to_string();
+        m.pop_grapheme();
+        assert_eq!(m.dump()[0], vec!["a", "", ""]);
+    }
+
+    #[test]
+    fn test_truncate_width() {
+        let mut m = mat![&str: ["a日本", "e\u{301}e\u{301}e\u{301}", "ab"]].to_string();
+        m.truncate_width(2);
+        assert_eq!(m.dump()[0], vec!["a", "e\u{301}e\u{301}", "ab"]);
+        m.truncate_width(0);
+        assert_eq!(m.dump()[0], vec!["", "", ""]);
+    }
+
+    #[test]
+    fn test_padding() {
+        let mut m = mat![&str: ["漢字", "abc", "toolong"]].to_string();
+        m.pad_left(6, '.');
+        assert_eq!(m.dump()[0], vec!["..漢字", "...abc", "toolong"]);
+        assert_eq!(m.display_width().dump()[0], vec![6, 6, 7]);
+
+        let mut m = mat![&str: ["漢字", "abc"]].to_string();
+        m.pad_right(7, '　');
+        assert_eq!(m.dump()[0], vec!["漢字　", "abc　　"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zero_width_fill() {
+        mat![&str: ["a"]].to_string().pad_left(3, '\u{301}');
+    }
+}
+
+#[cfg(all(test, feature = "unicode-normalization"))]
+mod tests_matrix_normalization {
+    use crate::core::Matrix;
+    use crate::mat;
+
+    #[test]
+    fn test_normalization() {
+        let mut m = mat![&str: ["ｶﾞｷﾞ", "ＡＢＣ１２３"], ["ﬁ", "が"]].to_string();
+        m.to_nfc();
+        assert_eq!(m.dump()[0], vec!["ｶﾞｷﾞ", "ＡＢＣ１２３"]);
+        m.to_nfkc();
+        assert_eq!(m.dump()[0], vec!["ガギ", "ABC123"]);
+        assert_eq!(m.dump()[1], vec!["fi", "が"]);
+        assert_eq!(m.grapheme_count().dump()[0], vec![2, 6]);
+    }
+}